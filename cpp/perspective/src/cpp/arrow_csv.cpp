@@ -598,11 +598,25 @@ parseAsArrowTimestamp(const std::string& input) {
     return std::nullopt;
 }
 
+// Re-type a timestamp[ms] column as date64, which shares its layout.
+static std::shared_ptr<arrow::ChunkedArray>
+timestampToDate(const std::shared_ptr<arrow::ChunkedArray>& column) {
+    arrow::ArrayVector chunks;
+    for (const auto& chunk : column->chunks()) {
+        auto data = chunk->data()->Copy();
+        data->type = arrow::date64();
+        chunks.push_back(arrow::MakeArray(data));
+    }
+
+    return std::make_shared<arrow::ChunkedArray>(chunks, arrow::date64());
+}
+
 std::shared_ptr<::arrow::Table>
 csvToTable(
     const std::string_view& csv,
     bool is_update,
-    std::unordered_map<std::string, std::shared_ptr<arrow::DataType>>& schema
+    std::unordered_map<std::string, std::shared_ptr<arrow::DataType>>& schema,
+    const CsvOptions& options
 ) {
     const arrow::io::IOContext& io_context = arrow::io::default_io_context();
     auto input = std::make_shared<arrow::io::BufferReader>(csv);
@@ -615,25 +629,93 @@ csvToTable(
     read_options.use_threads = false;
 #endif
     parse_options.newlines_in_values = true;
+    if (options.delimiter.has_value()) {
+        parse_options.delimiter = *options.delimiter;
+    }
+
+    if (options.quote.has_value()) {
+        parse_options.quote_char = *options.quote;
+    }
+
+    if (!options.column_names.empty()) {
+        read_options.column_names = options.column_names;
+        read_options.skip_rows = options.header ? 1 : 0;
+    } else if (!options.header) {
+        read_options.autogenerate_column_names = true;
+    }
+
+    if (!options.null_values.empty()) {
+        convert_options.null_values = options.null_values;
+        convert_options.strings_can_be_null = true;
+    }
+
+    if (options.decimal_point.has_value()) {
+        convert_options.decimal_point = *options.decimal_point;
+    }
+
+    std::vector<std::shared_ptr<arrow::TimestampParser>> parsers;
+    for (const auto& format : options.datetime_formats) {
+        parsers.push_back(arrow::TimestampParser::MakeStrptime(format));
+    }
 
-    if (is_update) {
-        convert_options.column_types = std::move(schema);
-        convert_options.timestamp_parsers = DATE_READERS;
-    } else {
-        convert_options.timestamp_parsers = DATE_PARSERS;
+    for (const auto& format : options.date_formats) {
+        parsers.push_back(arrow::TimestampParser::MakeStrptime(format));
     }
 
+    const auto& defaults = is_update ? DATE_READERS : DATE_PARSERS;
+    parsers.insert(parsers.end(), defaults.begin(), defaults.end());
+    convert_options.timestamp_parsers = std::move(parsers);
+
+    // The CSV reader only parses ISO dates, so with custom date formats, read
+    // date columns as timestamps and convert them afterwards.
+    std::vector<std::string> date_columns;
+    if (!options.date_formats.empty()) {
+        for (auto& [name, type] : schema) {
+            if (type->id() == arrow::Type::DATE32
+                || type->id() == arrow::Type::DATE64) {
+                date_columns.push_back(name);
+                type = arrow::timestamp(arrow::TimeUnit::MILLI);
+            }
+        }
+    }
+
+    convert_options.column_types = std::move(schema);
     auto maybe_reader = arrow::csv::TableReader::Make(
         io_context, input, read_options, parse_options, convert_options
     );
 
+    if (!maybe_reader.ok()) {
+        PSP_COMPLAIN_AND_ABORT(maybe_reader.status().ToString());
+    }
+
     std::shared_ptr<arrow::csv::TableReader> reader = *maybe_reader;
 
     auto maybe_table = reader->Read();
     if (!maybe_table.ok()) {
         PSP_COMPLAIN_AND_ABORT(maybe_table.status().ToString());
     }
-    return *maybe_table;
+
+    std::shared_ptr<arrow::Table> table = *maybe_table;
+    for (const auto& name : date_columns) {
+        auto idx = table->schema()->GetFieldIndex(name);
+        if (idx < 0) {
+            continue;
+        }
+
+        auto maybe_dated = table->SetColumn(
+            idx,
+            arrow::field(name, arrow::date64()),
+            timestampToDate(table->column(idx))
+        );
+
+        if (!maybe_dated.ok()) {
+            PSP_COMPLAIN_AND_ABORT(maybe_dated.status().ToString());
+        }
+
+        table = *maybe_dated;
+    }
+
+    return table;
 }
 
 } // namespace perspective::apachearrow
@@ -219,9 +219,11 @@ ArrowLoader::init_csv(
     const std::string_view& csv,
     bool is_update,
     std::unordered_map<std::string, std::shared_ptr<arrow::DataType>>&
-        psp_schema
+        psp_schema,
+    const CsvOptions& options
 ) {
-    m_table = deduplicate_table(csvToTable(csv, is_update, psp_schema));
+    m_table =
+        deduplicate_table(csvToTable(csv, is_update, psp_schema, options));
     std::shared_ptr<arrow::Schema> schema = m_table->schema();
     std::vector<std::shared_ptr<arrow::Field>> fields = schema->fields();
     for (const auto& field : fields) {
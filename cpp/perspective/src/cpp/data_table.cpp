@@ -20,6 +20,7 @@
 #include <perspective/tracing.h>
 #include <perspective/utils.h>
 
+#include <algorithm>
 #include <sstream>
 #include <utility>
 namespace perspective {
@@ -481,6 +482,71 @@ t_data_table::clear() {
     m_size = 0;
 }
 
+namespace {
+
+// A filter term bound to the column it reads, or a group of bound terms.
+struct t_bound_fterm {
+    t_fterm m_fterm;
+    const t_column* m_column = nullptr;
+    std::vector<t_bound_fterm> m_children;
+
+    bool
+    operator()(t_uindex ridx) const {
+        auto child_passes = [ridx](const t_bound_fterm& child) {
+            return child(ridx);
+        };
+
+        switch (m_fterm.m_op) {
+            case FILTER_OP_AND: {
+                return std::all_of(
+                    m_children.begin(), m_children.end(), child_passes
+                );
+            }
+            case FILTER_OP_OR: {
+                // Like an empty `AND`, an empty group matches every row.
+                return m_children.empty()
+                    || std::any_of(
+                           m_children.begin(), m_children.end(), child_passes
+                    );
+            }
+            default: {
+                if (m_fterm.m_use_interned) {
+                    t_tscalar cell_val;
+                    cell_val.set(*(m_column->get_nth<t_uindex>(ridx)));
+                    cell_val.set_status(*(m_column->get_nth_status(ridx)));
+                    return m_fterm(cell_val);
+                }
+
+                return m_fterm(m_column->get_scalar(ridx));
+            }
+        }
+    }
+};
+
+t_bound_fterm
+bind_fterm(t_data_table& tbl, const t_fterm& fterm) {
+    t_bound_fterm bound{fterm};
+    if (fterm.is_group()) {
+        for (const auto& child : fterm.m_children) {
+            bound.m_children.push_back(bind_fterm(tbl, child));
+        }
+
+        return bound;
+    }
+
+    auto column = tbl.get_column(fterm.m_colname);
+    bound.m_column = column.get();
+    bound.m_fterm.coerce_numeric(column->get_dtype());
+    if (bound.m_fterm.m_use_interned) {
+        t_tscalar& thr = bound.m_fterm.m_threshold;
+        thr.set(column->get_interned(thr.get_char_ptr()));
+    }
+
+    return bound;
+}
+
+} // namespace
+
 t_mask
 t_data_table::filter_cpp(
     t_filter_op combiner, const std::vector<t_fterm>& fterms_
@@ -489,6 +555,23 @@ t_data_table::filter_cpp(
     auto fterms = fterms_;
 
     t_mask mask(size());
+
+    // Nested groups are evaluated as a tree, one row at a time.
+    bool has_groups =
+        std::any_of(fterms.begin(), fterms.end(), [](const t_fterm& ft) {
+            return ft.is_group();
+        });
+
+    if (has_groups) {
+        auto root = bind_fterm(*self, t_fterm(combiner, fterms));
+        for (t_uindex ridx = 0, rloop_end = size(); ridx < rloop_end;
+             ++ridx) {
+            mask.set(ridx, root(ridx));
+        }
+
+        return mask;
+    }
+
     t_uindex fterm_size = fterms.size();
     std::vector<t_uindex> indices(fterm_size);
     std::vector<const t_column*> columns(fterm_size);
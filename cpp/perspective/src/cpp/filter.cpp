@@ -53,6 +53,13 @@ t_fterm::t_fterm(
         && threshold.m_type == DTYPE_STR;
 }
 
+t_fterm::t_fterm(t_filter_op op, const std::vector<t_fterm>& children) :
+    m_op(op),
+    m_negated(false),
+    m_is_primary(false),
+    m_use_interned(false),
+    m_children(children) {}
+
 void
 t_fterm::coerce_numeric(t_dtype dtype) {
     m_threshold.set(m_threshold.coerce_numeric_dtype(dtype));
@@ -64,6 +71,19 @@ t_fterm::coerce_numeric(t_dtype dtype) {
 std::string
 t_fterm::get_expr() const {
     std::stringstream ss;
+    if (is_group()) {
+        ss << "(";
+        for (t_uindex idx = 0; idx < m_children.size(); ++idx) {
+            if (idx > 0) {
+                ss << " " << filter_op_to_str(m_op) << " ";
+            }
+
+            ss << m_children[idx].get_expr();
+        }
+
+        ss << ")";
+        return ss.str();
+    }
 
     ss << m_colname << " ";
 
@@ -28,6 +28,7 @@
 #include <chrono>
 #include <cstdint>
 #include <cstring>
+#include <functional>
 #include <limits>
 #include <memory>
 #include <perspective/server.h>
@@ -861,6 +862,47 @@ column_type_to_dtype(const proto::ColumnType& t) {
     }
 }
 
+static std::optional<char>
+first_char(const std::string& s) {
+    if (s.empty()) {
+        return std::nullopt;
+    }
+
+    return s[0];
+}
+
+// Unpack the wire `CsvOptions`. Column type overrides are returned separately
+// as a schema, as they only apply when creating a `Table`.
+static std::pair<apachearrow::CsvOptions, t_schema>
+csv_options_from_proto(const proto::CsvOptions& opts) {
+    apachearrow::CsvOptions options;
+    options.delimiter = first_char(opts.delimiter());
+    options.quote = first_char(opts.quote());
+    options.decimal_point = first_char(opts.decimal_point());
+    options.header = !opts.has_header() || opts.header();
+    options.column_names = {
+        opts.column_names().begin(), opts.column_names().end()
+    };
+    options.null_values = {
+        opts.null_values().begin(), opts.null_values().end()
+    };
+    options.date_formats = {
+        opts.date_formats().begin(), opts.date_formats().end()
+    };
+    options.datetime_formats = {
+        opts.datetime_formats().begin(), opts.datetime_formats().end()
+    };
+
+    std::vector<std::string> names;
+    std::vector<t_dtype> types;
+    for (const auto& pair : opts.column_types()) {
+        names.push_back(pair.name());
+        types.push_back(column_type_to_dtype(pair.type()));
+    }
+
+    return {options, t_schema(names, types)};
+}
+
 struct ValidViewPort {
     std::uint32_t start_row;
     std::uint32_t end_row;
@@ -951,6 +993,9 @@ needs_poll(const proto::Request::ClientReqCase proto_case) {
         case ReqCase::kViewRemoveOnUpdateReq:
         case ReqCase::kServerSystemInfoReq:
         case ReqCase::kGetFeaturesReq:
+        case ReqCase::kServerSessionsReq:
+        case ReqCase::kServerCloseSessionReq:
+        case ReqCase::kServerDeleteViewReq:
             return false;
         case proto::Request::CLIENT_REQ_NOT_SET:
             throw std::runtime_error("Unhandled request type 2");
@@ -999,6 +1044,9 @@ entity_type_is_table(const proto::Request::ClientReqCase proto_case) {
         case ReqCase::kViewExpressionSchemaReq:
         case ReqCase::kViewRemoveOnUpdateReq:
         case ReqCase::kRemoveHostedTablesUpdateReq:
+        case ReqCase::kServerSessionsReq:
+        case ReqCase::kServerCloseSessionReq:
+        case ReqCase::kServerDeleteViewReq:
             return false;
         case proto::Request::CLIENT_REQ_NOT_SET:
             throw std::runtime_error("Unhandled request type 2");
@@ -1217,6 +1265,101 @@ coerce_to(const t_dtype dtype, const A& val) {
     }
 }
 
+// Write `filter` (and the clauses of its group, if it is one) to `f`.
+static void
+fterm_to_proto(const t_fterm& filter, proto::ViewConfig_Filter* f) {
+    if (filter.is_group()) {
+        auto* group = f->mutable_group();
+        group->set_filter_op(
+            filter.m_op == FILTER_OP_OR
+                ? proto::ViewConfig_FilterReducer::ViewConfig_FilterReducer_OR
+                : proto::ViewConfig_FilterReducer::ViewConfig_FilterReducer_AND
+        );
+
+        for (const auto& child : filter.m_children) {
+            fterm_to_proto(child, group->add_filter());
+        }
+
+        return;
+    }
+
+    f->set_column(filter.m_colname);
+    f->set_op(filter_op_to_str(filter.m_op));
+    auto vals = std::vector<t_tscalar>(filter.m_bag.size());
+    if (filter.m_op != FILTER_OP_NOT_IN && filter.m_op != FILTER_OP_IN) {
+        vals.push_back(filter.m_threshold);
+    } else {
+        for (const auto& scalar : filter.m_bag) {
+            vals.push_back(scalar);
+        }
+    }
+
+    for (const auto& scalar : vals) {
+        auto* s = f->mutable_value()->Add();
+        switch (scalar.get_dtype()) {
+            case DTYPE_BOOL:
+                s->set_bool_(scalar.get<bool>());
+                break;
+            case DTYPE_FLOAT32:
+                s->set_float_(scalar.get<float>());
+                break;
+            case DTYPE_FLOAT64:
+                s->set_float_(scalar.get<double>());
+                break;
+            case DTYPE_INT8:
+                s->set_float_((double)scalar.get<std::int8_t>());
+                break;
+            case DTYPE_INT16:
+                s->set_float_((double)scalar.get<std::int16_t>());
+                break;
+            case DTYPE_INT32:
+                s->set_float_((double)scalar.get<std::int32_t>());
+                break;
+            case DTYPE_INT64:
+                s->set_float_((double)scalar.get<std::int64_t>());
+                break;
+            case DTYPE_UINT8:
+                s->set_float_((double)scalar.get<std::uint8_t>());
+                break;
+            case DTYPE_UINT16:
+                s->set_float_((double)scalar.get<std::uint16_t>());
+                break;
+            case DTYPE_UINT32:
+                s->set_float_((double)scalar.get<std::uint32_t>());
+                break;
+            case DTYPE_UINT64:
+                s->set_float_((double)scalar.get<std::uint64_t>());
+                break;
+            case DTYPE_STR:
+                s->set_string(scalar.get<const char*>());
+                break;
+            case DTYPE_DATE: {
+                auto tm = scalar.get<t_date>();
+                std::stringstream ss;
+                ss << std::setfill('0') << std::setw(4) << tm.year() << "-"
+                   << std::setfill('0')
+                   << std::setw(2)
+                   // Increment month by 1, as date::month is [1-12]
+                   // but t_date::month() is [0-11]
+                   << tm.month() + 1 << "-" << std::setfill('0')
+                   << std::setw(2) << tm.day();
+                s->set_string(ss.str());
+                break;
+            }
+            case DTYPE_TIME:
+                s->set_float_((double)scalar.get<t_time>().raw_value());
+                break;
+            case DTYPE_NONE:
+                s->set_null(::google::protobuf::NullValue::NULL_VALUE);
+                break;
+            default:
+                PSP_COMPLAIN_AND_ABORT(
+                    "Invalid scalar type: " + scalar.to_string()
+                );
+        }
+    }
+}
+
 std::vector<ProtoServerResp<ProtoServer::Response>>
 ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
     static bool is_init_expr = false;
@@ -1383,9 +1526,13 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 }
                 case proto::MakeTableData::kFromCsv: {
                     std::string data = r.data().from_csv();
+                    auto [options, column_types] =
+                        csv_options_from_proto(r.data().csv_options());
                     { auto _ = std::move(req); }
 
-                    table = Table::from_csv(index, std::move(data), limit);
+                    table = Table::from_csv(
+                        index, std::move(data), limit, options, column_types
+                    );
                     break;
                 }
                 case proto::MakeTableData::kFromCols: {
@@ -1556,7 +1703,9 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                     break;
                 }
                 case proto::MakeTableData::kFromCsv: {
-                    table->update_csv(r.data().from_csv(), 0);
+                    auto options =
+                        csv_options_from_proto(r.data().csv_options()).first;
+                    table->update_csv(r.data().from_csv(), 0, options);
                     break;
                 }
                 case proto::MakeTableData::kFromRows: {
@@ -1612,25 +1761,35 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
         case proto::Request::kTableUpdateReq: {
             const auto& r = req.table_update_req();
             auto table = m_resources.get_table(req.entity_id());
+            t_uindex num_rows = 0;
             switch (r.data().data_case()) {
                 case proto::MakeTableData::kFromArrow: {
-                    table->update_arrow(r.data().from_arrow(), r.port_id());
+                    num_rows =
+                        table->update_arrow(r.data().from_arrow(), r.port_id());
                     break;
                 }
                 case proto::MakeTableData::kFromCsv: {
-                    table->update_csv(r.data().from_csv(), r.port_id());
+                    auto options =
+                        csv_options_from_proto(r.data().csv_options()).first;
+                    num_rows = table->update_csv(
+                        r.data().from_csv(), r.port_id(), options
+                    );
                     break;
                 }
                 case proto::MakeTableData::kFromRows: {
-                    table->update_rows(r.data().from_rows(), r.port_id());
+                    num_rows =
+                        table->update_rows(r.data().from_rows(), r.port_id());
                     break;
                 }
                 case proto::MakeTableData::kFromCols: {
-                    table->update_cols(r.data().from_cols(), r.port_id());
+                    num_rows =
+                        table->update_cols(r.data().from_cols(), r.port_id());
                     break;
                 }
                 case proto::MakeTableData::kFromNdjson: {
-                    table->update_ndjson(r.data().from_ndjson(), r.port_id());
+                    num_rows = table->update_ndjson(
+                        r.data().from_ndjson(), r.port_id()
+                    );
                     break;
                 }
                 case proto::MakeTableData::kFromSchema:
@@ -1643,7 +1802,7 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
 
             m_resources.mark_table_dirty(req.entity_id());
             proto::Response resp;
-            resp.mutable_table_update_resp();
+            resp.mutable_table_update_resp()->set_num_rows(num_rows);
             push_resp(std::move(resp));
             break;
         }
@@ -1764,10 +1923,26 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             std::vector<
                 std::tuple<std::string, std::string, std::vector<t_tscalar>>>
                 filter;
-            filter.reserve(cfg.filter().size());
+            // Nested filter groups are flattened in prefix order, see
+            // `t_view_config`.
+            std::vector<const proto::ViewConfig_Filter*> filters;
+            std::function<void(const proto::ViewConfig_Filter&)> flatten =
+                [&](const proto::ViewConfig_Filter& f) {
+                    filters.push_back(&f);
+                    if (f.has_group()) {
+                        for (const auto& child : f.group().filter()) {
+                            flatten(child);
+                        }
+                    }
+                };
 
             for (const auto& f : cfg.filter()) {
-                for (const auto& arg : f.value()) {
+                flatten(f);
+            }
+
+            filter.reserve(filters.size());
+            for (const auto* f : filters) {
+                for (const auto& arg : f->value()) {
                     switch (arg.scalar_case()) {
                         case proto::Scalar::kString: {
 #ifdef PSP_SSO_SCALAR
@@ -1788,10 +1963,35 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                 }
             }
 
-            for (const auto& f : cfg.filter()) {
+            for (const auto* f : filters) {
+                if (f->has_group()) {
+                    t_tscalar size;
+                    size.set(
+                        static_cast<std::uint64_t>(f->group().filter_size())
+                    );
+
+                    auto op = f->group().filter_op()
+                            == proto::ViewConfig_FilterReducer::
+                                ViewConfig_FilterReducer_OR
+                        ? "or"
+                        : "and";
+
+                    filter.emplace_back("", op, std::vector<t_tscalar>{size});
+                    continue;
+                }
+
+                // `and` and `or` are reserved for group headers, which are
+                // only produced from a `group` above.
+                if (f->op() == "and" || f->op() == "or") {
+                    PSP_COMPLAIN_AND_ABORT(
+                        "Invalid filter op '" + f->op() + "' for column '"
+                        + f->column() + "'"
+                    );
+                }
+
                 std::vector<t_tscalar> args;
-                args.reserve(f.value().size());
-                for (const auto& arg : f.value()) {
+                args.reserve(f->value().size());
+                for (const auto& arg : f->value()) {
                     t_tscalar a;
                     a.clear();
                     switch (arg.scalar_case()) {
@@ -1802,16 +2002,17 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                         }
                         case proto::Scalar::kFloat: {
                             a = coerce_to(
-                                schema->get_dtype(f.column()), arg.float_()
+                                schema->get_dtype(f->column()), arg.float_()
                             );
 
                             args.push_back(a);
                             break;
                         }
                         case proto::Scalar::kString: {
-                            if (!schema->has_column(f.column())) {
+                            if (!schema->has_column(f->column())) {
                                 PSP_COMPLAIN_AND_ABORT(
-                                    "Filter column not in schema: " + f.column()
+                                    "Filter column not in schema: "
+                                    + f->column()
                                 );
                             }
 
@@ -1819,7 +2020,7 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                             if (!t_tscalar::can_store_inplace(arg.string())) {
 #endif
                                 a = coerce_to(
-                                    schema->get_dtype(f.column()),
+                                    schema->get_dtype(f->column()),
                                     vocab.unintern_c(
                                         vocab.get_interned(arg.string())
                                     )
@@ -1828,7 +2029,7 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                             } else {
 
                                 a = coerce_to(
-                                    schema->get_dtype(f.column()),
+                                    schema->get_dtype(f->column()),
                                     arg.string().c_str()
                                 );
                             }
@@ -1849,7 +2050,7 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
                     }
                 }
 
-                filter.emplace_back(f.column(), f.op(), args);
+                filter.emplace_back(f->column(), f->op(), args);
             }
 
             const auto& cols = cfg.columns();
@@ -2084,88 +2285,7 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             }
 
             for (const auto& filter : view_config->get_fterm()) {
-                auto* proto_filter = view_config_proto->mutable_filter();
-                auto* f = proto_filter->Add();
-                f->set_column(filter.m_colname);
-                f->set_op(filter_op_to_str(filter.m_op));
-                auto vals = std::vector<t_tscalar>(filter.m_bag.size());
-                if (filter.m_op != FILTER_OP_NOT_IN
-                    && filter.m_op != FILTER_OP_IN) {
-                    vals.push_back(filter.m_threshold);
-                } else {
-                    for (const auto& scalar : filter.m_bag) {
-                        vals.push_back(scalar);
-                    }
-                }
-
-                for (const auto& scalar : vals) {
-                    auto* s = f->mutable_value()->Add();
-                    switch (scalar.get_dtype()) {
-                        case DTYPE_BOOL:
-                            s->set_bool_(scalar.get<bool>());
-                            break;
-                        case DTYPE_FLOAT32:
-                            s->set_float_(scalar.get<float>());
-                            break;
-                        case DTYPE_FLOAT64:
-                            s->set_float_(scalar.get<double>());
-                            break;
-                        case DTYPE_INT8:
-                            s->set_float_((double)scalar.get<std::int8_t>());
-                            break;
-                        case DTYPE_INT16:
-                            s->set_float_((double)scalar.get<std::int16_t>());
-                            break;
-                        case DTYPE_INT32:
-                            s->set_float_((double)scalar.get<std::int32_t>());
-                            break;
-                        case DTYPE_INT64:
-                            s->set_float_((double)scalar.get<std::int64_t>());
-                            break;
-                        case DTYPE_UINT8:
-                            s->set_float_((double)scalar.get<std::uint8_t>());
-                            break;
-                        case DTYPE_UINT16:
-                            s->set_float_((double)scalar.get<std::uint16_t>());
-                            break;
-                        case DTYPE_UINT32:
-                            s->set_float_((double)scalar.get<std::uint32_t>());
-                            break;
-                        case DTYPE_UINT64:
-                            s->set_float_((double)scalar.get<std::uint64_t>());
-                            break;
-                        case DTYPE_STR:
-                            s->set_string(scalar.get<const char*>());
-                            break;
-                        case DTYPE_DATE: {
-                            auto tm = scalar.get<t_date>();
-                            std::stringstream ss;
-                            ss << std::setfill('0') << std::setw(4) << tm.year()
-                               << "-" << std::setfill('0')
-                               << std::setw(2)
-                               // Increment month by 1, as date::month is [1-12]
-                               // but t_date::month() is [0-11]
-                               << tm.month() + 1 << "-" << std::setfill('0')
-                               << std::setw(2) << tm.day();
-                            s->set_string(ss.str());
-                            break;
-                        }
-                        case DTYPE_TIME:
-                            s->set_float_(
-                                (double)scalar.get<t_time>().raw_value()
-                            );
-                            break;
-                        case DTYPE_NONE:
-                            s->set_null(
-                                ::google::protobuf::NullValue::NULL_VALUE
-                            );
-                            break;
-                        default:
-                            PSP_COMPLAIN_AND_ABORT(
-                                "Invalid scalar type: " + scalar.to_string()
-                            );
-                    }
-                }
+                fterm_to_proto(filter, view_config_proto->add_filter());
             }
 
             switch (view_config->get_filter_op()) {
@@ -2554,6 +2674,12 @@ ProtoServer::_handle_request(std::uint32_t client_id, Request&& req) {
             push_resp(std::move(resp));
             break;
         }
+        case proto::Request::kServerSessionsReq:
+        case proto::Request::kServerCloseSessionReq:
+        case proto::Request::kServerDeleteViewReq: {
+            PSP_COMPLAIN_AND_ABORT("Administration requests are handled by the host server")
+            break;
+        }
         case proto::Request::CLIENT_REQ_NOT_SET: {
             PSP_COMPLAIN_AND_ABORT("Client request unknown variant")
             break;
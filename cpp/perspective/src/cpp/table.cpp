@@ -340,11 +340,15 @@ schema_to_arrow_map(const t_schema& gnode_output_schema) {
     return map;
 }
 
-void
-Table::update_csv(const std::string_view& data, std::uint32_t port_id) {
+t_uindex
+Table::update_csv(
+    const std::string_view& data,
+    std::uint32_t port_id,
+    const apachearrow::CsvOptions& options
+) {
     auto type_map = schema_to_arrow_map(get_gnode()->get_output_schema());
     apachearrow::ArrowLoader arrow_loader;
-    arrow_loader.init_csv(data, true, type_map);
+    arrow_loader.init_csv(data, true, type_map, options);
     std::uint32_t row_count = 0;
     row_count = arrow_loader.row_count();
     t_data_table data_table(get_schema());
@@ -356,17 +360,21 @@ Table::update_csv(const std::string_view& data, std::uint32_t port_id) {
     process_op_column(data_table, t_op::OP_INSERT);
     calculate_offset(row_count);
     m_pool->send(get_gnode()->get_id(), port_id, data_table);
+    return row_count;
 }
 
 std::shared_ptr<Table>
 Table::from_csv(
-    const std::string& index, std::string&& data, std::uint32_t limit
+    const std::string& index,
+    std::string&& data,
+    std::uint32_t limit,
+    const apachearrow::CsvOptions& options,
+    const t_schema& column_types
 ) {
-    auto map =
-        std::unordered_map<std::string, std::shared_ptr<arrow::DataType>>();
-
+    // Explicit column types, where given, replace inference.
+    auto map = schema_to_arrow_map(column_types);
     apachearrow::ArrowLoader arrow_loader;
-    arrow_loader.init_csv(data, false, map);
+    arrow_loader.init_csv(data, false, map, options);
 
     std::vector<std::string> column_names;
     std::vector<t_dtype> data_types;
@@ -898,7 +906,7 @@ Table::remove_cols(const std::string_view& data) {
     m_pool->send(get_gnode()->get_id(), 0, data_table);
 }
 
-void
+t_uindex
 Table::update_cols(const std::string_view& data, std::uint32_t port_id) {
     // 1.) Infer schema
     rapidjson::Document document;
@@ -988,6 +996,7 @@ Table::update_cols(const std::string_view& data, std::uint32_t port_id) {
     process_op_column(data_table, t_op::OP_INSERT);
     calculate_offset(nrows);
     m_pool->send(get_gnode()->get_id(), port_id, data_table);
+    return nrows;
 }
 
 std::shared_ptr<Table>
@@ -1114,13 +1123,13 @@ Table::from_cols(
 // document.Accept(writer);
 // std::cout << buffer.GetString() << std::endl;
 
-void
+t_uindex
 Table::update_rows(const std::string_view& data, std::uint32_t port_id) {
     // 1.) Infer schema
     rapidjson::Document document;
     document.Parse(data.data());
     if (document.Size() == 0) {
-        return;
+        return 0;
     }
 
     if (!document[0].IsObject()) {
@@ -1223,6 +1232,7 @@ Table::update_rows(const std::string_view& data, std::uint32_t port_id) {
     process_op_column(data_table, t_op::OP_INSERT);
     calculate_offset(size);
     m_pool->send(get_gnode()->get_id(), port_id, data_table);
+    return size;
 }
 
 std::shared_ptr<Table>
@@ -1358,13 +1368,13 @@ Table::from_rows(
     return tbl;
 }
 
-void
+t_uindex
 Table::update_ndjson(const std::string_view& data, std::uint32_t port_id) {
     rapidjson::Document document;
     rapidjson::StringStream s(data.data());
     document.ParseStream<rapidjson::kParseStopWhenDoneFlag>(s);
     if (document.Size() == 0) {
-        return;
+        return 0;
     }
 
     if (!document.IsObject()) {
@@ -1468,6 +1478,7 @@ Table::update_ndjson(const std::string_view& data, std::uint32_t port_id) {
     process_op_column(data_table, t_op::OP_INSERT);
     calculate_offset(ii);
     m_pool->send(get_gnode()->get_id(), port_id, data_table);
+    return ii;
 }
 
 std::shared_ptr<Table>
@@ -1651,7 +1662,7 @@ Table::from_schema(
     return tbl;
 }
 
-void
+t_uindex
 Table::update_arrow(const std::string_view& data, std::uint32_t port_id) {
     apachearrow::ArrowLoader arrow_loader;
     arrow_loader.initialize(
@@ -1683,6 +1694,7 @@ Table::update_arrow(const std::string_view& data, std::uint32_t port_id) {
     process_op_column(data_table, t_op::OP_INSERT);
     calculate_offset(row_count);
     m_pool->send(get_gnode()->get_id(), port_id, data_table);
+    return row_count;
 }
 
 std::shared_ptr<Table>
@@ -101,6 +101,18 @@ t_view_config::validate(const std::shared_ptr<t_schema>& schema) {
 
     for (const auto& filter : m_filter) {
         const std::string& col = std::get<0>(filter);
+        const std::string& op = std::get<1>(filter);
+        if (op == "and" || op == "or") {
+            if (!col.empty() || std::get<2>(filter).size() != 1) {
+                std::stringstream ss;
+                ss << "Invalid filter op '" << op << "' for column '" << col
+                   << "' found in View filters." << '\n';
+                PSP_COMPLAIN_AND_ABORT(ss.str());
+            }
+
+            continue;
+        }
+
         if (!schema->has_column(col) && expression_aliases.count(col) == 0) {
             std::stringstream ss;
             ss << "Invalid column '" << col << "' found in View filters."
@@ -359,24 +371,39 @@ t_view_config::fill_aggspecs(const std::shared_ptr<t_schema>& schema) {
 
 void
 t_view_config::fill_fterm() {
-    for (auto filter : m_filter) {
-        t_filter_op op = str_to_filter_op(std::get<1>(filter));
-        switch (op) {
-            case FILTER_OP_NOT_IN:
-            case FILTER_OP_IN: {
-                m_fterm.emplace_back(
-                    std::get<0>(filter), op, mktscalar(0), std::get<2>(filter)
-                );
-            } break;
-            default: {
-                t_tscalar filter_term = std::get<2>(filter)[0];
-                m_fterm.emplace_back(
-                    std::get<0>(filter),
-                    op,
-                    filter_term,
-                    std::vector<t_tscalar>()
-                );
+    t_uindex idx = 0;
+    while (idx < m_filter.size()) {
+        m_fterm.push_back(make_fterm(idx));
+    }
+}
+
+t_fterm
+t_view_config::make_fterm(t_uindex& idx) const {
+    const auto& filter = m_filter[idx++];
+    t_filter_op op = str_to_filter_op(std::get<1>(filter));
+    switch (op) {
+        case FILTER_OP_AND:
+        case FILTER_OP_OR: {
+            auto size = std::get<2>(filter)[0].to_uint64();
+            std::vector<t_fterm> children;
+            children.reserve(size);
+            for (t_uindex i = 0; i < size && idx < m_filter.size(); ++i) {
+                children.push_back(make_fterm(idx));
             }
+
+            return {op, children};
+        }
+        case FILTER_OP_NOT_IN:
+        case FILTER_OP_IN: {
+            return {
+                std::get<0>(filter), op, mktscalar(0), std::get<2>(filter)
+            };
+        }
+        default: {
+            t_tscalar filter_term = std::get<2>(filter)[0];
+            return {
+                std::get<0>(filter), op, filter_term, std::vector<t_tscalar>()
+            };
         }
     }
 }
@@ -11,7 +11,9 @@
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
 #pragma once
+#include <optional>
 #include <unordered_map>
+#include <vector>
 #include <arrow/io/memory.h>
 #include <arrow/table.h>
 
@@ -20,6 +22,27 @@ namespace apachearrow {
 
     std::optional<int64_t> parseAsArrowTimestamp(const std::string& input);
 
+    /**
+     * @brief Overrides for CSV parsing. Unset fields fall back to the
+     * auto-detecting defaults.
+     */
+    struct CsvOptions {
+        std::optional<char> delimiter;
+        std::optional<char> quote;
+        bool header = true;
+
+        // Column names to use instead of (or, if `header` is false, in
+        // absence of) the header row.
+        std::vector<std::string> column_names;
+        std::vector<std::string> null_values;
+
+        // `strptime` formats, tried before the built-in parsers. Date formats
+        // only apply to columns typed as dates by the schema.
+        std::vector<std::string> date_formats;
+        std::vector<std::string> datetime_formats;
+        std::optional<char> decimal_point;
+    };
+
     /**
      * @brief Initialize the arrow loader with a CSV.
      *
@@ -29,7 +52,8 @@ namespace apachearrow {
         const std::string_view& csv,
         bool is_update,
         std::unordered_map<std::string, std::shared_ptr<arrow::DataType>>&
-            schema
+            schema,
+        const CsvOptions& options = {}
     );
 
 } // namespace apachearrow
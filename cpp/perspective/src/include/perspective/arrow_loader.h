@@ -50,7 +50,8 @@ namespace apachearrow {
             const std::string_view& csv,
             bool is_update,
             std::unordered_map<std::string, std::shared_ptr<arrow::DataType>>&
-                schema
+                schema,
+            const CsvOptions& options = {}
         );
 
         /**
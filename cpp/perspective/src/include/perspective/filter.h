@@ -148,6 +148,20 @@ struct PERSPECTIVE_EXPORT t_fterm {
         bool is_primary
     );
 
+    /**
+     * @brief A group of filter terms, combined by `op`, which must be
+     * `FILTER_OP_AND` or `FILTER_OP_OR`.
+     *
+     * @param op
+     * @param children
+     */
+    t_fterm(t_filter_op op, const std::vector<t_fterm>& children);
+
+    inline bool
+    is_group() const {
+        return m_op == FILTER_OP_AND || m_op == FILTER_OP_OR;
+    }
+
     inline bool
     operator()(t_tscalar s) const {
         bool rv;
@@ -177,6 +191,7 @@ struct PERSPECTIVE_EXPORT t_fterm {
     bool m_negated;
     bool m_is_primary;
     bool m_use_interned;
+    std::vector<t_fterm> m_children;
 };
 
 class PERSPECTIVE_EXPORT t_filter {
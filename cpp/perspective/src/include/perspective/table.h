@@ -18,6 +18,7 @@
 #include <perspective/gnode.h>
 #include <perspective/pool.h>
 #include <perspective/data_table.h>
+#include <perspective/arrow_csv.h>
 
 namespace perspective {
 
@@ -194,17 +195,24 @@ public:
     void remove_cols(const std::string_view& data);
     void remove_rows(const std::string_view& data);
 
-    void update_arrow(const std::string_view& data, std::uint32_t port_id);
-    void update_csv(const std::string_view& data, std::uint32_t port_id);
-    void update_rows(const std::string_view& data, std::uint32_t port_id);
-    void update_cols(const std::string_view& data, std::uint32_t port_id);
-    void update_ndjson(const std::string_view& data, std::uint32_t port_id);
+    // The `update_*` methods return the number of rows in `data`.
+    t_uindex update_arrow(const std::string_view& data, std::uint32_t port_id);
+    t_uindex update_csv(
+        const std::string_view& data,
+        std::uint32_t port_id,
+        const apachearrow::CsvOptions& options = {}
+    );
+    t_uindex update_rows(const std::string_view& data, std::uint32_t port_id);
+    t_uindex update_cols(const std::string_view& data, std::uint32_t port_id);
+    t_uindex update_ndjson(const std::string_view& data, std::uint32_t port_id);
     // void update_cols(const std::string_view& data) const;
 
     static std::shared_ptr<Table> from_csv(
         const std::string& index,
         std::string&& data,
-        std::uint32_t limit = std::numeric_limits<std::uint32_t>::max()
+        std::uint32_t limit = std::numeric_limits<std::uint32_t>::max(),
+        const apachearrow::CsvOptions& options = {},
+        const t_schema& column_types = t_schema()
     );
 
     static std::shared_ptr<Table> from_cols(
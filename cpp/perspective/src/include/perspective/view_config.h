@@ -41,7 +41,8 @@ public:
      * @param column_pivots
      * @param aggregates
      * @param columns
-     * @param filter
+     * @param filter `(column, op, args)` clauses. A nested group of clauses
+     * is `("", "and" | "or", {size})`, followed by its `size` clauses.
      * @param sort
      */
     t_view_config(
@@ -152,6 +153,15 @@ private:
      */
     void fill_fterm();
 
+    /**
+     * @brief Make the `t_fterm` for the clause of `m_filter` at `idx` (and
+     * the clauses of its group, if it is one), advancing `idx` past them.
+     *
+     * @param idx
+     * @return t_fterm
+     */
+    t_fterm make_fterm(t_uindex& idx) const;
+
     /**
      * @brief Fill the `m_sortspec` vectors with `t_sortspec` objects which
      * define the view's sorting.
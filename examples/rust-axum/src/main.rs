@@ -13,11 +13,13 @@
 use std::fs::File;
 use std::io::Read;
 use std::net::SocketAddr;
+use std::time::Duration;
 
 use axum::routing::get_service;
 use axum::Router;
 use perspective::client::{TableInitOptions, UpdateData};
-use perspective::server::Server;
+use perspective::file_table::{FileTable, FileTableOptions};
+use perspective::server::{ExecutionStrategy, LocalClient, PollLoopOptions, Server, ServerOptions};
 use tower_http::services::{ServeDir, ServeFile};
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::filter::LevelFilter;
@@ -47,6 +49,21 @@ async fn load_server_arrow(server: &Server) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Host the file at `path` as "my_data_source" instead, which follows the
+/// file as it is appended to or rewritten for as long as the returned
+/// [`FileTable`] is alive. The [`FileTable`] updates through the returned
+/// client, so drop it before closing the client.
+async fn load_server_file(
+    server: &Server,
+    path: &str,
+) -> Result<(LocalClient, FileTable), AppError> {
+    let client = server.new_local_client();
+    let mut options = FileTableOptions::default();
+    options.table.set_name("my_data_source");
+    let file = FileTable::open(&client, path, options).await?;
+    Ok((client, file))
+}
+
 /// Host a combination HTTP file server + WebSocket server, which serves a
 /// simple Perspective application. The app's HTML, etc., assets are served
 /// from the root, while the app's embedded WebAssembly [`perspective::Client`]
@@ -55,6 +72,7 @@ async fn start_web_server_and_block(server: Server) -> Result<(), AppError> {
     let app = Router::new()
         .route("/", get_service(ServeFile::new("src/index.html")))
         .route("/ws", perspective::axum::websocket_handler())
+        .route("/metrics", perspective::prometheus::metrics_handler())
         .fallback_service(ServeDir::new(ROOT_PATH))
         .with_state(server)
         .layer(TraceLayer::new_for_http());
@@ -72,8 +90,35 @@ async fn main() -> Result<(), AppError> {
         .with(layer().compact().with_filter(LevelFilter::INFO))
         .init();
 
-    let server = Server::default();
-    load_server_arrow(&server).await?;
-    start_web_server_and_block(server).await?;
-    Ok(())
+    // Run engine calls on a dedicated thread so that a long-running query from
+    // one WebSocket doesn't stall the `tokio` workers serving the others.
+    let server = Server::new(ServerOptions {
+        execution: ExecutionStrategy::WorkerThread,
+        ..ServerOptions::default()
+    })?;
+
+    // Flush updates to every connected WebSocket promptly, rather than only
+    // when each happens to send a request of its own.
+    server.spawn_poll_loop(PollLoopOptions {
+        coalesce: Some(Duration::from_millis(10)),
+        ..PollLoopOptions::default()
+    });
+
+    // Serve a live view of a CSV, NDJSON, Arrow or Parquet file given on the
+    // command line, or the example Arrow file otherwise.
+    let file = match std::env::args().nth(1) {
+        Some(path) => Some(load_server_file(&server, &path).await?),
+        None => {
+            load_server_arrow(&server).await?;
+            None
+        },
+    };
+
+    let result = start_web_server_and_block(server).await;
+    if let Some((client, file)) = file {
+        drop(file);
+        client.close().await;
+    }
+
+    result
 }
@@ -32,7 +32,8 @@ use std::fs;
 
 use perspective_client::config::*;
 use perspective_client::{
-    OnUpdateOptions, TableInitOptions, UpdateOptions, ViewOnUpdateResp, ViewWindow,
+    IngestReport, OnUpdateOptions, ParquetCompression, TableInitOptions, UpdateOptions,
+    ViewOnUpdateResp, ViewWindow,
 };
 use perspective_viewer::config::ViewerConfigUpdate;
 use ts_rs::TS;
@@ -68,6 +69,8 @@ pub fn generate_type_bindings_js() -> Result<(), Box<dyn Error>> {
     ViewOnUpdateResp::export_all_to(&path)?;
     OnUpdateOptions::export_all_to(&path)?;
     UpdateOptions::export_all_to(&path)?;
+    ParquetCompression::export_all_to(&path)?;
+    IngestReport::export_all_to(&path)?;
     ViewWindow::export_all_to(&path)?;
     Ok(())
 }
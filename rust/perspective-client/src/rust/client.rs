@@ -14,6 +14,7 @@ use std::collections::HashMap;
 use std::error::Error;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU32;
+use std::time::{Duration, SystemTime};
 
 use async_lock::{Mutex, RwLock};
 use futures::future::{BoxFuture, LocalBoxFuture, join_all};
@@ -22,13 +23,17 @@ use nanoid::*;
 use prost::Message;
 use serde::{Deserialize, Serialize};
 
+use crate::config::ViewConfig;
+use crate::ingest::{self, IngestReport};
 use crate::proto::request::ClientReq;
 use crate::proto::response::ClientResp;
 use crate::proto::{
     self, ColumnType, GetFeaturesReq, GetFeaturesResp, GetHostedTablesReq, GetHostedTablesResp,
-    HostedTable, MakeTableReq, RemoveHostedTablesUpdateReq, Request, Response, ServerSystemInfoReq,
+    HostedTable, MakeTableData, MakeTableReq, RemoveHostedTablesUpdateReq, Request, Response,
+    ServerCloseSessionReq, ServerDeleteViewReq, ServerSessionsReq, ServerSessionsResp,
+    ServerSystemInfoReq,
 };
-use crate::table::{Table, TableInitOptions, TableOptions};
+use crate::table::{Table, TableInitOptions, TableOptions, UpdateOptions};
 use crate::table_data::{TableData, UpdateData};
 use crate::utils::*;
 use crate::view::ViewWindow;
@@ -47,6 +52,89 @@ impl From<proto::ServerSystemInfoResp> for SystemInfo {
     }
 }
 
+/// Administrative metadata about a single `Session` of a `Server`, e.g. a
+/// connected [`Client`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub id: u32,
+    pub connected_since: SystemTime,
+    pub views: Vec<SessionViewInfo>,
+    pub bytes_sent: u64,
+
+    /// The authenticated identity attached to this session by its transport,
+    /// if any.
+    pub principal: Option<String>,
+}
+
+impl SessionInfo {
+    /// The total number of `View::on_update` subscriptions across this
+    /// session's views.
+    pub fn on_update_subscriptions(&self) -> u32 {
+        self.views.iter().map(|x| x.on_update_subscriptions).sum()
+    }
+}
+
+/// Administrative metadata about a `View` owned by a [`SessionInfo`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionViewInfo {
+    pub view_id: String,
+    pub table_id: String,
+    pub config: ViewConfig,
+    pub on_update_subscriptions: u32,
+}
+
+impl From<proto::server_sessions_resp::SessionInfo> for SessionInfo {
+    fn from(value: proto::server_sessions_resp::SessionInfo) -> Self {
+        SessionInfo {
+            id: value.id,
+            connected_since: SystemTime::UNIX_EPOCH
+                + Duration::from_secs_f64(value.connected_since / 1000.0),
+            views: value.views.into_iter().map(|x| x.into()).collect(),
+            bytes_sent: value.bytes_sent,
+            principal: value.principal,
+        }
+    }
+}
+
+impl From<SessionInfo> for proto::server_sessions_resp::SessionInfo {
+    fn from(value: SessionInfo) -> Self {
+        let connected_since = value
+            .connected_since
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+
+        proto::server_sessions_resp::SessionInfo {
+            id: value.id,
+            connected_since: connected_since.as_secs_f64() * 1000.0,
+            views: value.views.into_iter().map(|x| x.into()).collect(),
+            bytes_sent: value.bytes_sent,
+            principal: value.principal,
+        }
+    }
+}
+
+impl From<proto::server_sessions_resp::SessionViewInfo> for SessionViewInfo {
+    fn from(value: proto::server_sessions_resp::SessionViewInfo) -> Self {
+        SessionViewInfo {
+            view_id: value.view_id,
+            table_id: value.table_id,
+            config: value.config.map(|x| x.into()).unwrap_or_default(),
+            on_update_subscriptions: value.on_update_subscriptions,
+        }
+    }
+}
+
+impl From<SessionViewInfo> for proto::server_sessions_resp::SessionViewInfo {
+    fn from(value: SessionViewInfo) -> Self {
+        proto::server_sessions_resp::SessionViewInfo {
+            view_id: value.view_id,
+            table_id: value.table_id,
+            config: Some(crate::config::ViewConfigUpdate::from(value.config).into()),
+            on_update_subscriptions: value.on_update_subscriptions,
+        }
+    }
+}
+
 /// Metadata about what features are supported by the `Server` this `Client`
 /// is connected to.
 pub type Features = Arc<GetFeaturesResp>;
@@ -61,6 +149,53 @@ impl GetFeaturesResp {
     }
 }
 
+impl ClientReq {
+    /// A stable, `snake_case` name for this request variant, e.g. for a metric
+    /// label or an error message (without formatting the request itself).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::GetFeaturesReq(_) => "get_features",
+            Self::GetHostedTablesReq(_) => "get_hosted_tables",
+            Self::RemoveHostedTablesUpdateReq(_) => "remove_hosted_tables_update",
+            Self::TableMakePortReq(_) => "table_make_port",
+            Self::TableMakeViewReq(_) => "table_make_view",
+            Self::TableSchemaReq(_) => "table_schema",
+            Self::TableSizeReq(_) => "table_size",
+            Self::TableValidateExprReq(_) => "table_validate_expr",
+            Self::ViewColumnPathsReq(_) => "view_column_paths",
+            Self::ViewDeleteReq(_) => "view_delete",
+            Self::ViewDimensionsReq(_) => "view_dimensions",
+            Self::ViewExpressionSchemaReq(_) => "view_expression_schema",
+            Self::ViewGetConfigReq(_) => "view_get_config",
+            Self::ViewSchemaReq(_) => "view_schema",
+            Self::ViewToArrowReq(_) => "view_to_arrow",
+            Self::ServerSystemInfoReq(_) => "server_system_info",
+            Self::ViewCollapseReq(_) => "view_collapse",
+            Self::ViewExpandReq(_) => "view_expand",
+            Self::ViewGetMinMaxReq(_) => "view_get_min_max",
+            Self::ViewOnUpdateReq(_) => "view_on_update",
+            Self::ViewRemoveOnUpdateReq(_) => "view_remove_on_update",
+            Self::ViewSetDepthReq(_) => "view_set_depth",
+            Self::ViewToColumnsStringReq(_) => "view_to_columns_string",
+            Self::ViewToCsvReq(_) => "view_to_csv",
+            Self::ViewToRowsStringReq(_) => "view_to_rows_string",
+            Self::ViewToNdjsonStringReq(_) => "view_to_ndjson_string",
+            Self::MakeTableReq(_) => "make_table",
+            Self::TableDeleteReq(_) => "table_delete",
+            Self::TableOnDeleteReq(_) => "table_on_delete",
+            Self::TableRemoveDeleteReq(_) => "table_remove_delete",
+            Self::TableRemoveReq(_) => "table_remove",
+            Self::TableReplaceReq(_) => "table_replace",
+            Self::TableUpdateReq(_) => "table_update",
+            Self::ViewOnDeleteReq(_) => "view_on_delete",
+            Self::ViewRemoveDeleteReq(_) => "view_remove_delete",
+            Self::ServerSessionsReq(_) => "server_sessions",
+            Self::ServerCloseSessionReq(_) => "server_close_session",
+            Self::ServerDeleteViewReq(_) => "server_delete_view",
+        }
+    }
+}
+
 type BoxFn<I, O> = Box<dyn Fn(I) -> O + Send + Sync + 'static>;
 type Box2Fn<I, J, O> = Box<dyn Fn(I, J) -> O + Send + Sync + 'static>;
 
@@ -290,6 +425,47 @@ impl Client {
 
     #[doc = include_str!("../../docs/client/table.md")]
     pub async fn table(&self, input: TableData, options: TableInitOptions) -> ClientResult<Table> {
+        if options.ingest.is_some() {
+            Ok(self.table_with_report(input, options).await?.0)
+        } else {
+            self.table_inner(input, options).await
+        }
+    }
+
+    #[doc = include_str!("../../docs/client/table_with_report.md")]
+    pub async fn table_with_report(
+        &self,
+        input: TableData,
+        options: TableInitOptions,
+    ) -> ClientResult<(Table, IngestReport)> {
+        let (ingest, data) = match (options.ingest.clone(), input) {
+            (Some(ingest), TableData::Update(data)) => (ingest, data),
+            (_, input) => {
+                let table = self.table_inner(input, options).await?;
+                return Ok((table, IngestReport::default()));
+            },
+        };
+
+        let input = ingest::parse(data)?;
+        let schema = ingest::infer_schema(&input);
+        let validated = ingest::validate(input, &schema.iter().cloned().collect(), ingest.mode)?;
+        let table = self.table_inner(TableData::Schema(schema), options).await?;
+        table
+            .update_inner(validated.data, UpdateOptions::default())
+            .await?;
+
+        if let Some(name) = &ingest.rejects_table {
+            ingest::route_rejects(self, name, &validated.report, validated.rejects).await?;
+        }
+
+        Ok((table, validated.report))
+    }
+
+    pub(crate) async fn table_inner(
+        &self,
+        input: TableData,
+        options: TableInitOptions,
+    ) -> ClientResult<Table> {
         let entity_id = match options.name.clone() {
             Some(x) => x.to_owned(),
             None => nanoid!(),
@@ -298,8 +474,9 @@ impl Client {
         if let TableData::View(view) = &input {
             let window = ViewWindow::default();
             let arrow = view.to_arrow(window).await?;
+            let data = UpdateData::Arrow(arrow).into_proto(None, None)?;
             let mut table = self
-                .crate_table_inner(UpdateData::Arrow(arrow).into(), options.into(), entity_id)
+                .crate_table_inner(data, options.into(), entity_id)
                 .await?;
 
             let callback = {
@@ -328,14 +505,15 @@ impl Client {
             table.view_update_token = Some(on_update_token);
             Ok(table)
         } else {
-            self.crate_table_inner(input, options.into(), entity_id)
+            let data = input.into_proto(options.csv.as_ref(), options.parquet.as_ref())?;
+            self.crate_table_inner(data, options.into(), entity_id)
                 .await
         }
     }
 
     async fn crate_table_inner(
         &self,
-        input: TableData,
+        data: MakeTableData,
         options: TableOptions,
         entity_id: String,
     ) -> ClientResult<Table> {
@@ -343,7 +521,7 @@ impl Client {
             msg_id: self.gen_id(),
             entity_id: entity_id.clone(),
             client_req: Some(ClientReq::MakeTableReq(MakeTableReq {
-                data: Some(input.into()),
+                data: Some(data),
                 options: Some(options.clone().try_into()?),
             })),
         };
@@ -469,4 +647,55 @@ impl Client {
             resp => Err(resp.into()),
         }
     }
+
+    /// List the `Session`s of the connected `Server`, with their views and
+    /// subscriptions. Requires the `Server` to have granted this [`Client`]'s
+    /// session administrative access.
+    pub async fn server_sessions(&self) -> ClientResult<Vec<SessionInfo>> {
+        let msg = Request {
+            msg_id: self.gen_id(),
+            entity_id: "".to_string(),
+            client_req: Some(ClientReq::ServerSessionsReq(ServerSessionsReq {})),
+        };
+
+        match self.oneshot(&msg).await? {
+            ClientResp::ServerSessionsResp(ServerSessionsResp { sessions }) => {
+                Ok(sessions.into_iter().map(|x| x.into()).collect())
+            },
+            resp => Err(resp.into()),
+        }
+    }
+
+    /// Forcibly close another `Session` of the connected `Server` by id, as
+    /// reported by [`Client::server_sessions`]. Requires administrative
+    /// access.
+    pub async fn server_close_session(&self, session_id: u32) -> ClientResult<()> {
+        let msg = Request {
+            msg_id: self.gen_id(),
+            entity_id: "".to_string(),
+            client_req: Some(ClientReq::ServerCloseSessionReq(ServerCloseSessionReq {
+                session_id,
+            })),
+        };
+
+        match self.oneshot(&msg).await? {
+            ClientResp::ServerCloseSessionResp(_) => Ok(()),
+            resp => Err(resp.into()),
+        }
+    }
+
+    /// Forcibly delete any `Session`'s `View` by name, as reported by
+    /// [`Client::server_sessions`]. Requires administrative access.
+    pub async fn server_delete_view(&self, view_id: String) -> ClientResult<()> {
+        let msg = Request {
+            msg_id: self.gen_id(),
+            entity_id: "".to_string(),
+            client_req: Some(ClientReq::ServerDeleteViewReq(ServerDeleteViewReq { view_id })),
+        };
+
+        match self.oneshot(&msg).await? {
+            ClientResp::ServerDeleteViewResp(_) => Ok(()),
+            resp => Err(resp.into()),
+        }
+    }
 }
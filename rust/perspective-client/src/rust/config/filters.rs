@@ -133,6 +133,15 @@ impl Filter {
     }
 }
 
+impl Display for Filter {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match &self.2 {
+            FilterTerm::Scalar(Scalar::Null) => write!(fmt, "{} {}", self.0, self.1),
+            term => write!(fmt, "{} {} {}", self.0, self.1, term),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, TS)]
 pub enum FilterReducer {
     #[serde(rename = "and")]
@@ -147,6 +156,103 @@ impl Default for FilterReducer {
     }
 }
 
+impl Display for FilterReducer {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Self::And => write!(fmt, "and"),
+            Self::Or => write!(fmt, "or"),
+        }
+    }
+}
+
+/// A nested group of filter clauses, combined by `filter_op`, e.g.
+/// `{"filter_op": "or", "filter": [["x", ">", 1], ["y", "==", "a"]]}`. An
+/// empty group matches every row.
+#[derive(Clone, Default, Deserialize, Debug, PartialEq, Serialize, TS)]
+#[serde(deny_unknown_fields)]
+pub struct FilterGroup {
+    #[serde(default)]
+    pub filter: Vec<FilterClause>,
+
+    #[serde(default)]
+    pub filter_op: FilterReducer,
+}
+
+impl FilterGroup {
+    pub fn new(filter_op: FilterReducer, filter: Vec<FilterClause>) -> Self {
+        Self { filter, filter_op }
+    }
+}
+
+impl Display for FilterGroup {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        for (idx, clause) in self.filter.iter().enumerate() {
+            if idx > 0 {
+                write!(fmt, " {} ", self.filter_op)?;
+            }
+
+            write!(fmt, "{}", clause)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An entry of a `ViewConfig`'s `filter` list: either a [`Filter`], or a
+/// nested [`FilterGroup`].
+#[derive(Clone, Deserialize, Debug, PartialEq, Serialize, TS)]
+#[serde(untagged)]
+pub enum FilterClause {
+    Filter(Filter),
+    Group(FilterGroup),
+}
+
+impl FilterClause {
+    /// This clause as a [`Filter`], or [`None`] if it is a group.
+    pub fn as_filter(&self) -> Option<&Filter> {
+        match self {
+            Self::Filter(x) => Some(x),
+            Self::Group(_) => None,
+        }
+    }
+
+    pub fn as_filter_mut(&mut self) -> Option<&mut Filter> {
+        match self {
+            Self::Filter(x) => Some(x),
+            Self::Group(_) => None,
+        }
+    }
+
+    /// The [`Filter`]s of this clause, including those of nested groups.
+    pub fn filters(&self) -> Vec<&Filter> {
+        match self {
+            Self::Filter(x) => vec![x],
+            Self::Group(x) => x.filter.iter().flat_map(|x| x.filters()).collect(),
+        }
+    }
+}
+
+impl From<Filter> for FilterClause {
+    fn from(value: Filter) -> Self {
+        Self::Filter(value)
+    }
+}
+
+impl From<FilterGroup> for FilterClause {
+    fn from(value: FilterGroup) -> Self {
+        Self::Group(value)
+    }
+}
+
+impl Display for FilterClause {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Self::Filter(x) => write!(fmt, "{}", x),
+            Self::Group(x) => write!(fmt, "({})", x),
+        }
+    }
+}
+
 impl From<Scalar> for proto::Scalar {
     fn from(value: Scalar) -> Self {
         match value {
@@ -187,6 +293,22 @@ impl From<Filter> for proto::view_config::Filter {
                 FilterTerm::Scalar(x) => vec![x.into()],
                 FilterTerm::Array(x) => x.into_iter().map(|x| x.into()).collect(),
             },
+            group: None,
+        }
+    }
+}
+
+impl From<FilterClause> for proto::view_config::Filter {
+    fn from(value: FilterClause) -> Self {
+        match value {
+            FilterClause::Filter(x) => x.into(),
+            FilterClause::Group(x) => proto::view_config::Filter {
+                group: Some(proto::view_config::FilterGroup {
+                    filter: x.filter.into_iter().map(|x| x.into()).collect(),
+                    filter_op: proto::view_config::FilterReducer::from(x.filter_op) as i32,
+                }),
+                ..proto::view_config::Filter::default()
+            },
         }
     }
 }
@@ -204,3 +326,17 @@ impl From<proto::view_config::Filter> for Filter {
         )
     }
 }
+
+impl From<proto::view_config::Filter> for FilterClause {
+    fn from(value: proto::view_config::Filter) -> Self {
+        match value.group {
+            Some(group) => FilterClause::Group(FilterGroup {
+                filter: group.filter.into_iter().map(|x| x.into()).collect(),
+                filter_op: proto::view_config::FilterReducer::try_from(group.filter_op)
+                    .unwrap_or_default()
+                    .into(),
+            }),
+            None => FilterClause::Filter(value.into()),
+        }
+    }
+}
@@ -22,6 +22,9 @@ mod plugin;
 mod sort;
 mod view_config;
 
+#[cfg(test)]
+mod tests;
+
 pub use aggregates::*;
 pub use expressions::*;
 pub use filters::*;
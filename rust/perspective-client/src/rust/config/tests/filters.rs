@@ -0,0 +1,80 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use crate::config::*;
+use crate::proto;
+
+const NESTED: &str = r#"[{"filter":[["region","==","EU"],["qty",">",10.0]],"filter_op":"and"},["desk","in",["a","b"]]]"#;
+
+#[test]
+fn test_flat_filter_json_is_compatible() {
+    let json = r#"[["x","==",1.0],["y","in",["a","b"]],["z","is null",null]]"#;
+    let filter: Vec<FilterClause> = serde_json::from_str(json).unwrap();
+    assert_eq!(filter, vec![
+        Filter::new("x", "==", FilterTerm::Scalar(Scalar::Float(1.0))).into(),
+        Filter::new("y", "in", ["a", "b"]).into(),
+        Filter::new("z", "is null", FilterTerm::Scalar(Scalar::Null)).into(),
+    ]);
+
+    assert_eq!(serde_json::to_string(&filter).unwrap(), json);
+}
+
+#[test]
+fn test_nested_filter_json_round_trip() {
+    let filter: Vec<FilterClause> = serde_json::from_str(NESTED).unwrap();
+    assert_eq!(filter, vec![
+        FilterGroup::new(FilterReducer::And, vec![
+            Filter::new("region", "==", FilterTerm::Scalar("EU".into())).into(),
+            Filter::new("qty", ">", FilterTerm::Scalar(Scalar::Float(10.0))).into(),
+        ])
+        .into(),
+        Filter::new("desk", "in", ["a", "b"]).into(),
+    ]);
+
+    assert_eq!(serde_json::to_string(&filter).unwrap(), NESTED);
+}
+
+#[test]
+fn test_filter_group_unknown_fields() {
+    let json = r#"[{"op":"or","filter":[]}]"#;
+    assert!(serde_json::from_str::<Vec<FilterClause>>(json).is_err());
+}
+
+#[test]
+fn test_filter_display() {
+    let filter = serde_json::from_str(NESTED).unwrap();
+    let group = FilterGroup::new(FilterReducer::Or, filter);
+    assert_eq!(
+        group.to_string(),
+        "(region == EU and qty > 10) or desk in a,b"
+    );
+
+    let filter = Filter::new("z", "is null", FilterTerm::Scalar(Scalar::Null));
+    assert_eq!(filter.to_string(), "z is null");
+}
+
+#[test]
+fn test_nested_filter_proto_round_trip() {
+    let update = ViewConfigUpdate {
+        filter: Some(serde_json::from_str(NESTED).unwrap()),
+        filter_op: Some(FilterReducer::Or),
+        columns: Some(vec![Some("qty".to_owned())]),
+        ..ViewConfigUpdate::default()
+    };
+
+    let config = ViewConfig::from(proto::ViewConfig::from(update.clone()));
+    assert_eq!(Some(config.filter), update.filter);
+    assert_eq!(config.filter_op, FilterReducer::Or);
+    assert!(config.is_column_expression_in_use("region"));
+    assert_eq!(config.filter_index(0), 1);
+    assert_eq!(config.filter_index(1), 2);
+}
@@ -35,7 +35,7 @@ pub struct ViewConfig {
     pub columns: Vec<Option<String>>,
 
     #[serde(default)]
-    pub filter: Vec<Filter>,
+    pub filter: Vec<FilterClause>,
 
     #[serde(skip_serializing_if = "is_default_value")]
     #[serde(default)]
@@ -80,7 +80,7 @@ pub struct ViewConfigUpdate {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     #[ts(optional)]
-    pub filter: Option<Vec<Filter>>,
+    pub filter: Option<Vec<FilterClause>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
@@ -253,10 +253,27 @@ impl ViewConfig {
         self.group_by.contains(&name)
             || self.split_by.contains(&name)
             || self.sort.iter().any(|x| x.0 == name)
-            || self.filter.iter().any(|x| x.column() == name)
+            || self
+                .filter
+                .iter()
+                .flat_map(|x| x.filters())
+                .any(|x| x.column() == name)
             || self.columns.contains(&Some(name))
     }
 
+    /// The index into `filter` of the `position`-th top-level `Filter`,
+    /// skipping any `FilterGroup` clauses, or `filter.len()` if there are
+    /// fewer than `position` such filters.
+    pub fn filter_index(&self, position: usize) -> usize {
+        self.filter
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| x.as_filter().is_some())
+            .nth(position)
+            .map(|(idx, _)| idx)
+            .unwrap_or(self.filter.len())
+    }
+
     /// `ViewConfig` carries additional metadata in the form of `None` columns
     /// which are filtered befor ebeing passed to the engine, but whose position
     /// is a placeholder for Viewer functionality. `is_equivalent` tests
@@ -0,0 +1,118 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::proto::{self, ColumnType};
+use crate::utils::*;
+#[cfg(doc)]
+use crate::{Client, Table, UpdateData};
+
+/// Options which override the engine's auto-detection when parsing
+/// [`UpdateData::Csv`] input, for [`Client::table`] and [`Table::update`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TS)]
+pub struct CsvOptions {
+    /// The field delimiter, e.g. `;` or `\t`, which must be ASCII. Defaults
+    /// to `,`.
+    #[serde(default)]
+    #[ts(optional)]
+    pub delimiter: Option<char>,
+
+    /// The quote character. Defaults to `"`.
+    #[serde(default)]
+    #[ts(optional)]
+    pub quote: Option<char>,
+
+    /// Whether the first row is a header. Defaults to `true`; when `false`,
+    /// columns are named by `column_names` or else `f0`, `f1`, ...
+    #[serde(default)]
+    #[ts(optional)]
+    pub header: Option<bool>,
+
+    /// Column names to use instead of the header row.
+    #[serde(default)]
+    #[ts(optional)]
+    pub column_names: Option<Vec<String>>,
+
+    /// Values to read as `null`, e.g. `"NA"` or `"-"`. This also applies to
+    /// `string` columns.
+    #[serde(default)]
+    #[ts(optional)]
+    pub null_values: Option<Vec<String>>,
+
+    /// `strptime` formats for `date` columns, tried before the built-in
+    /// formats. Only applies to columns typed as `date` by `column_types` or
+    /// by the [`Table`] schema.
+    #[serde(default)]
+    #[ts(optional)]
+    pub date_formats: Option<Vec<String>>,
+
+    /// `strptime` formats for `datetime` columns, tried before the built-in
+    /// formats.
+    #[serde(default)]
+    #[ts(optional)]
+    pub datetime_formats: Option<Vec<String>>,
+
+    /// The decimal separator for `float` columns, e.g. `,`.
+    #[serde(default)]
+    #[ts(optional)]
+    pub decimal_point: Option<char>,
+
+    /// Explicit column types, which replace inference for these columns when
+    /// creating a [`Table`]. Ignored by [`Table::update`], which always uses
+    /// the [`Table`] schema.
+    #[serde(default)]
+    #[ts(optional)]
+    #[ts(as = "Option<HashMap<String, String>>")]
+    pub column_types: Option<HashMap<String, ColumnType>>,
+}
+
+/// The engine reads single-character options as one byte, so they must be
+/// ASCII.
+fn ascii_option(name: &str, value: Option<char>) -> ClientResult<Option<String>> {
+    match value {
+        Some(x) if !x.is_ascii() => Err(ClientError::Unknown(format!(
+            "CSV `{}` must be an ASCII character, not {:?}",
+            name, x
+        ))),
+        x => Ok(x.map(String::from)),
+    }
+}
+
+impl TryFrom<CsvOptions> for proto::CsvOptions {
+    type Error = ClientError;
+
+    fn try_from(value: CsvOptions) -> ClientResult<Self> {
+        Ok(proto::CsvOptions {
+            delimiter: ascii_option("delimiter", value.delimiter)?,
+            quote: ascii_option("quote", value.quote)?,
+            header: value.header,
+            column_names: value.column_names.unwrap_or_default(),
+            null_values: value.null_values.unwrap_or_default(),
+            date_formats: value.date_formats.unwrap_or_default(),
+            datetime_formats: value.datetime_formats.unwrap_or_default(),
+            decimal_point: ascii_option("decimal_point", value.decimal_point)?,
+            column_types: value
+                .column_types
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(name, r#type)| proto::schema::KeyTypePair {
+                    name,
+                    r#type: r#type as i32,
+                })
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,571 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Client-side validation of row-oriented input against a [`Table`] schema,
+//! which reports (and optionally diverts) values the engine would otherwise
+//! silently coerce or null.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::de::{IgnoredAny, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use ts_rs::TS;
+
+use crate::client::Client;
+use crate::proto::ColumnType;
+use crate::table::{Schema, TableInitOptions};
+use crate::table_data::{TableData, UpdateData};
+use crate::utils::*;
+#[cfg(doc)]
+use crate::{Table, UpdateOptions};
+
+/// How [`IngestOptions`] treats rows with values that can't be coerced to the
+/// column type.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub enum IngestMode {
+    /// Reject the entire update on the first bad value.
+    #[serde(rename = "strict")]
+    Strict,
+
+    /// Load the good rows, and drop (or divert to `rejects_table`) the rest.
+    #[default]
+    #[serde(rename = "lenient")]
+    Lenient,
+}
+
+/// Options which enable ingest validation and reporting for
+/// [`Client::table`] and [`Table::update`]. Only JSON input
+/// ([`UpdateData::JsonRows`], [`UpdateData::JsonColumns`] and
+/// [`UpdateData::Ndjson`]) can be validated.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TS)]
+pub struct IngestOptions {
+    #[serde(default)]
+    pub mode: IngestMode,
+
+    /// In [`IngestMode::Lenient`], the name of a [`Table`] which receives
+    /// rejected rows, created on first use with `row`, `column`, `reason` and
+    /// `data` columns.
+    #[serde(default)]
+    #[ts(optional)]
+    pub rejects_table: Option<String>,
+}
+
+/// A single value which was rejected or coerced.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+pub struct IngestIssue {
+    /// The 0-based row number within the input.
+    pub row: usize,
+    pub column: String,
+    pub reason: String,
+}
+
+/// The outcome of a validated [`Client::table`] or [`Table::update`] call.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TS)]
+pub struct IngestReport {
+    /// The 0-based row numbers of the rows loaded.
+    pub accepted: Vec<usize>,
+
+    /// Values which caused their row to be rejected.
+    pub rejected: Vec<IngestIssue>,
+
+    /// Values which were converted to the column type, e.g. `"12"` for an
+    /// `integer` column. Rows with only coerced values are accepted.
+    pub coerced: Vec<IngestIssue>,
+
+    /// Input columns which are not in the [`Table`] schema, and so were not
+    /// loaded.
+    pub ignored: Vec<String>,
+}
+
+/// JSON input parsed as rows, with its column names in input order (which
+/// [`serde_json::Map`] does not preserve).
+pub(crate) struct Input {
+    rows: Vec<Map<String, Value>>,
+    columns: Vec<String>,
+    is_columnar: bool,
+}
+
+/// The keys of a JSON object, in input order.
+struct Keys(Vec<String>);
+
+impl<'de> Deserialize<'de> for Keys {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeysVisitor;
+
+        impl<'de> Visitor<'de> for KeysVisitor {
+            type Value = Keys;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a JSON object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Keys, A::Error> {
+                let mut keys = vec![];
+                while let Some((key, IgnoredAny)) = map.next_entry::<String, IgnoredAny>()? {
+                    keys.push(key);
+                }
+
+                Ok(Keys(keys))
+            }
+        }
+
+        deserializer.deserialize_map(KeysVisitor)
+    }
+}
+
+/// The union of `keys`, in order of first appearance.
+fn ordered_columns(keys: impl IntoIterator<Item = Keys>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    keys.into_iter()
+        .flat_map(|x| x.0)
+        .filter(|x| seen.insert(x.clone()))
+        .collect()
+}
+
+/// The validated input, split into the rows to load and the rejected rows as
+/// raw JSON.
+pub(crate) struct Validated {
+    pub data: UpdateData,
+    pub report: IngestReport,
+    pub rejects: Vec<(usize, String)>,
+}
+
+enum Check {
+    Valid,
+    Coerced(Value, String),
+    Rejected(String),
+}
+
+/// Parse `n` ASCII digits from the start of `s`.
+fn digits(s: &str, n: usize) -> Option<(u32, &str)> {
+    let prefix = s.get(..n)?;
+    if prefix.bytes().all(|x| x.is_ascii_digit()) {
+        Some((prefix.parse().ok()?, &s[n..]))
+    } else {
+        None
+    }
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// Parse a `YYYY-MM-DD`, `YYYY/MM/DD` or `MM/DD/YYYY` date from the start of
+/// `s`, returning the remainder.
+fn parse_date(s: &str) -> Option<&str> {
+    let (year, month, day, rest) = match digits(s, 4) {
+        Some((year, rest)) => {
+            let sep = rest.chars().next().filter(|x| *x == '-' || *x == '/')?;
+            let (month, rest) = digits(&rest[1..], 2)?;
+            let (day, rest) = digits(rest.strip_prefix(sep)?, 2)?;
+            (year, month, day, rest)
+        },
+        None => {
+            let (month, rest) = digits(s, 2)?;
+            let (day, rest) = digits(rest.strip_prefix('/')?, 2)?;
+            let (year, rest) = digits(rest.strip_prefix('/')?, 4)?;
+            (year, month, day, rest)
+        },
+    };
+
+    (1..=days_in_month(year, month))
+        .contains(&day)
+        .then_some(rest)
+}
+
+/// Parse a `HH:MM[:SS[.fff]]` time from the start of `s`, returning the
+/// remainder.
+fn parse_time(s: &str) -> Option<&str> {
+    let (hour, rest) = digits(s, 2)?;
+    let (minute, mut rest) = digits(rest.strip_prefix(':')?, 2)?;
+    let mut second = 0;
+    if let Some(x) = rest.strip_prefix(':') {
+        (second, rest) = digits(x, 2)?;
+        if let Some(fraction) = rest.strip_prefix('.') {
+            let len = fraction.bytes().take_while(|x| x.is_ascii_digit()).count();
+            rest = fraction.get(len..).filter(|_| len > 0)?;
+        }
+    }
+
+    (hour < 24 && minute < 60 && second < 61).then_some(rest)
+}
+
+/// Whether `s` is empty or a `Z`, `±HH` or `±HH:MM` UTC offset.
+fn is_offset(s: &str) -> bool {
+    let Some(offset) = s.strip_prefix(['+', '-']) else {
+        return s.is_empty() || s == "Z";
+    };
+
+    match digits(offset, 2) {
+        Some((hour, "")) => hour < 24,
+        Some((hour, rest)) => {
+            let rest = rest.strip_prefix(':').unwrap_or(rest);
+            hour < 24 && matches!(digits(rest, 2), Some((minute, "")) if minute < 60)
+        },
+        None => false,
+    }
+}
+
+fn is_date(s: &str) -> bool {
+    parse_date(s.trim()) == Some("")
+}
+
+/// Whether `s` is a date, optionally followed by a time and UTC offset.
+fn is_datetime(s: &str) -> bool {
+    let Some(rest) = parse_date(s.trim()) else {
+        return false;
+    };
+
+    match rest.strip_prefix(['T', ' ']) {
+        Some(time) => parse_time(time).is_some_and(is_offset),
+        None => rest.is_empty(),
+    }
+}
+
+fn is_integer(value: &Value) -> bool {
+    value.as_i64().is_some_and(|x| i32::try_from(x).is_ok())
+}
+
+/// Infer the type of a column from its values, by the kind of the first
+/// non-null value. Values which don't fit this type are then reported by
+/// [`validate`].
+fn infer_type<'a>(mut values: impl Iterator<Item = &'a Value> + Clone) -> ColumnType {
+    let strings = values.clone().filter_map(Value::as_str);
+    let numbers = values.clone().filter(|x| x.is_number());
+    match values.find(|x| !x.is_null()) {
+        Some(Value::Bool(_)) => ColumnType::Boolean,
+        Some(Value::Number(_)) if numbers.into_iter().all(is_integer) => ColumnType::Integer,
+        Some(Value::Number(_)) => ColumnType::Float,
+        Some(Value::String(_)) if strings.clone().all(is_date) => ColumnType::Date,
+        Some(Value::String(_)) if strings.clone().all(is_datetime) => ColumnType::Datetime,
+        _ => ColumnType::String,
+    }
+}
+
+/// Infer the schema of `input`, in input column order, as the engine would
+/// without validation.
+pub(crate) fn infer_schema(input: &Input) -> Vec<(String, ColumnType)> {
+    input
+        .columns
+        .iter()
+        .map(|name| {
+            let values = input.rows.iter().filter_map(|row| row.get(name));
+            (name.clone(), infer_type(values))
+        })
+        .collect()
+}
+
+/// Whether `f` is within the range of the engine's 32-bit `integer` type.
+fn is_i32(f: f64) -> bool {
+    (i32::MIN as f64..=i32::MAX as f64).contains(&f)
+}
+
+fn out_of_range(x: impl fmt::Display) -> String {
+    format!("{} is out of range for a 32-bit integer", x)
+}
+
+fn check_value(value: &Value, ctype: ColumnType) -> Check {
+    match (ctype, value) {
+        (_, Value::Null) => Check::Valid,
+        (ColumnType::Integer, Value::Number(_)) if is_integer(value) => Check::Valid,
+        (ColumnType::Integer, Value::Number(x)) if x.is_i64() || x.is_u64() => {
+            Check::Rejected(out_of_range(x))
+        },
+        (ColumnType::Integer, Value::Number(x)) => match x.as_f64() {
+            Some(f) if f.fract() == 0.0 && is_i32(f) => Check::Coerced(
+                Value::from(f as i32),
+                "float with integral value".to_owned(),
+            ),
+            Some(f) if f.fract() == 0.0 => Check::Rejected(out_of_range(x)),
+            _ => Check::Rejected(format!("{} is not an integer", x)),
+        },
+        (ColumnType::Integer, Value::String(x)) => match x.trim().parse::<i64>() {
+            Ok(i) => match i32::try_from(i) {
+                Ok(i) => Check::Coerced(Value::from(i), "string parsed as integer".to_owned()),
+                Err(_) => Check::Rejected(out_of_range(x)),
+            },
+            Err(_) => Check::Rejected(format!("{:?} is not an integer", x)),
+        },
+        (ColumnType::Float, Value::Number(_)) => Check::Valid,
+        (ColumnType::Float, Value::String(x)) => match x.trim().parse::<f64>() {
+            Ok(f) if f.is_finite() => {
+                Check::Coerced(Value::from(f), "string parsed as float".to_owned())
+            },
+            _ => Check::Rejected(format!("{:?} is not a float", x)),
+        },
+        (ColumnType::String, Value::String(_)) => Check::Valid,
+        (ColumnType::String, Value::Number(x)) => {
+            Check::Coerced(Value::from(x.to_string()), "number as string".to_owned())
+        },
+        (ColumnType::String, Value::Bool(x)) => {
+            Check::Coerced(Value::from(x.to_string()), "boolean as string".to_owned())
+        },
+        (ColumnType::Boolean, Value::Bool(_)) => Check::Valid,
+        (ColumnType::Boolean, Value::String(x)) => match x.trim().to_lowercase().as_str() {
+            "true" => Check::Coerced(Value::from(true), "string parsed as boolean".to_owned()),
+            "false" => Check::Coerced(Value::from(false), "string parsed as boolean".to_owned()),
+            _ => Check::Rejected(format!("{:?} is not a boolean", x)),
+        },
+        (ColumnType::Boolean, Value::Number(x)) if x.as_f64() == Some(0.0) => {
+            Check::Coerced(Value::from(false), "number as boolean".to_owned())
+        },
+        (ColumnType::Boolean, Value::Number(x)) if x.as_f64() == Some(1.0) => {
+            Check::Coerced(Value::from(true), "number as boolean".to_owned())
+        },
+        (ColumnType::Date | ColumnType::Datetime, Value::Number(_)) => Check::Valid,
+        (ColumnType::Date | ColumnType::Datetime, Value::String(x)) if is_datetime(x) => {
+            Check::Valid
+        },
+        (ColumnType::Date, Value::String(x)) => Check::Rejected(format!("{:?} is not a date", x)),
+        (ColumnType::Datetime, Value::String(x)) => {
+            Check::Rejected(format!("{:?} is not a datetime", x))
+        },
+        (ctype, value) => Check::Rejected(format!("{} is not a {:?}", value, ctype)),
+    }
+}
+
+/// Check (and coerce, in place) one row. Returns `false` if the row is
+/// rejected.
+fn check_row(
+    idx: usize,
+    row: &mut Map<String, Value>,
+    schema: &Schema,
+    mode: IngestMode,
+    report: &mut IngestReport,
+) -> ClientResult<bool> {
+    let mut valid = true;
+    for (column, value) in row.iter_mut() {
+        let issue = |reason: String| IngestIssue {
+            row: idx,
+            column: column.to_owned(),
+            reason,
+        };
+
+        let Some(ctype) = schema.get(column) else {
+            if !report.ignored.contains(column) {
+                report.ignored.push(column.to_owned());
+            }
+
+            continue;
+        };
+
+        match check_value(value, *ctype) {
+            Check::Valid => (),
+            Check::Coerced(coerced, reason) => {
+                *value = coerced;
+                report.coerced.push(issue(reason));
+            },
+            Check::Rejected(reason) if mode == IngestMode::Strict => {
+                return Err(ClientError::IngestRejected(Box::new(issue(reason))));
+            },
+            Check::Rejected(reason) => {
+                report.rejected.push(issue(reason));
+                valid = false;
+            },
+        }
+    }
+
+    Ok(valid)
+}
+
+fn validate_rows(
+    rows: Vec<Map<String, Value>>,
+    schema: &Schema,
+    mode: IngestMode,
+    report: &mut IngestReport,
+    rejects: &mut Vec<(usize, String)>,
+) -> ClientResult<Vec<Map<String, Value>>> {
+    let mut accepted = Vec::with_capacity(rows.len());
+    for (idx, mut row) in rows.into_iter().enumerate() {
+        let raw = serde_json::to_string(&row)?;
+        if check_row(idx, &mut row, schema, mode, report)? {
+            report.accepted.push(idx);
+            accepted.push(row);
+        } else {
+            rejects.push((idx, raw));
+        }
+    }
+
+    Ok(accepted)
+}
+
+fn unsupported() -> ClientError {
+    ClientError::NotImplemented("ingest validation of non-JSON input")
+}
+
+/// Only JSON input can be validated, which is checked before any work is
+/// done.
+pub(crate) fn check_supported(data: &UpdateData) -> ClientResult<()> {
+    match data {
+        UpdateData::JsonRows(_) | UpdateData::JsonColumns(_) | UpdateData::Ndjson(_) => Ok(()),
+        _ => Err(unsupported()),
+    }
+}
+
+/// Parse JSON `data` as rows.
+pub(crate) fn parse(data: UpdateData) -> ClientResult<Input> {
+    Ok(match data {
+        UpdateData::JsonRows(json) => {
+            let keys: Vec<Keys> = serde_json::from_str(&json)?;
+            Input {
+                rows: serde_json::from_str(&json)?,
+                columns: ordered_columns(keys),
+                is_columnar: false,
+            }
+        },
+        UpdateData::Ndjson(ndjson) => {
+            let lines = ndjson.lines().filter(|line| !line.trim().is_empty());
+            let keys = lines
+                .clone()
+                .map(serde_json::from_str::<Keys>)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Input {
+                rows: lines
+                    .map(serde_json::from_str)
+                    .collect::<Result<Vec<_>, _>>()?,
+                columns: ordered_columns(keys),
+                is_columnar: false,
+            }
+        },
+        UpdateData::JsonColumns(json) => {
+            let names = serde_json::from_str::<Keys>(&json)?.0;
+            let columns: Map<String, Value> = serde_json::from_str(&json)?;
+            let columns = columns
+                .into_iter()
+                .map(|(name, col)| match col {
+                    Value::Array(values) => Ok((name, values)),
+                    _ => Err(ClientError::Unknown(format!(
+                        "Column {:?} is not a list",
+                        name
+                    ))),
+                })
+                .collect::<ClientResult<Vec<_>>>()?;
+
+            let num_rows = columns
+                .iter()
+                .map(|(_, col)| col.len())
+                .max()
+                .unwrap_or_default();
+
+            let rows = (0..num_rows)
+                .map(|idx| {
+                    columns
+                        .iter()
+                        .filter_map(|(name, col)| Some((name.clone(), col.get(idx)?.clone())))
+                        .collect::<Map<_, _>>()
+                })
+                .collect();
+
+            Input {
+                rows,
+                columns: names,
+                is_columnar: true,
+            }
+        },
+        _ => return Err(unsupported()),
+    })
+}
+
+/// Validate `input` against `schema`, returning the rows to load. In
+/// [`IngestMode::Strict`], the first rejected value is an error.
+pub(crate) fn validate(input: Input, schema: &Schema, mode: IngestMode) -> ClientResult<Validated> {
+    let mut report = IngestReport::default();
+    let mut rejects = vec![];
+    let rows = validate_rows(input.rows, schema, mode, &mut report, &mut rejects)?;
+    let data = if input.is_columnar {
+        let mut columns = Map::new();
+        for name in input.columns {
+            let col = rows
+                .iter()
+                .map(|row| row.get(&name).cloned().unwrap_or_default())
+                .collect();
+
+            columns.insert(name, Value::Array(col));
+        }
+
+        UpdateData::JsonColumns(serde_json::to_string(&columns)?)
+    } else {
+        UpdateData::JsonRows(serde_json::to_string(&rows)?)
+    };
+
+    Ok(Validated {
+        data,
+        report,
+        rejects,
+    })
+}
+
+/// Append rejected rows to the `rejects_table`, creating it if necessary.
+pub(crate) async fn route_rejects(
+    client: &Client,
+    name: &str,
+    report: &IngestReport,
+    rejects: Vec<(usize, String)>,
+) -> ClientResult<()> {
+    if rejects.is_empty() {
+        return Ok(());
+    }
+
+    let table = if client
+        .get_hosted_table_names()
+        .await?
+        .iter()
+        .any(|x| x == name)
+    {
+        client.open_table(name.to_owned()).await?
+    } else {
+        let schema = TableData::Schema(vec![
+            ("row".to_owned(), ColumnType::Integer),
+            ("column".to_owned(), ColumnType::String),
+            ("reason".to_owned(), ColumnType::String),
+            ("data".to_owned(), ColumnType::String),
+        ]);
+
+        let mut options = TableInitOptions::default();
+        options.set_name(name);
+        client.table_inner(schema, options).await?
+    };
+
+    let rows = rejects
+        .into_iter()
+        .flat_map(|(row, data)| {
+            report
+                .rejected
+                .iter()
+                .filter(move |issue| issue.row == row)
+                .map(move |issue| {
+                    serde_json::json!({
+                        "row": row,
+                        "column": issue.column,
+                        "reason": issue.reason,
+                        "data": data,
+                    })
+                })
+        })
+        .collect::<Vec<_>>();
+
+    table
+        .update_inner(
+            UpdateData::JsonRows(serde_json::to_string(&rows)?),
+            Default::default(),
+        )
+        .await
+}
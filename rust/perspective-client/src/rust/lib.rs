@@ -17,26 +17,53 @@
 )]
 
 mod client;
+mod csv_data;
+mod ingest;
+mod parquet_data;
+#[cfg(feature = "polars")]
+mod polars_data;
 mod session;
+mod sql;
 mod table;
 mod table_data;
 mod view;
+mod virtual_server;
 
 pub mod config;
 
+/// The generated protocol message types, for implementing transports and
+/// servers which need to inspect messages rather than tunnel them as bytes.
 #[allow(unknown_lints)]
 #[allow(clippy::all)]
-mod proto;
+pub mod proto;
 pub mod utils;
 
-pub use crate::client::{Client, ClientHandler, Features, ReconnectCallback, SystemInfo};
+pub use crate::client::{
+    Client, ClientHandler, Features, ReconnectCallback, SessionInfo, SessionViewInfo, SystemInfo,
+};
+pub use crate::csv_data::CsvOptions;
+pub use crate::ingest::{IngestIssue, IngestMode, IngestOptions, IngestReport};
+pub use crate::parquet_data::{ParquetCompression, ParquetReadOptions};
+#[cfg(feature = "polars")]
+pub use crate::polars_data::polars_to_column_type;
+/// The `polars` version [`UpdateData`] and [`View::to_polars`] convert to and
+/// from.
+#[cfg(feature = "polars")]
+pub use polars;
 pub use crate::proto::{ColumnType, SortOp, ViewOnUpdateResp};
 pub use crate::session::{ProxySession, Session};
+pub use crate::sql::{
+    GeneratedSql, SqlDialect, SqlOptions, SqlQuery, compile_sql, generate_split_by_sql,
+    generate_sql,
+};
 pub use crate::table::{
     Schema, Table, TableInitOptions, TableReadFormat, UpdateOptions, ValidateExpressionsData,
 };
 pub use crate::table_data::{TableData, UpdateData};
 pub use crate::view::{OnUpdateMode, OnUpdateOptions, View, ViewWindow};
+pub use crate::virtual_server::{
+    VirtualServer, VirtualServerError, VirtualServerResult, VirtualSession,
+};
 
 pub type ClientError = utils::ClientError;
 pub type ExprValidationError = crate::proto::table_validate_expr_resp::ExprValidationError;
@@ -69,10 +96,12 @@ macro_rules! assert_table_api {
                     &$x::make_port,
                     &$x::on_delete,
                     &$x::remove_delete,
+                    &$x::query,
                     &$x::replace,
                     &$x::schema,
                     &$x::size,
                     &$x::update,
+                    &$x::update_with_report,
                     &$x::validate_expressions,
                     &$x::view,
                 );
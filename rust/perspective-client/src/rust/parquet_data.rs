@@ -0,0 +1,189 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Client-side conversion between Apache Parquet and the Arrow IPC stream
+//! format the engine reads and writes. The server never sees Parquet; data
+//! is decoded before it is sent and encoded after it is received.
+
+use prost::bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::utils::*;
+#[cfg(doc)]
+use crate::{Client, Table, View};
+
+/// Options which control how [`crate::UpdateData::Parquet`] input is read, for
+/// [`Client::table`] and [`Table::update`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TS)]
+pub struct ParquetReadOptions {
+    /// Only read these top-level columns. Columns are loaded in file order,
+    /// regardless of the order given here.
+    #[serde(default)]
+    #[ts(optional)]
+    pub columns: Option<Vec<String>>,
+
+    /// Only read these row groups, by index.
+    #[serde(default)]
+    #[ts(optional)]
+    pub row_groups: Option<Vec<usize>>,
+}
+
+/// The compression codec used for Parquet pages by [`View::to_parquet`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub enum ParquetCompression {
+    #[serde(rename = "uncompressed")]
+    Uncompressed,
+
+    #[default]
+    #[serde(rename = "snappy")]
+    Snappy,
+
+    #[serde(rename = "gzip")]
+    Gzip,
+
+    #[serde(rename = "lz4")]
+    Lz4,
+
+    /// Only supported with the `parquet-zstd` feature, otherwise
+    /// [`View::to_parquet`] fails with [`ClientError::NotImplemented`].
+    #[serde(rename = "zstd")]
+    Zstd,
+
+    #[serde(rename = "brotli")]
+    Brotli,
+}
+
+impl ParquetCompression {
+    pub fn parse(value: Option<String>) -> Result<Option<Self>, String> {
+        Ok(match value.as_deref() {
+            Some("uncompressed") => Some(ParquetCompression::Uncompressed),
+            Some("snappy") => Some(ParquetCompression::Snappy),
+            Some("gzip") => Some(ParquetCompression::Gzip),
+            Some("lz4") => Some(ParquetCompression::Lz4),
+            Some("zstd") => Some(ParquetCompression::Zstd),
+            Some("brotli") => Some(ParquetCompression::Brotli),
+            None => None,
+            Some(x) => return Err(format!("Unknown compression \"{}\"", x)),
+        })
+    }
+}
+
+/// Decode a Parquet file into an Arrow IPC stream, applying the projection and
+/// row group selection in `options`.
+#[cfg(feature = "parquet")]
+pub(crate) fn parquet_to_arrow(
+    parquet: Bytes,
+    options: &ParquetReadOptions,
+) -> ClientResult<Bytes> {
+    use arrow_ipc::writer::StreamWriter;
+    use parquet::arrow::ProjectionMask;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let mut builder = ParquetRecordBatchReaderBuilder::try_new(parquet).map_err(external)?;
+    if let Some(columns) = &options.columns {
+        let schema = builder.schema().clone();
+        let indices = columns
+            .iter()
+            .map(|name| schema.index_of(name).map_err(external))
+            .collect::<ClientResult<Vec<_>>>()?;
+
+        let mask = ProjectionMask::roots(builder.parquet_schema(), indices);
+        builder = builder.with_projection(mask);
+    }
+
+    if let Some(row_groups) = &options.row_groups {
+        let num_row_groups = builder.metadata().num_row_groups();
+        if let Some(idx) = row_groups.iter().find(|idx| **idx >= num_row_groups) {
+            return Err(ClientError::Unknown(format!(
+                "Row group {} out of range, file has {}",
+                idx, num_row_groups
+            )));
+        }
+
+        builder = builder.with_row_groups(row_groups.clone());
+    }
+
+    let reader = builder.build().map_err(external)?;
+    let schema = reader.schema();
+    let mut writer = StreamWriter::try_new(vec![], &schema).map_err(external)?;
+    for batch in reader {
+        writer.write(&batch.map_err(external)?).map_err(external)?;
+    }
+
+    writer.finish().map_err(external)?;
+    Ok(writer.into_inner().map_err(external)?.into())
+}
+
+#[cfg(not(feature = "parquet"))]
+pub(crate) fn parquet_to_arrow(
+    _parquet: Bytes,
+    _options: &ParquetReadOptions,
+) -> ClientResult<Bytes> {
+    Err(ClientError::NotImplemented("parquet"))
+}
+
+/// Encode an Arrow IPC stream (as produced by [`View::to_arrow`]) as a Parquet
+/// file.
+#[cfg(feature = "parquet")]
+pub(crate) fn arrow_to_parquet(
+    arrow: Bytes,
+    compression: ParquetCompression,
+) -> ClientResult<Bytes> {
+    use arrow_ipc::reader::StreamReader;
+    use parquet::arrow::ArrowWriter;
+    #[cfg(feature = "parquet-zstd")]
+    use parquet::basic::ZstdLevel;
+    use parquet::basic::{BrotliLevel, Compression, GzipLevel};
+    use parquet::file::properties::WriterProperties;
+
+    let compression = match compression {
+        ParquetCompression::Uncompressed => Compression::UNCOMPRESSED,
+        ParquetCompression::Snappy => Compression::SNAPPY,
+        ParquetCompression::Gzip => Compression::GZIP(GzipLevel::default()),
+        ParquetCompression::Lz4 => Compression::LZ4_RAW,
+        #[cfg(feature = "parquet-zstd")]
+        ParquetCompression::Zstd => Compression::ZSTD(ZstdLevel::default()),
+        #[cfg(not(feature = "parquet-zstd"))]
+        ParquetCompression::Zstd => {
+            return Err(ClientError::NotImplemented("zstd Parquet compression"));
+        },
+        ParquetCompression::Brotli => Compression::BROTLI(BrotliLevel::default()),
+    };
+
+    let props = WriterProperties::builder()
+        .set_compression(compression)
+        .build();
+
+    let reader = StreamReader::try_new(std::io::Cursor::new(arrow), None).map_err(external)?;
+    let mut writer =
+        ArrowWriter::try_new(vec![], reader.schema(), Some(props)).map_err(external)?;
+
+    for batch in reader {
+        writer.write(&batch.map_err(external)?).map_err(external)?;
+    }
+
+    Ok(writer.into_inner().map_err(external)?.into())
+}
+
+#[cfg(not(feature = "parquet"))]
+pub(crate) fn arrow_to_parquet(
+    _arrow: Bytes,
+    _compression: ParquetCompression,
+) -> ClientResult<Bytes> {
+    Err(ClientError::NotImplemented("parquet"))
+}
+
+#[cfg(feature = "parquet")]
+fn external<E: std::error::Error + Send + Sync + 'static>(err: E) -> ClientError {
+    ClientError::ExternalError(Box::new(err))
+}
@@ -0,0 +1,107 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Conversion between `polars` frames and the Arrow IPC stream format the
+//! engine reads and writes, for Rust services which already hold a
+//! [`DataFrame`].
+
+use std::io::Cursor;
+
+use polars::prelude::{
+    DataFrame, DataType, IpcStreamReader, IpcStreamWriter, LazyFrame, SerReader, SerWriter,
+};
+use prost::bytes::Bytes;
+
+use crate::proto::ColumnType;
+use crate::table_data::{TableData, UpdateData};
+use crate::utils::*;
+#[cfg(doc)]
+use crate::{Table, View};
+
+/// Encodes the frame as an Arrow IPC stream. Strings are written as
+/// `large_utf8` rather than polars' native `utf8_view`, which the engine does
+/// not read.
+impl TryFrom<DataFrame> for UpdateData {
+    type Error = ClientError;
+
+    fn try_from(mut df: DataFrame) -> ClientResult<Self> {
+        let mut arrow = vec![];
+        IpcStreamWriter::new(&mut arrow)
+            .with_pl_flavor(false)
+            .finish(&mut df)
+            .map_err(external)?;
+
+        Ok(UpdateData::Arrow(arrow.into()))
+    }
+}
+
+/// Collects the query, then converts it as a [`DataFrame`].
+impl TryFrom<LazyFrame> for UpdateData {
+    type Error = ClientError;
+
+    fn try_from(lf: LazyFrame) -> ClientResult<Self> {
+        lf.collect().map_err(external)?.try_into()
+    }
+}
+
+/// A [`TableData::Schema`] for a frame's schema, e.g. to create an empty
+/// indexed [`Table`] before streaming frames into it.
+impl TryFrom<&polars::prelude::Schema> for TableData {
+    type Error = ClientError;
+
+    fn try_from(schema: &polars::prelude::Schema) -> ClientResult<Self> {
+        let schema = schema
+            .iter()
+            .map(|(name, dtype)| match polars_to_column_type(dtype) {
+                Some(ctype) => Ok((name.to_string(), ctype)),
+                None => Err(ClientError::Unknown(format!(
+                    "Column {:?} has unsupported type {}",
+                    name, dtype
+                ))),
+            })
+            .collect::<ClientResult<Vec<_>>>()?;
+
+        Ok(TableData::Schema(schema))
+    }
+}
+
+/// The [`ColumnType`] the engine loads a polars [`DataType`] as, if any.
+pub fn polars_to_column_type(dtype: &DataType) -> Option<ColumnType> {
+    match dtype {
+        DataType::Boolean => Some(ColumnType::Boolean),
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64 => Some(ColumnType::Integer),
+        DataType::Float32 | DataType::Float64 => Some(ColumnType::Float),
+        DataType::String => Some(ColumnType::String),
+        DataType::Date => Some(ColumnType::Date),
+        DataType::Datetime(..) => Some(ColumnType::Datetime),
+        _ => None,
+    }
+}
+
+/// Decode an Arrow IPC stream (as produced by [`View::to_arrow`]) into a
+/// [`DataFrame`].
+pub(crate) fn arrow_to_polars(arrow: Bytes) -> ClientResult<DataFrame> {
+    IpcStreamReader::new(Cursor::new(arrow))
+        .finish()
+        .map_err(external)
+}
+
+fn external<E: std::error::Error + Send + Sync + 'static>(err: E) -> ClientError {
+    ClientError::ExternalError(Box::new(err))
+}
@@ -79,7 +79,7 @@ pub trait Session<E> {
     fn close(self) -> impl Future<Output = ()>;
 }
 
-type ProxyCallback =
+pub(crate) type ProxyCallback =
     Arc<dyn Fn(&[u8]) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync>;
 
 /// A [`Session`] implementation which tunnels through another [`Client`].
@@ -104,7 +104,7 @@ impl ProxySession {
     }
 }
 
-fn encode(response: Response, callback: ProxyCallback) -> Result<(), ClientError> {
+pub(crate) fn encode(response: Response, callback: ProxyCallback) -> Result<(), ClientError> {
     let mut enc = vec![];
     response.encode(&mut enc)?;
     callback(&enc).map_err(|x| ClientError::Unknown(x.to_string()))?;
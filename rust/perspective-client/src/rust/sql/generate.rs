@@ -0,0 +1,523 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Generates SQL for a [`ViewConfig`], to answer the same query against an
+//! external database.
+//!
+//! - `group_by` (up to `group_by_depth`) becomes `GROUP BY`, and each column is
+//!   aggregated with its entry in `aggregates` (or the default for its type in
+//!   [`SqlOptions::schema`]). Only the leaf rows are generated, not the total
+//!   rows of the intermediate levels.
+//! - `split_by` becomes one conditional aggregate per column, per entry of
+//!   [`SqlOptions::split_by_values`], named like Perspective's `a|b|column`.
+//! - `filter` becomes `WHERE`, joined by `filter_op`, with nested filter groups
+//!   in parentheses. String matches (`begins with`, `ends with`, `contains`)
+//!   are case-insensitive. A filter which can't be translated becomes `1 = 1`,
+//!   so the SQL returns a superset of the view's rows (even within an `OR`).
+//! - `sort` becomes `ORDER BY`, followed by the `group_by` columns.
+//!
+//! Anything which can't be translated, e.g. ExprTK `expressions` or an
+//! aggregate the dialect lacks, is left out of the SQL and described in
+//! [`GeneratedSql::untranslated`].
+
+use std::collections::{HashMap, HashSet};
+
+use crate::config::{
+    Aggregate, Filter, FilterClause, FilterReducer, FilterTerm, MultiAggregate, Scalar,
+    SingleAggregate, Sort, SortDir, ViewConfig,
+};
+use crate::proto::ColumnType;
+
+/// The database to generate SQL for.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SqlDialect {
+    #[default]
+    Postgres,
+    DuckDb,
+    Sqlite,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SqlOptions {
+    pub dialect: SqlDialect,
+
+    /// Column types of the table, used for default aggregates and to convert
+    /// `datetime` filter terms (which are epoch milliseconds).
+    pub schema: HashMap<String, ColumnType>,
+
+    /// The `split_by` values to generate columns for, e.g. the rows returned
+    /// by [`generate_split_by_sql`].
+    pub split_by_values: Vec<Vec<Scalar>>,
+}
+
+/// The SQL generated for a [`ViewConfig`], and a description of each part of
+/// the config which it leaves out.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GeneratedSql {
+    pub sql: String,
+    pub untranslated: Vec<String>,
+}
+
+/// Generate a `SELECT` over `table` which computes the rows of a view created
+/// with `config`.
+pub fn generate_sql(table: &str, config: &ViewConfig, options: &SqlOptions) -> GeneratedSql {
+    let mut generator = Generator::new(config, options);
+    let sql = generator.select(table);
+    generator.finish(sql)
+}
+
+/// Generate a `SELECT DISTINCT` of the `split_by` values of a view created with
+/// `config`, or [`None`] if it has no `split_by`.
+pub fn generate_split_by_sql(
+    table: &str,
+    config: &ViewConfig,
+    options: &SqlOptions,
+) -> Option<GeneratedSql> {
+    if config.split_by.is_empty() {
+        return None;
+    }
+
+    let mut generator = Generator::new(config, options);
+    let columns = generator.columns(&config.split_by);
+    let mut sql = format!(
+        "SELECT DISTINCT {}\nFROM {}",
+        columns.join(", "),
+        quote_table(table)
+    );
+
+    generator.push_where(&mut sql);
+    sql.push_str(&format!("\nORDER BY {}", columns.join(", ")));
+    Some(generator.finish(sql))
+}
+
+/// The condition for a filter which keeps every row.
+const TRUE: &str = "1 = 1";
+
+fn quote(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+/// Quote a possibly schema-qualified table name.
+fn quote_table(table: &str) -> String {
+    table.split('.').map(quote).collect::<Vec<_>>().join(".")
+}
+
+fn quote_str(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Escape the `LIKE` wildcards in `value`, for use with `ESCAPE '\'`.
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+struct Generator<'a> {
+    config: &'a ViewConfig,
+    options: &'a SqlOptions,
+    untranslated: Vec<String>,
+}
+
+impl<'a> Generator<'a> {
+    fn new(config: &'a ViewConfig, options: &'a SqlOptions) -> Self {
+        let mut expressions = config.expressions.keys().collect::<Vec<_>>();
+        expressions.sort();
+        let untranslated = expressions
+            .into_iter()
+            .map(|name| format!("Expression `{}`", name))
+            .collect();
+
+        Self {
+            config,
+            options,
+            untranslated,
+        }
+    }
+
+    fn finish(mut self, sql: String) -> GeneratedSql {
+        let mut seen = HashSet::new();
+        self.untranslated.retain(|x| seen.insert(x.clone()));
+        GeneratedSql {
+            sql,
+            untranslated: self.untranslated,
+        }
+    }
+
+    fn report(&mut self, msg: String) {
+        self.untranslated.push(msg);
+    }
+
+    fn is_expression(&self, column: &str) -> bool {
+        self.config.expressions.contains_key(column)
+    }
+
+    /// Quote `columns`, skipping (already reported) expressions.
+    fn columns(&self, columns: &[String]) -> Vec<String> {
+        columns
+            .iter()
+            .filter(|x| !self.is_expression(x))
+            .map(|x| quote(x))
+            .collect()
+    }
+
+    fn select(&mut self, table: &str) -> String {
+        let config = self.config;
+        let group_by = match config.group_by_depth {
+            Some(depth) => {
+                let depth = (depth as usize).min(config.group_by.len());
+                &config.group_by[..depth]
+            },
+            None => &config.group_by[..],
+        };
+
+        let grouped = !config.group_by.is_empty();
+        if !grouped && !config.split_by.is_empty() {
+            self.report("`split_by` without `group_by`".to_owned());
+        }
+
+        let group_by = self.columns(group_by);
+        let mut items = group_by.clone();
+        let columns = config
+            .columns
+            .iter()
+            .flatten()
+            .filter(|x| !self.is_expression(x))
+            .collect::<Vec<_>>();
+
+        if !grouped {
+            items.extend(columns.iter().map(|x| quote(x)));
+        } else if config.split_by.is_empty() {
+            for column in columns {
+                if let Some(agg) = self.aggregate(column, None) {
+                    items.push(format!("{} AS {}", agg, quote(column)));
+                }
+            }
+        } else {
+            for (prefix, cond) in self.split_by_conditions() {
+                for column in &columns {
+                    if let Some(agg) = self.aggregate(column, Some(&cond)) {
+                        let name = format!("{}|{}", prefix, column);
+                        items.push(format!("{} AS {}", agg, quote(&name)));
+                    }
+                }
+            }
+        }
+
+        if items.is_empty() {
+            items.push("NULL".to_owned());
+        }
+
+        let mut sql = format!("SELECT {}\nFROM {}", items.join(", "), quote_table(table));
+        self.push_where(&mut sql);
+        if !group_by.is_empty() {
+            sql.push_str(&format!("\nGROUP BY {}", group_by.join(", ")));
+        }
+
+        let mut order_by = vec![];
+        for sort in &config.sort {
+            if let Some(term) = self.sort(sort, grouped) {
+                order_by.push(term);
+            }
+        }
+
+        order_by.extend(group_by.iter().map(|x| format!("{} ASC", x)));
+        if !order_by.is_empty() {
+            sql.push_str(&format!("\nORDER BY {}", order_by.join(", ")));
+        }
+
+        sql
+    }
+
+    /// The column name prefix and `WHERE` condition for each entry of
+    /// `split_by_values`.
+    fn split_by_conditions(&mut self) -> Vec<(String, String)> {
+        let (config, options) = (self.config, self.options);
+        let split_by = &config.split_by;
+        if split_by.iter().any(|x| self.is_expression(x)) {
+            return vec![];
+        }
+
+        if options.split_by_values.is_empty() {
+            self.report("`split_by` without `split_by_values`".to_owned());
+        }
+
+        let mut conditions = vec![];
+        for values in &options.split_by_values {
+            if values.len() != split_by.len() {
+                self.report(format!(
+                    "`split_by_values` entry {:?} for {} `split_by` columns",
+                    values,
+                    split_by.len()
+                ));
+
+                continue;
+            }
+
+            let prefix = values
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join("|");
+
+            let cond = split_by
+                .iter()
+                .zip(values)
+                .map(|(column, value)| match value {
+                    Scalar::Null => format!("{} IS NULL", quote(column)),
+                    value => format!("{} = {}", quote(column), self.literal(column, value)),
+                })
+                .collect::<Vec<_>>()
+                .join(" AND ");
+
+            conditions.push((prefix, cond));
+        }
+
+        conditions
+    }
+
+    /// The aggregate of `column`, optionally only over rows matching `cond`.
+    fn aggregate(&mut self, column: &str, cond: Option<&str>) -> Option<String> {
+        let agg = match self.config.aggregates.get(column) {
+            Some(agg) => agg.clone(),
+            None => match self.options.schema.get(column) {
+                Some(column_type) => column_type.default_aggregate(),
+                None => {
+                    self.report(format!(
+                        "Aggregate of `{}`, which is not in `aggregates` or `schema`",
+                        column
+                    ));
+
+                    return None;
+                },
+            },
+        };
+
+        let when = |x: String| match cond {
+            Some(cond) => format!("CASE WHEN {} THEN {} END", cond, x),
+            None => x,
+        };
+
+        let sql = match &agg {
+            Aggregate::SingleAggregate(single) => {
+                self.single_aggregate(*single, &when(quote(column)))
+            },
+            Aggregate::MultiAggregate(MultiAggregate::WeightedMean, weight) => {
+                if self.is_expression(weight) {
+                    return None;
+                }
+
+                let product = when(format!("{} * {}", quote(column), quote(weight)));
+                Some(format!(
+                    "SUM({}) * 1.0 / SUM({})",
+                    product,
+                    when(quote(weight))
+                ))
+            },
+        };
+
+        if sql.is_none() {
+            self.report(format!(
+                "Aggregate `{}` of `{}` in {:?}",
+                agg, column, self.options.dialect
+            ));
+        }
+
+        sql
+    }
+
+    fn single_aggregate(&self, agg: SingleAggregate, x: &str) -> Option<String> {
+        use SingleAggregate::*;
+        use SqlDialect::*;
+
+        let quantile = match agg {
+            Q1 => 0.25,
+            Q3 => 0.75,
+            _ => 0.5,
+        };
+
+        Some(match (agg, self.options.dialect) {
+            (Sum | SumNotNull, _) => format!("SUM({})", x),
+            (SumAbs, _) => format!("SUM(ABS({}))", x),
+            (AbsSum, _) => format!("ABS(SUM({}))", x),
+            (Any, DuckDb) => format!("ANY_VALUE({})", x),
+            (Any | Low | Min, _) => format!("MIN({})", x),
+            (High | Max, _) => format!("MAX({})", x),
+            (HighMinusLow, _) => format!("MAX({}) - MIN({})", x, x),
+            (Unique, _) => format!("CASE WHEN COUNT(DISTINCT {}) = 1 THEN MIN({}) END", x, x),
+            (Count, _) => format!("COUNT({})", x),
+            (DistinctCount, _) => format!("COUNT(DISTINCT {})", x),
+            (Avg | Mean, _) => format!("AVG({})", x),
+            (Dominant, Postgres) => format!("MODE() WITHIN GROUP (ORDER BY {})", x),
+            (Dominant, DuckDb) => format!("MODE({})", x),
+            (Median | Q1 | Q3, Postgres) => {
+                format!(
+                    "PERCENTILE_DISC({}) WITHIN GROUP (ORDER BY {})",
+                    quantile, x
+                )
+            },
+            (Median | Q1 | Q3, DuckDb) => format!("QUANTILE_DISC({}, {})", x, quantile),
+            (Join, Postgres) => format!("STRING_AGG(DISTINCT CAST({} AS TEXT), ', ')", x),
+            (Join, DuckDb) => format!("STRING_AGG(DISTINCT CAST({} AS VARCHAR), ', ')", x),
+            (StdDev, Postgres | DuckDb) => format!("STDDEV_POP({})", x),
+            (Var, Postgres | DuckDb) => format!("VAR_POP({})", x),
+
+            // Depend on row order or parent rows, which SQL doesn't have.
+            (
+                First | FirstByIndex | Last | LastByIndex | LastMinusFirst | PctSumParent
+                | PctSumGrandTotal,
+                _,
+            )
+            | (Dominant | Median | Q1 | Q3 | Join | StdDev | Var, Sqlite) => return None,
+        })
+    }
+
+    fn literal(&self, column: &str, value: &Scalar) -> String {
+        let dialect = self.options.dialect;
+        match value {
+            Scalar::Float(x) if !x.is_finite() => "NULL".to_owned(),
+            Scalar::Float(x) if self.options.schema.get(column) == Some(&ColumnType::Datetime) => {
+                match dialect {
+                    SqlDialect::Postgres => format!("TO_TIMESTAMP({} / 1000.0)", x),
+                    SqlDialect::DuckDb => format!("EPOCH_MS(CAST({} AS BIGINT))", x),
+                    SqlDialect::Sqlite => format!("DATETIME({} / 1000.0, 'unixepoch')", x),
+                }
+            },
+            Scalar::Float(x) => x.to_string(),
+            Scalar::String(x) => quote_str(x),
+            Scalar::Bool(x) if dialect == SqlDialect::Sqlite => u8::from(*x).to_string(),
+            Scalar::Bool(x) => x.to_string().to_uppercase(),
+            Scalar::Null => "NULL".to_owned(),
+        }
+    }
+
+    fn push_where(&mut self, sql: &mut String) {
+        let config = self.config;
+        if !config.filter.is_empty() {
+            let cond = self.filters(&config.filter, &config.filter_op);
+            sql.push_str(&format!("\nWHERE {}", cond));
+        }
+    }
+
+    /// `filter` joined by `filter_op`, with nested groups in parentheses. An
+    /// empty group filters nothing.
+    fn filters(&mut self, filter: &[FilterClause], filter_op: &FilterReducer) -> String {
+        if filter.is_empty() {
+            return TRUE.to_owned();
+        }
+
+        let sep = match filter_op {
+            FilterReducer::And => " AND ",
+            FilterReducer::Or => " OR ",
+        };
+
+        filter
+            .iter()
+            .map(|clause| match clause {
+                FilterClause::Filter(filter) => self.filter(filter),
+                FilterClause::Group(group) => {
+                    format!("({})", self.filters(&group.filter, &group.filter_op))
+                },
+            })
+            .collect::<Vec<_>>()
+            .join(sep)
+    }
+
+    fn filter(&mut self, filter: &Filter) -> String {
+        let column = filter.column();
+        if self.is_expression(column) {
+            self.report(format!("Filter {:?}", filter));
+            return TRUE.to_owned();
+        }
+
+        let like = match self.options.dialect {
+            SqlDialect::Sqlite => "LIKE",
+            _ => "ILIKE",
+        };
+
+        let col = quote(column);
+        match (filter.op(), filter.term()) {
+            ("is null", _) => format!("{} IS NULL", col),
+            ("is not null", _) => format!("{} IS NOT NULL", col),
+            (op @ ("==" | "!=" | "<" | ">" | "<=" | ">="), FilterTerm::Scalar(value))
+                if *value != Scalar::Null =>
+            {
+                let op = match op {
+                    "==" => "=",
+                    "!=" => "<>",
+                    op => op,
+                };
+
+                format!("{} {} {}", col, op, self.literal(column, value))
+            },
+            (op @ ("begins with" | "ends with" | "contains"), FilterTerm::Scalar(value))
+                if *value != Scalar::Null =>
+            {
+                let value = escape_like(&value.to_string());
+                let pattern = match op {
+                    "begins with" => format!("{}%", value),
+                    "ends with" => format!("%{}", value),
+                    _ => format!("%{}%", value),
+                };
+
+                format!("{} {} {} ESCAPE '\\'", col, like, quote_str(&pattern))
+            },
+            ("in", FilterTerm::Array(values)) if values.is_empty() => "1 = 0".to_owned(),
+            ("not in", FilterTerm::Array(values)) if values.is_empty() => TRUE.to_owned(),
+            (op @ ("in" | "not in"), FilterTerm::Array(values)) => {
+                let values = values
+                    .iter()
+                    .map(|x| self.literal(column, x))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!("{} {} ({})", col, op.to_uppercase(), values)
+            },
+            _ => {
+                self.report(format!("Filter {:?}", filter));
+                TRUE.to_owned()
+            },
+        }
+    }
+
+    fn sort(&mut self, sort: &Sort, grouped: bool) -> Option<String> {
+        let Sort(column, dir) = sort;
+        if self.is_expression(column) {
+            return None;
+        }
+
+        let (abs, dir) = match dir {
+            SortDir::None => return None,
+            SortDir::Asc => (false, "ASC"),
+            SortDir::Desc => (false, "DESC"),
+            SortDir::AscAbs => (true, "ASC"),
+            SortDir::DescAbs => (true, "DESC"),
+            SortDir::ColAsc | SortDir::ColDesc | SortDir::ColAscAbs | SortDir::ColDescAbs => {
+                self.report(format!("Sort {:?}", sort));
+                return None;
+            },
+        };
+
+        let expr = if grouped {
+            self.aggregate(column, None)?
+        } else {
+            quote(column)
+        };
+
+        Some(if abs {
+            format!("ABS({}) {}", expr, dir)
+        } else {
+            format!("{} {}", expr, dir)
+        })
+    }
+}
@@ -0,0 +1,43 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Conversions between SQL and [`crate::config::ViewConfig`].
+
+mod generate;
+#[cfg(feature = "sql")]
+mod query;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::generate::{
+    GeneratedSql, SqlDialect, SqlOptions, generate_split_by_sql, generate_sql,
+};
+#[cfg(feature = "sql")]
+pub use self::query::compile_sql;
+use crate::config::ViewConfigUpdate;
+#[cfg(not(feature = "sql"))]
+use crate::utils::*;
+use crate::view::ViewWindow;
+
+/// A SQL query compiled to the arguments of a [`crate::View`]: the config to
+/// create it with, and the window to read from it.
+#[derive(Clone, Debug, Default)]
+pub struct SqlQuery {
+    pub config: ViewConfigUpdate,
+    pub window: ViewWindow,
+}
+
+#[cfg(not(feature = "sql"))]
+pub fn compile_sql(_sql: &str) -> ClientResult<SqlQuery> {
+    Err(ClientError::NotImplemented("sql"))
+}
@@ -0,0 +1,776 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Compiles a subset of SQL `SELECT` into a [`SqlQuery`].
+//!
+//! - `SELECT` items may be `*`, columns, aggregate calls (see [`aggregate`]) or
+//!   scalar expressions, which become ExprTK expressions named by their alias
+//!   (or their SQL text).
+//! - `WHERE` may be any `AND`/`OR` combination of simple predicates on a
+//!   column, which become [`Filter`]s. Parenthesized terms of the other
+//!   operator become nested [`FilterGroup`]s.
+//! - A single `GROUP BY` column or expression becomes `group_by` (more would
+//!   return the engine's subtotal rows too). Aggregates without a `GROUP BY`
+//!   compute a single total row, like SQL.
+//! - `ORDER BY` becomes `sort`, and `LIMIT`/`OFFSET` become the window. The
+//!   engine sorts by a grouped column's aggregate rather than its values, so
+//!   the grouped column may only be ordered by as the first ascending term,
+//!   which is the engine's own order.
+//!
+//! The grand total row the engine emits for `group_by` views is skipped by the
+//! returned window. Anything else is rejected with an error naming the
+//! unsupported fragment.
+
+use std::collections::HashMap;
+
+use sqlparser::ast::{
+    BinaryOperator, Distinct, DuplicateTreatment, Expr, Function, FunctionArg, FunctionArgExpr,
+    FunctionArguments, GroupByExpr, OrderByExpr, Query, Select, SelectItem, SetExpr, Statement,
+    TableFactor, UnaryOperator, Value,
+};
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
+
+use super::SqlQuery;
+use crate::config::{
+    Aggregate, Expressions, Filter, FilterClause, FilterGroup, FilterReducer, FilterTerm, Scalar,
+    SingleAggregate, Sort, SortDir, ViewConfigUpdate,
+};
+use crate::utils::*;
+use crate::view::ViewWindow;
+
+/// The name of the constant expression grouped by for aggregates without a
+/// `GROUP BY`.
+const TOTAL_GROUP: &str = "__sql_total__";
+
+/// ExprTK functions which may be called from SQL, by the same name.
+const SCALAR_FUNCTIONS: &[&str] = &[
+    "abs",
+    "bucket",
+    "ceil",
+    "concat",
+    "exp",
+    "floor",
+    "hour_of_day",
+    "day_of_week",
+    "inrange",
+    "length",
+    "log",
+    "log10",
+    "lower",
+    "max",
+    "min",
+    "month_of_year",
+    "now",
+    "percent_of",
+    "pow",
+    "round",
+    "sqrt",
+    "substring",
+    "today",
+    "upper",
+];
+
+fn error(msg: impl Into<String>) -> ClientError {
+    ClientError::SqlError(msg.into())
+}
+
+fn unsupported(what: impl std::fmt::Display) -> ClientError {
+    error(format!("{} is not supported", what))
+}
+
+/// Compile `sql`, a single `SELECT` statement, into a [`SqlQuery`]. The
+/// `FROM` clause names the table but is otherwise ignored.
+pub fn compile_sql(sql: &str) -> ClientResult<SqlQuery> {
+    let mut statements =
+        Parser::parse_sql(&GenericDialect {}, sql).map_err(|err| error(err.to_string()))?;
+
+    if statements.len() != 1 {
+        return Err(error(format!(
+            "Expected 1 statement, found {}",
+            statements.len()
+        )));
+    }
+
+    match statements.remove(0) {
+        Statement::Query(query) => compile_query(*query),
+        statement => Err(unsupported(format!("Statement `{}`", statement))),
+    }
+}
+
+fn compile_query(query: Query) -> ClientResult<SqlQuery> {
+    if let Some(with) = &query.with {
+        return Err(unsupported(format!("`{}`", with)));
+    }
+
+    let select = match *query.body {
+        SetExpr::Select(select) => *select,
+        body => return Err(unsupported(format!("`{}`", body))),
+    };
+
+    let mut compiler = Compiler::default();
+    compiler.select(select)?;
+    for order in &query.order_by {
+        compiler.order_by(order)?;
+    }
+
+    let offset = query
+        .offset
+        .as_ref()
+        .map(|offset| to_count(&offset.value, "OFFSET"))
+        .transpose()?
+        .unwrap_or_default();
+
+    let limit = query
+        .limit
+        .as_ref()
+        .map(|limit| to_count(limit, "LIMIT"))
+        .transpose()?;
+
+    // `group_by` views lead with a grand total row, which SQL doesn't have -
+    // unless it is the only row requested.
+    let skip = usize::from(!compiler.group_by.is_empty() && !compiler.total_only);
+    let start_row = offset + skip;
+    let window = ViewWindow {
+        start_row: Some(start_row as f32).filter(|x| *x > 0.0),
+        end_row: limit.map(|limit| (start_row + limit) as f32),
+        ..ViewWindow::default()
+    };
+
+    Ok(SqlQuery {
+        config: compiler.into_config(),
+        window,
+    })
+}
+
+fn to_count(expr: &Expr, clause: &str) -> ClientResult<usize> {
+    match expr {
+        Expr::Value(Value::Number(x, _)) => x
+            .parse()
+            .map_err(|_| error(format!("{} must be a non-negative integer", clause))),
+        expr => Err(unsupported(format!("{} `{}`", clause, expr))),
+    }
+}
+
+#[derive(Default)]
+struct Compiler {
+    /// [`None`] for `SELECT *`.
+    columns: Option<Vec<String>>,
+    expressions: HashMap<String, String>,
+    aggregates: HashMap<String, Aggregate>,
+    group_by: Vec<String>,
+    filter: Vec<FilterClause>,
+    filter_op: Option<FilterReducer>,
+    sort: Vec<Sort>,
+
+    /// The column name each `SELECT` item's SQL text and alias resolves to,
+    /// for `GROUP BY` and `ORDER BY`.
+    names: HashMap<String, String>,
+
+    /// Aggregates without a `GROUP BY`, so only the total row is wanted.
+    total_only: bool,
+
+    /// `ORDER BY` the grouped column, which is the engine's order and which
+    /// leaves no ties for later terms.
+    group_ordered: bool,
+}
+
+impl Compiler {
+    fn into_config(self) -> ViewConfigUpdate {
+        ViewConfigUpdate {
+            group_by: Some(self.group_by).filter(|x| !x.is_empty()),
+            group_by_depth: self.total_only.then_some(0),
+            columns: self.columns.map(|x| x.into_iter().map(Some).collect()),
+            filter: Some(self.filter).filter(|x| !x.is_empty()),
+            filter_op: self.filter_op,
+            sort: Some(self.sort).filter(|x| !x.is_empty()),
+            expressions: Some(Expressions(self.expressions)).filter(|x| !x.is_empty()),
+            aggregates: Some(self.aggregates).filter(|x| !x.is_empty()),
+            ..ViewConfigUpdate::default()
+        }
+    }
+
+    fn select(&mut self, select: Select) -> ClientResult<()> {
+        match &select.distinct {
+            None => (),
+            Some(Distinct::Distinct) => return Err(unsupported("SELECT DISTINCT")),
+            Some(distinct) => return Err(unsupported(format!("`{}`", distinct))),
+        }
+
+        if let Some(top) = &select.top {
+            return Err(unsupported(format!("`{}`", top)));
+        }
+
+        if let Some(having) = &select.having {
+            return Err(unsupported(format!("HAVING `{}`", having)));
+        }
+
+        match select.from.as_slice() {
+            [] => (),
+            [from] if from.joins.is_empty() => {
+                if !matches!(from.relation, TableFactor::Table { .. }) {
+                    return Err(unsupported(format!("FROM `{}`", from.relation)));
+                }
+            },
+            [from] => return Err(unsupported(format!("JOIN `{}`", from.joins[0]))),
+            _ => return Err(unsupported("FROM with multiple tables")),
+        }
+
+        let items = select
+            .projection
+            .into_iter()
+            .map(|item| match item {
+                SelectItem::UnnamedExpr(expr) => Ok(Some((expr, None))),
+                SelectItem::ExprWithAlias { expr, alias } => Ok(Some((expr, Some(alias.value)))),
+                SelectItem::Wildcard(_) => Ok(None),
+                item => Err(unsupported(format!("`{}`", item))),
+            })
+            .collect::<ClientResult<Vec<_>>>()?;
+
+        let group_exprs = match select.group_by {
+            GroupByExpr::Expressions(exprs, ..) => exprs,
+            group_by => return Err(unsupported(format!("`{}`", group_by))),
+        };
+
+        if group_exprs.len() > 1 {
+            return Err(unsupported("GROUP BY more than one column"));
+        }
+
+        let aliases: HashMap<&str, &Expr> = items
+            .iter()
+            .flatten()
+            .filter_map(|(expr, alias)| Some((alias.as_deref()?, expr)))
+            .collect();
+
+        for expr in &group_exprs {
+            let name = match expr {
+                Expr::Identifier(ident) => match aliases.get(ident.value.as_str()) {
+                    Some(aliased) if !is_aggregate(aliased) => {
+                        self.alias(&ident.value, aliased)?;
+                        ident.value.clone()
+                    },
+                    Some(_) => return Err(error(format!("Cannot GROUP BY aggregate `{}`", expr))),
+                    None => ident.value.clone(),
+                },
+                expr if is_aggregate(expr) => {
+                    return Err(error(format!("Cannot GROUP BY aggregate `{}`", expr)));
+                },
+                expr => self.expression(expr.to_string(), expr)?,
+            };
+
+            self.group_by.push(name);
+        }
+
+        let has_aggregates = items.iter().flatten().any(|(expr, _)| is_aggregate(expr));
+        if has_aggregates && self.group_by.is_empty() {
+            self.expressions
+                .insert(TOTAL_GROUP.to_owned(), "'total'".to_owned());
+            self.group_by.push(TOTAL_GROUP.to_owned());
+            self.total_only = true;
+        }
+
+        if items.iter().any(Option::is_none) {
+            if items.len() > 1 {
+                return Err(unsupported("`*` with other SELECT items"));
+            }
+
+            if !self.group_by.is_empty() {
+                return Err(error("SELECT * cannot be used with GROUP BY"));
+            }
+        } else {
+            self.columns = Some(vec![]);
+            for (expr, alias) in items.iter().flatten() {
+                self.select_item(expr, alias.as_deref())?;
+            }
+        }
+
+        if let Some(selection) = &select.selection {
+            self.filter(selection)?;
+        }
+
+        Ok(())
+    }
+
+    fn select_item(&mut self, expr: &Expr, alias: Option<&str>) -> ClientResult<()> {
+        let text = expr.to_string();
+        let grouped = self
+            .group_by
+            .iter()
+            .find(|x| Some(x.as_str()) == alias || **x == text)
+            .cloned();
+
+        let name = match (expr, alias, grouped) {
+            (Expr::Function(func), ..) if is_aggregate(expr) => {
+                self.aggregate(func, expr, alias)?
+            },
+            (.., Some(grouped)) => {
+                // Grouped columns are the row path, not columns.
+                self.names.insert(text, grouped);
+                return Ok(());
+            },
+            _ if !self.group_by.is_empty() => {
+                return Err(error(format!(
+                    "`{}` must appear in GROUP BY or be used in an aggregate function",
+                    text
+                )));
+            },
+            (Expr::Identifier(ident), None, _) => ident.value.clone(),
+            (expr, Some(alias), _) => {
+                self.alias(alias, expr)?;
+                alias.to_owned()
+            },
+            (expr, None, _) => self.expression(text.clone(), expr)?,
+        };
+
+        self.names.insert(text, name.clone());
+        if let Some(alias) = alias {
+            self.names.insert(alias.to_owned(), name.clone());
+        }
+
+        self.columns.get_or_insert_with(Vec::new).push(name);
+        Ok(())
+    }
+
+    /// Name `expr` as `alias`, via an expression.
+    fn alias(&mut self, alias: &str, expr: &Expr) -> ClientResult<()> {
+        self.expression(alias.to_owned(), expr).map(|_| ())
+    }
+
+    fn expression(&mut self, name: String, expr: &Expr) -> ClientResult<String> {
+        let exprtk = to_exprtk(expr)?;
+        self.expressions.insert(name.clone(), exprtk);
+        Ok(name)
+    }
+
+    /// Add the column for an aggregate call, returning its name.
+    fn aggregate(
+        &mut self,
+        func: &Function,
+        expr: &Expr,
+        alias: Option<&str>,
+    ) -> ClientResult<String> {
+        let (distinct, args) = match &func.args {
+            FunctionArguments::List(list) => (
+                list.duplicate_treatment == Some(DuplicateTreatment::Distinct),
+                list.args.as_slice(),
+            ),
+            _ => (false, &[][..]),
+        };
+
+        let fname = func.name.to_string().to_lowercase();
+        let agg = match (fname.as_str(), distinct) {
+            ("count", true) => SingleAggregate::DistinctCount,
+            (_, true) => return Err(unsupported(format!("DISTINCT in `{}`", expr))),
+            (name, false) => {
+                aggregate(name).ok_or_else(|| unsupported(format!("Function `{}`", expr)))?
+            },
+        };
+
+        let name = match args {
+            [FunctionArg::Unnamed(FunctionArgExpr::Wildcard)] if fname == "count" => {
+                // Sum a constant, as `count` skips nothing but needs a column.
+                let name = alias.map_or_else(|| expr.to_string(), str::to_owned);
+                self.expressions.insert(name.clone(), "1".to_owned());
+                self.aggregates.insert(
+                    name.clone(),
+                    Aggregate::SingleAggregate(SingleAggregate::Sum),
+                );
+                return Ok(name);
+            },
+            [FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Identifier(ident)))]
+                if alias.is_none_or(|x| x == ident.value) =>
+            {
+                ident.value.clone()
+            },
+            [FunctionArg::Unnamed(FunctionArgExpr::Expr(arg))] => {
+                let name = alias.map_or_else(|| expr.to_string(), str::to_owned);
+                self.expression(name, arg)?
+            },
+            _ => return Err(unsupported(format!("Arguments of `{}`", expr))),
+        };
+
+        let agg = Aggregate::SingleAggregate(agg);
+        match self.aggregates.get(&name) {
+            Some(existing) if *existing != agg => Err(error(format!(
+                "`{}` is aggregated twice, alias one of them",
+                name
+            ))),
+            _ => {
+                self.aggregates.insert(name.clone(), agg);
+                Ok(name)
+            },
+        }
+    }
+
+    fn filter(&mut self, expr: &Expr) -> ClientResult<()> {
+        let group = to_filter_group(expr)?;
+        self.filter.extend(group.filter);
+        if group.filter_op == FilterReducer::Or {
+            self.filter_op = Some(group.filter_op);
+        }
+
+        Ok(())
+    }
+
+    fn order_by(&mut self, order: &OrderByExpr) -> ClientResult<()> {
+        if order.nulls_first.is_some() {
+            return Err(unsupported(format!("NULLS FIRST/LAST in `{}`", order)));
+        }
+
+        if self.group_ordered {
+            return Ok(());
+        }
+
+        let text = order.expr.to_string();
+        let name = match (self.names.get(&text), &order.expr) {
+            (Some(name), _) => name.clone(),
+            (None, Expr::Identifier(ident)) => ident.value.clone(),
+            (None, expr) => {
+                return Err(unsupported(format!(
+                    "ORDER BY `{}` which is not in SELECT",
+                    expr
+                )));
+            },
+        };
+
+        let dir = match order.asc {
+            Some(false) => SortDir::Desc,
+            _ => SortDir::Asc,
+        };
+
+        if self.group_by.contains(&name) && !self.total_only {
+            if dir != SortDir::Asc || !self.sort.is_empty() {
+                return Err(unsupported(format!(
+                    "ORDER BY grouped column `{}` except as the first, ascending term",
+                    order
+                )));
+            }
+
+            self.group_ordered = true;
+            return Ok(());
+        }
+
+        self.sort.push(Sort(name, dir));
+        Ok(())
+    }
+}
+
+/// The [`SingleAggregate`] for a SQL aggregate function name.
+fn aggregate(name: &str) -> Option<SingleAggregate> {
+    Some(match name {
+        "sum" => SingleAggregate::Sum,
+        "count" => SingleAggregate::Count,
+        "avg" | "mean" => SingleAggregate::Avg,
+        "min" => SingleAggregate::Min,
+        "max" => SingleAggregate::Max,
+        "median" => SingleAggregate::Median,
+        "first" | "first_value" => SingleAggregate::First,
+        "last" | "last_value" => SingleAggregate::Last,
+        "stddev" | "stddev_pop" => SingleAggregate::StdDev,
+        "var" | "variance" | "var_pop" => SingleAggregate::Var,
+        "any_value" => SingleAggregate::Any,
+        "string_agg" | "group_concat" => SingleAggregate::Join,
+        _ => return None,
+    })
+}
+
+/// Whether `expr` is a call to an aggregate function. `min` and `max` with
+/// more than one argument are the scalar ExprTK functions.
+fn is_aggregate(expr: &Expr) -> bool {
+    let Expr::Function(func) = expr else {
+        return false;
+    };
+
+    let name = func.name.to_string().to_lowercase();
+    let num_args = match &func.args {
+        FunctionArguments::List(list) => list.args.len(),
+        _ => 0,
+    };
+
+    aggregate(&name).is_some() && !(num_args > 1 && (name == "min" || name == "max"))
+}
+
+/// The terms of an `AND` or `OR` condition, with terms of the other operator
+/// as nested groups.
+fn to_filter_group(expr: &Expr) -> ClientResult<FilterGroup> {
+    let (filter_op, by) = match unnest(expr) {
+        Expr::BinaryOp {
+            op: BinaryOperator::Or,
+            ..
+        } => (FilterReducer::Or, BinaryOperator::Or),
+        _ => (FilterReducer::And, BinaryOperator::And),
+    };
+
+    let filter = flatten(expr, &by)
+        .into_iter()
+        .map(|term| match unnest(term) {
+            term @ Expr::BinaryOp {
+                op: BinaryOperator::And | BinaryOperator::Or,
+                ..
+            } => Ok(to_filter_group(term)?.into()),
+            term => Ok(to_filter(term)?.into()),
+        })
+        .collect::<ClientResult<Vec<_>>>()?;
+
+    Ok(FilterGroup::new(filter_op, filter))
+}
+
+fn unnest(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Nested(inner) => unnest(inner),
+        expr => expr,
+    }
+}
+
+fn flatten<'a>(expr: &'a Expr, by: &BinaryOperator) -> Vec<&'a Expr> {
+    match expr {
+        Expr::BinaryOp { left, op, right } if op == by => {
+            let mut terms = flatten(left, by);
+            terms.extend(flatten(right, by));
+            terms
+        },
+        Expr::Nested(inner) => match inner.as_ref() {
+            Expr::BinaryOp { op, .. } if op == by => flatten(inner, by),
+            _ => vec![expr],
+        },
+        expr => vec![expr],
+    }
+}
+
+fn column_name(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Identifier(ident) => Some(&ident.value),
+        Expr::CompoundIdentifier(idents) => idents.last().map(|x| x.value.as_str()),
+        Expr::Nested(inner) => column_name(inner),
+        _ => None,
+    }
+}
+
+fn to_scalar(expr: &Expr) -> Option<Scalar> {
+    match expr {
+        Expr::Value(Value::Number(x, _)) => x.parse().ok().map(Scalar::Float),
+        Expr::Value(Value::SingleQuotedString(x)) => Some(Scalar::String(x.clone())),
+        Expr::Value(Value::Boolean(x)) => Some(Scalar::Bool(*x)),
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr,
+        } => match to_scalar(expr)? {
+            Scalar::Float(x) => Some(Scalar::Float(-x)),
+            _ => None,
+        },
+        Expr::Nested(inner) => to_scalar(inner),
+        _ => None,
+    }
+}
+
+fn to_filter(expr: &Expr) -> ClientResult<Filter> {
+    let invalid = || unsupported(format!("WHERE term `{}`", expr));
+    match expr {
+        Expr::BinaryOp { left, op, right } => {
+            let (column, op, value) = match (column_name(left), column_name(right)) {
+                (Some(column), None) => (column, op.clone(), right),
+                (None, Some(column)) => {
+                    let op = match op {
+                        BinaryOperator::Lt => BinaryOperator::Gt,
+                        BinaryOperator::LtEq => BinaryOperator::GtEq,
+                        BinaryOperator::Gt => BinaryOperator::Lt,
+                        BinaryOperator::GtEq => BinaryOperator::LtEq,
+                        op => op.clone(),
+                    };
+
+                    (column, op, left)
+                },
+                _ => return Err(invalid()),
+            };
+
+            let op = match op {
+                BinaryOperator::Eq => "==",
+                BinaryOperator::NotEq => "!=",
+                BinaryOperator::Lt => "<",
+                BinaryOperator::LtEq => "<=",
+                BinaryOperator::Gt => ">",
+                BinaryOperator::GtEq => ">=",
+                _ => return Err(invalid()),
+            };
+
+            let value = to_scalar(value).ok_or_else(invalid)?;
+            Ok(Filter::new(column, op, FilterTerm::Scalar(value)))
+        },
+        Expr::IsNull(inner) | Expr::IsNotNull(inner) => {
+            let column = column_name(inner).ok_or_else(invalid)?;
+            let op = match expr {
+                Expr::IsNull(_) => "is null",
+                _ => "is not null",
+            };
+
+            Ok(Filter::new(column, op, FilterTerm::Scalar(Scalar::Null)))
+        },
+        Expr::InList {
+            expr: inner,
+            list,
+            negated,
+        } => {
+            let column = column_name(inner).ok_or_else(invalid)?;
+            let values = list
+                .iter()
+                .map(to_scalar)
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(invalid)?;
+
+            let op = if *negated { "not in" } else { "in" };
+            Ok(Filter::new(column, op, FilterTerm::Array(values)))
+        },
+        Expr::Like {
+            negated: false,
+            expr: inner,
+            pattern,
+            escape_char: None,
+            ..
+        } => {
+            let column = column_name(inner).ok_or_else(invalid)?;
+            let Some(Scalar::String(pattern)) = to_scalar(pattern) else {
+                return Err(invalid());
+            };
+
+            let (op, term) = match (pattern.strip_prefix('%'), pattern.strip_suffix('%')) {
+                (Some(rest), Some(_)) if rest.len() > 1 => ("contains", &rest[..rest.len() - 1]),
+                (Some(rest), None) => ("ends with", rest),
+                (None, Some(rest)) => ("begins with", rest),
+                (None, None) => ("==", pattern.as_str()),
+                _ => return Err(invalid()),
+            };
+
+            if term.contains(['%', '_']) {
+                return Err(invalid());
+            }
+
+            Ok(Filter::new(column, op, FilterTerm::Scalar(term.into())))
+        },
+        Expr::Nested(inner) => to_filter(inner),
+        _ => Err(invalid()),
+    }
+}
+
+/// Escape `text` for an ExprTK literal delimited by `quote`, i.e. a column
+/// name (`"`) or a string (`'`).
+fn exprtk_quote(text: &str, quote: char) -> String {
+    let text = text
+        .replace('\\', "\\\\")
+        .replace(quote, &format!("\\{}", quote));
+
+    format!("{0}{1}{0}", quote, text)
+}
+
+/// Translate a scalar SQL expression to ExprTK.
+fn to_exprtk(expr: &Expr) -> ClientResult<String> {
+    Ok(match expr {
+        Expr::Identifier(_) | Expr::CompoundIdentifier(_) => {
+            exprtk_quote(&column_name(expr).unwrap_or_default(), '"')
+        },
+        Expr::Value(Value::Number(x, _)) => x.clone(),
+        Expr::Value(Value::SingleQuotedString(x)) => exprtk_quote(x, '\''),
+        Expr::Value(Value::Boolean(x)) => x.to_string(),
+        Expr::Nested(inner) => format!("({})", to_exprtk(inner)?),
+        Expr::UnaryOp { op, expr: inner } => match op {
+            UnaryOperator::Minus => format!("-({})", to_exprtk(inner)?),
+            UnaryOperator::Plus => to_exprtk(inner)?,
+            UnaryOperator::Not => format!("not({})", to_exprtk(inner)?),
+            op => return Err(unsupported(format!("Operator `{}` in `{}`", op, expr))),
+        },
+        Expr::BinaryOp { left, op, right } => {
+            let (left, right) = (to_exprtk(left)?, to_exprtk(right)?);
+            let op = match op {
+                BinaryOperator::Plus => "+",
+                BinaryOperator::Minus => "-",
+                BinaryOperator::Multiply => "*",
+                BinaryOperator::Divide => "/",
+                BinaryOperator::Modulo => "%",
+                BinaryOperator::Eq => "==",
+                BinaryOperator::NotEq => "!=",
+                BinaryOperator::Lt => "<",
+                BinaryOperator::LtEq => "<=",
+                BinaryOperator::Gt => ">",
+                BinaryOperator::GtEq => ">=",
+                BinaryOperator::And => "and",
+                BinaryOperator::Or => "or",
+                BinaryOperator::StringConcat => return Ok(format!("concat({}, {})", left, right)),
+                op => return Err(unsupported(format!("Operator `{}` in `{}`", op, expr))),
+            };
+
+            format!("({} {} {})", left, op, right)
+        },
+        Expr::IsNull(inner) => format!("is_null({})", to_exprtk(inner)?),
+        Expr::IsNotNull(inner) => format!("is_not_null({})", to_exprtk(inner)?),
+        Expr::Cast {
+            expr: inner,
+            data_type,
+            ..
+        } => {
+            let func = match data_type.to_string().to_lowercase().as_str() {
+                "int" | "integer" | "bigint" | "smallint" => "integer",
+                "float" | "double" | "real" | "double precision" | "numeric" | "decimal" => "float",
+                "varchar" | "text" | "string" | "char" => "string",
+                "boolean" | "bool" => "boolean",
+                "date" => "date",
+                "timestamp" | "datetime" => "datetime",
+                _ => return Err(unsupported(format!("CAST to {}", data_type))),
+            };
+
+            format!("{}({})", func, to_exprtk(inner)?)
+        },
+        Expr::Case {
+            operand: None,
+            conditions,
+            results,
+            else_result: Some(else_result),
+        } => {
+            let mut exprtk = String::new();
+            for (cond, result) in conditions.iter().zip(results) {
+                exprtk.push_str(&format!(
+                    "if ({}) {{ {} }} else ",
+                    to_exprtk(cond)?,
+                    to_exprtk(result)?
+                ));
+            }
+
+            format!("{}{{ {} }}", exprtk, to_exprtk(else_result)?)
+        },
+        Expr::Function(func) if !is_aggregate(expr) => {
+            let name = func.name.to_string().to_lowercase();
+            if !SCALAR_FUNCTIONS.contains(&name.as_str()) {
+                return Err(unsupported(format!("Function `{}`", func.name)));
+            }
+
+            let args = match &func.args {
+                FunctionArguments::None => vec![],
+                FunctionArguments::List(list) if list.duplicate_treatment.is_none() => list
+                    .args
+                    .iter()
+                    .map(|arg| match arg {
+                        FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)) => to_exprtk(arg),
+                        arg => Err(unsupported(format!("Argument `{}` in `{}`", arg, expr))),
+                    })
+                    .collect::<ClientResult<Vec<_>>>()?,
+                _ => return Err(unsupported(format!("Arguments of `{}`", expr))),
+            };
+
+            format!("{}({})", name, args.join(", "))
+        },
+        Expr::Function(_) => {
+            return Err(error(format!(
+                "Aggregate `{}` must be a top-level SELECT item",
+                expr
+            )));
+        },
+        expr => return Err(unsupported(format!("Expression `{}`", expr))),
+    })
+}
@@ -0,0 +1,216 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::HashMap;
+
+use crate::config::*;
+use crate::proto::ColumnType;
+use crate::sql::*;
+
+fn config(json: &str) -> ViewConfig {
+    serde_json::from_str(json).unwrap()
+}
+
+fn options(dialect: SqlDialect) -> SqlOptions {
+    SqlOptions {
+        dialect,
+        schema: HashMap::from([
+            ("Sales".to_owned(), ColumnType::Float),
+            ("Region".to_owned(), ColumnType::String),
+            ("Order Date".to_owned(), ColumnType::Datetime),
+        ]),
+        ..SqlOptions::default()
+    }
+}
+
+#[test]
+fn test_flat() {
+    let config = config(
+        r#"{
+            "columns": ["Region", "Sales"],
+            "filter": [["Sales", ">", 10], ["Region", "in", ["East", "West"]]],
+            "sort": [["Sales", "desc abs"]]
+        }"#,
+    );
+
+    let sql = generate_sql("orders", &config, &options(SqlDialect::Postgres));
+    assert_eq!(
+        sql.sql,
+        "SELECT \"Region\", \"Sales\"\nFROM \"orders\"\nWHERE \"Sales\" > 10 AND \"Region\" IN \
+         ('East', 'West')\nORDER BY ABS(\"Sales\") DESC"
+    );
+
+    assert!(sql.untranslated.is_empty());
+}
+
+#[test]
+fn test_nested_filter() {
+    let config = config(
+        r#"{
+            "columns": ["Sales"],
+            "filter": [
+                {"filter": [["Region", "==", "East"], ["Sales", ">", 10]]},
+                ["Region", "in", ["West"]]
+            ],
+            "filter_op": "or"
+        }"#,
+    );
+
+    let sql = generate_sql("orders", &config, &options(SqlDialect::Postgres));
+    assert_eq!(
+        sql.sql,
+        "SELECT \"Sales\"\nFROM \"orders\"\nWHERE (\"Region\" = 'East' AND \"Sales\" > 10) OR \
+         \"Region\" IN ('West')"
+    );
+
+    assert!(sql.untranslated.is_empty());
+}
+
+#[test]
+fn test_nested_filter_untranslated() {
+    let config = config(
+        r#"{
+            "columns": ["Sales"],
+            "expressions": {"Margin": "\"Profit\" / \"Sales\""},
+            "filter": [
+                ["Sales", ">", 10],
+                {
+                    "filter": [["Margin", ">", 0.5], ["Region", "not in", []]],
+                    "filter_op": "or"
+                },
+                {"filter": [["Region", "==", "East"], ["Sales", "unknown", 1]], "filter_op": "or"}
+            ]
+        }"#,
+    );
+
+    let sql = generate_sql("orders", &config, &options(SqlDialect::Postgres));
+    assert_eq!(
+        sql.sql,
+        "SELECT \"Sales\"\nFROM \"orders\"\nWHERE \"Sales\" > 10 AND (1 = 1 OR 1 = 1) AND \
+         (\"Region\" = 'East' OR 1 = 1)"
+    );
+
+    assert_eq!(sql.untranslated, vec![
+        "Expression `Margin`",
+        "Filter Filter(\"Margin\", \">\", Scalar(Float(0.5)))",
+        "Filter Filter(\"Sales\", \"unknown\", Scalar(Float(1.0)))",
+    ]);
+}
+
+#[test]
+fn test_group_by() {
+    let config = config(
+        r#"{
+            "group_by": ["Region"],
+            "columns": ["Sales", "Region"],
+            "aggregates": {"Sales": "avg"},
+            "filter": [["Region", "contains", "o'_"], ["Sales", "is not null", null]],
+            "filter_op": "or",
+            "sort": [["Sales", "desc"]]
+        }"#,
+    );
+
+    let sql = generate_sql("sales.orders", &config, &options(SqlDialect::DuckDb));
+    assert_eq!(
+        sql.sql,
+        "SELECT \"Region\", AVG(\"Sales\") AS \"Sales\", COUNT(\"Region\") AS \"Region\"\nFROM \
+         \"sales\".\"orders\"\nWHERE \"Region\" ILIKE '%o''\\_%' ESCAPE '\\' OR \"Sales\" IS NOT \
+         NULL\nGROUP BY \"Region\"\nORDER BY AVG(\"Sales\") DESC, \"Region\" ASC"
+    );
+
+    assert!(sql.untranslated.is_empty());
+}
+
+#[test]
+fn test_group_by_depth() {
+    let config =
+        config(r#"{"group_by": ["Region", "Sales"], "columns": ["Sales"], "group_by_depth": 0}"#);
+
+    let sql = generate_sql("orders", &config, &options(SqlDialect::Sqlite));
+    assert_eq!(
+        sql.sql,
+        "SELECT SUM(\"Sales\") AS \"Sales\"\nFROM \"orders\""
+    );
+}
+
+#[test]
+fn test_split_by() {
+    let config = config(
+        r#"{
+            "group_by": ["Region"],
+            "split_by": ["Category"],
+            "columns": ["Sales"],
+            "aggregates": {"Sales": ["weighted mean", "Quantity"]},
+            "filter": [["Order Date", ">=", 86400000]]
+        }"#,
+    );
+
+    let mut options = options(SqlDialect::Sqlite);
+    let split = generate_split_by_sql("orders", &config, &options).unwrap();
+    assert_eq!(
+        split.sql,
+        "SELECT DISTINCT \"Category\"\nFROM \"orders\"\nWHERE \"Order Date\" >= DATETIME(86400000 \
+         / 1000.0, 'unixepoch')\nORDER BY \"Category\""
+    );
+
+    options.split_by_values = vec![vec!["Tech".into()], vec![Scalar::Null]];
+    let sql = generate_sql("orders", &config, &options);
+    assert_eq!(
+        sql.sql,
+        "SELECT \"Region\", SUM(CASE WHEN \"Category\" = 'Tech' THEN \"Sales\" * \"Quantity\" \
+         END) * 1.0 / SUM(CASE WHEN \"Category\" = 'Tech' THEN \"Quantity\" END) AS \
+         \"Tech|Sales\", SUM(CASE WHEN \"Category\" IS NULL THEN \"Sales\" * \"Quantity\" END) * \
+         1.0 / SUM(CASE WHEN \"Category\" IS NULL THEN \"Quantity\" END) AS \"|Sales\"\nFROM \
+         \"orders\"\nWHERE \"Order Date\" >= DATETIME(86400000 / 1000.0, 'unixepoch')\nGROUP BY \
+         \"Region\"\nORDER BY \"Region\" ASC"
+    );
+
+    assert!(sql.untranslated.is_empty());
+}
+
+#[test]
+fn test_untranslated() {
+    let config = config(
+        r#"{
+            "group_by": ["Region"],
+            "split_by": ["Category"],
+            "columns": ["Sales", "Profit", "Margin"],
+            "aggregates": {"Sales": "median"},
+            "expressions": {"Margin": "\"Profit\" / \"Sales\""},
+            "sort": [["Sales", "col asc"]]
+        }"#,
+    );
+
+    let mut options = options(SqlDialect::Sqlite);
+    options.split_by_values = vec![vec!["A".into()], vec!["B".into()]];
+    let sql = generate_sql("orders", &config, &options);
+    assert_eq!(
+        sql.sql,
+        "SELECT \"Region\"\nFROM \"orders\"\nGROUP BY \"Region\"\nORDER BY \"Region\" ASC"
+    );
+    assert_eq!(sql.untranslated, vec![
+        "Expression `Margin`",
+        "Aggregate `median` of `Sales` in Sqlite",
+        "Aggregate of `Profit`, which is not in `aggregates` or `schema`",
+        "Sort Sort(\"Sales\", ColAsc)",
+    ]);
+
+    let sql = generate_sql("orders", &config, &SqlOptions {
+        dialect: SqlDialect::Postgres,
+        ..options
+    });
+
+    assert!(sql.sql.contains(
+        "PERCENTILE_DISC(0.5) WITHIN GROUP (ORDER BY CASE WHEN \"Category\" = 'B' THEN \"Sales\" \
+         END) AS \"B|Sales\""
+    ));
+}
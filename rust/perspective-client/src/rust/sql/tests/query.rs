@@ -0,0 +1,167 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use crate::config::*;
+use crate::sql::*;
+
+fn compile(sql: &str) -> SqlQuery {
+    compile_sql(sql).unwrap()
+}
+
+fn compile_err(sql: &str) -> String {
+    compile_sql(sql).unwrap_err().to_string()
+}
+
+#[test]
+fn test_select_star() {
+    let query = compile("SELECT * FROM t");
+    assert_eq!(query.config.columns, None);
+    assert_eq!(query.config.group_by, None);
+    assert_eq!(query.window.start_row, None);
+    assert_eq!(query.window.end_row, None);
+}
+
+#[test]
+fn test_select_columns_with_limit_offset() {
+    let query = compile("SELECT x, y FROM t LIMIT 10 OFFSET 5");
+    assert_eq!(
+        query.config.columns,
+        Some(vec![Some("x".to_owned()), Some("y".to_owned())])
+    );
+
+    assert_eq!(query.window.start_row, Some(5.0));
+    assert_eq!(query.window.end_row, Some(15.0));
+}
+
+#[test]
+fn test_where_and() {
+    let query = compile("SELECT * FROM t WHERE x > 1 AND 'a' = y AND z IS NULL");
+    assert_eq!(
+        query.config.filter,
+        Some(vec![
+            Filter::new("x", ">", FilterTerm::Scalar(Scalar::Float(1.0))).into(),
+            Filter::new("y", "==", FilterTerm::Scalar("a".into())).into(),
+            Filter::new("z", "is null", FilterTerm::Scalar(Scalar::Null)).into(),
+        ])
+    );
+
+    assert_eq!(query.config.filter_op, None);
+}
+
+#[test]
+fn test_where_or_in_like() {
+    let query = compile("SELECT * FROM t WHERE x IN ('a', 'b') OR y LIKE 'ab%'");
+    assert_eq!(
+        query.config.filter,
+        Some(vec![
+            Filter::new("x", "in", ["a", "b"]).into(),
+            Filter::new("y", "begins with", FilterTerm::Scalar("ab".into())).into(),
+        ])
+    );
+
+    assert_eq!(query.config.filter_op, Some(FilterReducer::Or));
+}
+
+#[test]
+fn test_where_nested() {
+    let query = compile("SELECT * FROM t WHERE (x = 'EU' AND y > 10) OR z IN ('a', 'b')");
+    assert_eq!(
+        query.config.filter,
+        Some(vec![
+            FilterGroup::new(FilterReducer::And, vec![
+                Filter::new("x", "==", FilterTerm::Scalar("EU".into())).into(),
+                Filter::new("y", ">", FilterTerm::Scalar(Scalar::Float(10.0))).into(),
+            ])
+            .into(),
+            Filter::new("z", "in", ["a", "b"]).into(),
+        ])
+    );
+
+    assert_eq!(query.config.filter_op, Some(FilterReducer::Or));
+}
+
+#[test]
+fn test_group_by_skips_total_row() {
+    let query = compile("SELECT x, SUM(y) AS total FROM t GROUP BY x ORDER BY total DESC LIMIT 3");
+    assert_eq!(query.config.group_by, Some(vec!["x".to_owned()]));
+    assert_eq!(query.config.columns, Some(vec![Some("total".to_owned())]));
+    assert_eq!(
+        query.config.expressions.unwrap().0.get("total"),
+        Some(&"\"y\"".to_owned())
+    );
+
+    assert_eq!(
+        query.config.aggregates.unwrap().get("total"),
+        Some(&Aggregate::SingleAggregate(SingleAggregate::Sum))
+    );
+
+    assert_eq!(
+        query.config.sort,
+        Some(vec![Sort("total".to_owned(), SortDir::Desc)])
+    );
+
+    assert_eq!(query.window.start_row, Some(1.0));
+    assert_eq!(query.window.end_row, Some(4.0));
+}
+
+#[test]
+fn test_aggregate_without_group_by() {
+    let query = compile("SELECT COUNT(*) AS n, AVG(x) FROM t");
+    assert_eq!(
+        query.config.group_by,
+        Some(vec!["__sql_total__".to_owned()])
+    );
+    assert_eq!(query.config.group_by_depth, Some(0));
+    assert_eq!(
+        query.config.columns,
+        Some(vec![Some("n".to_owned()), Some("x".to_owned())])
+    );
+
+    let aggregates = query.config.aggregates.unwrap();
+    assert_eq!(
+        aggregates.get("x"),
+        Some(&Aggregate::SingleAggregate(SingleAggregate::Avg))
+    );
+
+    assert_eq!(query.window.start_row, None);
+}
+
+#[test]
+fn test_scalar_expressions() {
+    let query = compile("SELECT x * 2 AS double, upper(y) FROM t");
+    let expressions = query.config.expressions.unwrap().0;
+    assert_eq!(expressions.get("double"), Some(&"(\"x\" * 2)".to_owned()));
+
+    assert_eq!(
+        expressions.get("upper(y)"),
+        Some(&"upper(\"y\")".to_owned())
+    );
+}
+
+#[test]
+fn test_scalar_expressions_escape_quotes() {
+    let query = compile(r#"SELECT "a""b\c" + 1 AS q, concat(y, 'it''s') AS s FROM t"#);
+    let expressions = query.config.expressions.unwrap().0;
+    assert_eq!(expressions.get("q"), Some(&r#"("a\"b\\c" + 1)"#.to_owned()));
+    assert_eq!(
+        expressions.get("s"),
+        Some(&r#"concat("y", 'it\'s')"#.to_owned())
+    );
+}
+
+#[test]
+fn test_unsupported() {
+    assert!(compile_err("SELECT * FROM t JOIN u ON t.x = u.x").contains("JOIN"));
+    assert!(compile_err("SELECT x FROM t GROUP BY y").contains("GROUP BY"));
+    assert!(compile_err("SELECT DISTINCT x FROM t").contains("DISTINCT"));
+    assert!(compile_err("DELETE FROM t").contains("not supported"));
+}
@@ -20,14 +20,18 @@ use ts_rs::TS;
 use crate::assert_table_api;
 use crate::client::{Client, Features};
 use crate::config::{Expressions, ViewConfigUpdate};
+use crate::csv_data::CsvOptions;
+use crate::ingest::{self, IngestOptions, IngestReport};
+use crate::parquet_data::ParquetReadOptions;
 use crate::proto::make_table_req::make_table_options::MakeTableType;
 use crate::proto::make_table_req::MakeTableOptions;
 use crate::proto::request::ClientReq;
 use crate::proto::response::ClientResp;
 use crate::proto::*;
+use crate::sql::{SqlQuery, compile_sql};
 use crate::table_data::UpdateData;
 use crate::utils::*;
-use crate::view::View;
+use crate::view::{View, ViewWindow};
 
 pub type Schema = HashMap<String, ColumnType>;
 
@@ -47,6 +51,9 @@ pub enum TableReadFormat {
 
     #[serde(rename = "ndjson")]
     Ndjson,
+
+    #[serde(rename = "parquet")]
+    Parquet,
 }
 
 impl TableReadFormat {
@@ -57,6 +64,7 @@ impl TableReadFormat {
             Some("columns") => Some(TableReadFormat::ColumnsString),
             Some("arrow") => Some(TableReadFormat::Arrow),
             Some("ndjson") => Some(TableReadFormat::Ndjson),
+            Some("parquet") => Some(TableReadFormat::Parquet),
             None => None,
             Some(x) => return Err(format!("Unknown format \"{}\"", x)),
         })
@@ -89,6 +97,23 @@ pub struct TableInitOptions {
     #[serde(default)]
     #[ts(optional)]
     pub limit: Option<u32>,
+
+    /// Parsing options for [`UpdateData::Csv`] input.
+    #[serde(default)]
+    #[ts(optional)]
+    pub csv: Option<CsvOptions>,
+
+    /// Column projection and row group selection for
+    /// [`UpdateData::Parquet`] input.
+    #[serde(default)]
+    #[ts(optional)]
+    pub parquet: Option<ParquetReadOptions>,
+
+    /// Validate the input and report rejected and coerced values, see
+    /// [`Client::table_with_report`].
+    #[serde(default)]
+    #[ts(optional)]
+    pub ingest: Option<IngestOptions>,
 }
 
 impl TableInitOptions {
@@ -138,6 +163,18 @@ impl From<TableInitOptions> for TableOptions {
 pub struct UpdateOptions {
     pub port_id: Option<u32>,
     pub format: Option<TableReadFormat>,
+
+    #[serde(default)]
+    #[ts(optional)]
+    pub csv: Option<CsvOptions>,
+
+    #[serde(default)]
+    #[ts(optional)]
+    pub parquet: Option<ParquetReadOptions>,
+
+    #[serde(default)]
+    #[ts(optional)]
+    pub ingest: Option<IngestOptions>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -296,7 +333,7 @@ impl Table {
     #[doc = include_str!("../../docs/table/remove.md")]
     pub async fn remove(&self, input: UpdateData) -> ClientResult<()> {
         let msg = self.client_message(ClientReq::TableRemoveReq(TableRemoveReq {
-            data: Some(input.into()),
+            data: Some(input.into_proto(None, None)?),
         }));
 
         match self.client.oneshot(&msg).await? {
@@ -308,7 +345,7 @@ impl Table {
     #[doc = include_str!("../../docs/table/replace.md")]
     pub async fn replace(&self, input: UpdateData) -> ClientResult<()> {
         let msg = self.client_message(ClientReq::TableReplaceReq(TableReplaceReq {
-            data: Some(input.into()),
+            data: Some(input.into_proto(None, None)?),
         }));
 
         match self.client.oneshot(&msg).await? {
@@ -319,8 +356,43 @@ impl Table {
 
     #[doc = include_str!("../../docs/table/update.md")]
     pub async fn update(&self, input: UpdateData, options: UpdateOptions) -> ClientResult<()> {
+        if options.ingest.is_some() {
+            self.update_with_report(input, options).await.map(|_| ())
+        } else {
+            self.update_inner(input, options).await
+        }
+    }
+
+    #[doc = include_str!("../../docs/table/update_with_report.md")]
+    pub async fn update_with_report(
+        &self,
+        input: UpdateData,
+        options: UpdateOptions,
+    ) -> ClientResult<IngestReport> {
+        let Some(ingest) = options.ingest.clone() else {
+            self.update_inner(input, options).await?;
+            return Ok(IngestReport::default());
+        };
+
+        ingest::check_supported(&input)?;
+        let schema = self.schema().await?;
+        let validated = ingest::validate(ingest::parse(input)?, &schema, ingest.mode)?;
+        self.update_inner(validated.data, options).await?;
+        if let Some(name) = &ingest.rejects_table {
+            ingest::route_rejects(&self.client, name, &validated.report, validated.rejects).await?;
+        }
+
+        Ok(validated.report)
+    }
+
+    pub(crate) async fn update_inner(
+        &self,
+        input: UpdateData,
+        options: UpdateOptions,
+    ) -> ClientResult<()> {
+        let data = input.into_proto(options.csv.as_ref(), options.parquet.as_ref())?;
         let msg = self.client_message(ClientReq::TableUpdateReq(TableUpdateReq {
-            data: Some(input.into()),
+            data: Some(data),
             port_id: options.port_id.unwrap_or(0),
         }));
 
@@ -330,6 +402,12 @@ impl Table {
         }
     }
 
+    #[doc = include_str!("../../docs/table/query.md")]
+    pub async fn query(&self, sql: &str) -> ClientResult<(View, ViewWindow)> {
+        let SqlQuery { config, window } = compile_sql(sql)?;
+        Ok((self.view(Some(config)).await?, window))
+    }
+
     #[doc = include_str!("../../docs/table/validate_expressions.md")]
     pub async fn validate_expressions(
         &self,
@@ -12,8 +12,11 @@
 
 use prost::bytes::Bytes;
 
+use crate::csv_data::CsvOptions;
+use crate::parquet_data::{ParquetReadOptions, parquet_to_arrow};
 use crate::proto;
 use crate::proto::*;
+use crate::utils::*;
 use crate::view::View;
 #[cfg(doc)]
 use crate::{Client, Table};
@@ -29,13 +32,14 @@ pub enum TableData {
 
 /// The possible formats of input data which [`Table::update`] may take as an
 /// argument.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum UpdateData {
     Csv(String),
     Arrow(Bytes),
     JsonRows(String),
     JsonColumns(String),
     Ndjson(String),
+    Parquet(Bytes),
 }
 
 impl From<UpdateData> for TableData {
@@ -44,10 +48,16 @@ impl From<UpdateData> for TableData {
     }
 }
 
-impl From<TableData> for proto::MakeTableData {
-    fn from(value: TableData) -> Self {
-        let data = match value {
-            TableData::Update(x) => return x.into(),
+impl TableData {
+    /// Convert to the wire format. [`UpdateData::Parquet`] is decoded to Arrow
+    /// on the client, which is why this is fallible.
+    pub(crate) fn into_proto(
+        self,
+        csv: Option<&CsvOptions>,
+        parquet: Option<&ParquetReadOptions>,
+    ) -> ClientResult<proto::MakeTableData> {
+        let data = match self {
+            TableData::Update(x) => return x.into_proto(csv, parquet),
             TableData::View(view) => make_table_data::Data::FromView(view.name),
             TableData::Schema(x) => make_table_data::Data::FromSchema(proto::Schema {
                 schema: x
@@ -60,20 +70,39 @@ impl From<TableData> for proto::MakeTableData {
             }),
         };
 
-        MakeTableData { data: Some(data) }
+        Ok(MakeTableData {
+            data: Some(data),
+            csv_options: None,
+        })
     }
 }
 
-impl From<UpdateData> for proto::MakeTableData {
-    fn from(value: UpdateData) -> Self {
-        let data = match value {
+impl UpdateData {
+    pub(crate) fn into_proto(
+        self,
+        csv: Option<&CsvOptions>,
+        parquet: Option<&ParquetReadOptions>,
+    ) -> ClientResult<proto::MakeTableData> {
+        let csv_options = match &self {
+            UpdateData::Csv(_) => csv.cloned().map(TryInto::try_into).transpose()?,
+            _ => None,
+        };
+
+        let data = match self {
             UpdateData::Csv(x) => make_table_data::Data::FromCsv(x),
             UpdateData::Arrow(x) => make_table_data::Data::FromArrow(x.into()),
             UpdateData::JsonRows(x) => make_table_data::Data::FromRows(x),
             UpdateData::JsonColumns(x) => make_table_data::Data::FromCols(x),
             UpdateData::Ndjson(x) => make_table_data::Data::FromNdjson(x),
+            UpdateData::Parquet(x) => {
+                let options = parquet.cloned().unwrap_or_default();
+                make_table_data::Data::FromArrow(parquet_to_arrow(x, &options)?.into())
+            },
         };
 
-        MakeTableData { data: Some(data) }
+        Ok(MakeTableData {
+            data: Some(data),
+            csv_options,
+        })
     }
 }
@@ -43,6 +43,7 @@ impl std::fmt::Display for Request {
                         data:
                             Some(MakeTableData {
                                 data: Some(ref data),
+                                ref csv_options,
                             }),
                     })),
                 ..
@@ -51,6 +52,7 @@ impl std::fmt::Display for Request {
                     options: options.clone(),
                     data: Some(MakeTableData {
                         data: Some(replace(data.clone())),
+                        csv_options: csv_options.clone(),
                     }),
                 })),
                 ..msg.clone()
@@ -62,6 +64,7 @@ impl std::fmt::Display for Request {
                         data:
                             Some(MakeTableData {
                                 data: Some(ref data),
+                                ref csv_options,
                             }),
                     })),
                 ..
@@ -70,6 +73,7 @@ impl std::fmt::Display for Request {
                     port_id,
                     data: Some(MakeTableData {
                         data: Some(replace(data.clone())),
+                        csv_options: csv_options.clone(),
                     }),
                 })),
                 ..msg.clone()
@@ -20,6 +20,7 @@ mod tests;
 
 use thiserror::*;
 
+use crate::ingest::IngestIssue;
 use crate::proto;
 
 #[derive(Error, Debug)]
@@ -62,6 +63,15 @@ pub enum ClientError {
 
     #[error("Undecipherable proto message")]
     ProtoError(#[from] prost::EncodeError),
+
+    #[error("Invalid JSON: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("Row {} rejected, column {:?}: {}", .0.row, .0.column, .0.reason)]
+    IngestRejected(Box<IngestIssue>),
+
+    #[error("Invalid SQL query: {0}")]
+    SqlError(String),
 }
 
 pub type ClientResult<T> = Result<T, ClientError>;
@@ -22,6 +22,7 @@ use ts_rs::TS;
 use self::view_on_update_req::Mode;
 use crate::assert_view_api;
 use crate::client::Client;
+use crate::parquet_data::{ParquetCompression, arrow_to_parquet};
 use crate::proto::request::ClientReq;
 use crate::proto::response::ClientResp;
 use crate::proto::*;
@@ -200,6 +201,34 @@ impl View {
         }
     }
 
+    #[doc = include_str!("../../docs/view/to_parquet.md")]
+    pub async fn to_parquet(
+        &self,
+        window: ViewWindow,
+        compression: ParquetCompression,
+    ) -> ClientResult<Bytes> {
+        // Parquet pages are compressed separately, so fetch uncompressed IPC.
+        let window = ViewWindow {
+            compression: None,
+            ..window
+        };
+
+        let arrow = self.to_arrow(window).await?;
+        arrow_to_parquet(arrow, compression)
+    }
+
+    #[cfg(feature = "polars")]
+    #[doc = include_str!("../../docs/view/to_polars.md")]
+    pub async fn to_polars(&self, window: ViewWindow) -> ClientResult<polars::prelude::DataFrame> {
+        let window = ViewWindow {
+            compression: None,
+            ..window
+        };
+
+        let arrow = self.to_arrow(window).await?;
+        crate::polars_data::arrow_to_polars(arrow)
+    }
+
     #[doc = include_str!("../../docs/view/to_columns_string.md")]
     pub async fn to_columns_string(&self, window: ViewWindow) -> ClientResult<String> {
         let msg = self.client_message(ClientReq::ViewToColumnsStringReq(ViewToColumnsStringReq {
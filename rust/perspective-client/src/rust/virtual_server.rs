@@ -0,0 +1,464 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Serve [`Client`]s from data sources other than the Perspective engine.
+//!
+//! A [`VirtualServer`] implements the "Minimum Virtual API" of the protocol:
+//! listing tables, reading their schema and size, and creating [`View`]s
+//! which are read as Arrow. A [`VirtualSession`] decodes each request from a
+//! [`Client`] and dispatches it to the [`VirtualServer`], so external stores
+//! can be browsed with `<perspective-viewer>` like any other `Server`. The
+//! `ViewConfig` features the backend supports are advertised with
+//! [`VirtualServer::get_features`].
+//!
+//! Virtual tables are read-only and never update: requests to create or
+//! modify tables are answered with an error, and `on_update`/`on_delete`
+//! callbacks never fire.
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+
+use futures::Future;
+use prost::Message;
+
+use crate::config::{ViewConfig, ViewConfigUpdate};
+use crate::proto::columns_update::{Columns, OptColumns};
+use crate::proto::request::ClientReq;
+use crate::proto::response::ClientResp;
+use crate::proto::*;
+use crate::session::{ProxyCallback, Session, encode};
+use crate::utils::*;
+#[cfg(doc)]
+use crate::{Client, View};
+
+pub type VirtualServerError = Box<dyn Error + Send + Sync>;
+
+pub type VirtualServerResult<T> = Result<T, VirtualServerError>;
+
+fn unsupported<T>(method: &str) -> VirtualServerResult<T> {
+    Err(format!("`{}` is not supported by this server", method).into())
+}
+
+/// A backend for a [`VirtualSession`]. Tables are identified by the names
+/// returned from [`VirtualServer::get_hosted_tables`], and views by the
+/// `view_id` chosen by the [`Client`] in [`VirtualServer::table_make_view`].
+///
+/// Methods with a default implementation are optional, and answer with an
+/// error (or an empty result) unless overridden.
+pub trait VirtualServer: Send + Sync + 'static {
+    /// The `ViewConfig` features this backend supports, e.g. whether
+    /// `group_by` is implemented and which filter operators each
+    /// [`ColumnType`] supports. Defaults to none.
+    fn get_features(&self) -> GetFeaturesResp {
+        GetFeaturesResp::default()
+    }
+
+    /// The names of the tables this backend hosts.
+    fn get_hosted_tables(&self) -> impl Future<Output = VirtualServerResult<Vec<String>>> + Send;
+
+    /// The columns of table `table_id` and their types, in order.
+    fn table_schema(
+        &self,
+        table_id: &str,
+    ) -> impl Future<Output = VirtualServerResult<Vec<(String, ColumnType)>>> + Send;
+
+    /// The number of rows in table `table_id`.
+    fn table_size(&self, table_id: &str) -> impl Future<Output = VirtualServerResult<u32>> + Send;
+
+    /// Validate `expressions` against table `table_id`. By default, every
+    /// expression is invalid.
+    fn table_validate_expr(
+        &self,
+        table_id: &str,
+        expressions: HashMap<String, String>,
+    ) -> impl Future<Output = VirtualServerResult<TableValidateExprResp>> + Send {
+        let _ = table_id;
+        let errors = expressions
+            .into_keys()
+            .map(|name| {
+                let error = table_validate_expr_resp::ExprValidationError {
+                    error_message: "Expressions are not supported".to_owned(),
+                    line: 0,
+                    column: 0,
+                };
+
+                (name, error)
+            })
+            .collect();
+
+        async move {
+            Ok(TableValidateExprResp {
+                errors,
+                ..TableValidateExprResp::default()
+            })
+        }
+    }
+
+    /// Create view `view_id` of table `table_id`. Fields of `config` which are
+    /// [`None`] take their default, e.g. all of the table's columns.
+    fn table_make_view(
+        &self,
+        table_id: &str,
+        view_id: &str,
+        config: ViewConfigUpdate,
+    ) -> impl Future<Output = VirtualServerResult<()>> + Send;
+
+    /// The complete config of view `view_id`, with defaults applied.
+    fn view_get_config(
+        &self,
+        view_id: &str,
+    ) -> impl Future<Output = VirtualServerResult<ViewConfig>> + Send;
+
+    /// The types of the columns of view `view_id` (after aggregation).
+    fn view_schema(
+        &self,
+        view_id: &str,
+    ) -> impl Future<Output = VirtualServerResult<HashMap<String, ColumnType>>> + Send;
+
+    /// The types of the expression columns of view `view_id`. Defaults to
+    /// none.
+    fn view_expression_schema(
+        &self,
+        view_id: &str,
+    ) -> impl Future<Output = VirtualServerResult<HashMap<String, ColumnType>>> + Send {
+        let _ = view_id;
+        async { Ok(HashMap::new()) }
+    }
+
+    /// The names of the columns of view `view_id`, in order, including
+    /// `split_by` prefixes such as `a|b|column`.
+    fn view_column_paths(
+        &self,
+        view_id: &str,
+    ) -> impl Future<Output = VirtualServerResult<Vec<String>>> + Send;
+
+    /// The number of rows and columns of view `view_id`, and of its table.
+    fn view_dimensions(
+        &self,
+        view_id: &str,
+    ) -> impl Future<Output = VirtualServerResult<ViewDimensionsResp>> + Send;
+
+    /// The rows and columns of view `view_id` within `viewport`, as an Arrow
+    /// IPC stream.
+    fn view_to_arrow(
+        &self,
+        view_id: &str,
+        viewport: ViewPort,
+    ) -> impl Future<Output = VirtualServerResult<Vec<u8>>> + Send;
+
+    /// The rows of view `view_id` within `viewport` as a JSON object of
+    /// columns.
+    fn view_to_columns_string(
+        &self,
+        view_id: &str,
+        viewport: ViewPort,
+    ) -> impl Future<Output = VirtualServerResult<String>> + Send {
+        let _ = (view_id, viewport);
+        async { unsupported("View::to_columns_string") }
+    }
+
+    /// The rows of view `view_id` within `viewport` as a JSON array of rows.
+    fn view_to_rows_string(
+        &self,
+        view_id: &str,
+        viewport: ViewPort,
+    ) -> impl Future<Output = VirtualServerResult<String>> + Send {
+        let _ = (view_id, viewport);
+        async { unsupported("View::to_json") }
+    }
+
+    /// The rows of view `view_id` within `viewport` as CSV.
+    fn view_to_csv(
+        &self,
+        view_id: &str,
+        viewport: ViewPort,
+    ) -> impl Future<Output = VirtualServerResult<String>> + Send {
+        let _ = (view_id, viewport);
+        async { unsupported("View::to_csv") }
+    }
+
+    /// The minimum and maximum of `column_name` in view `view_id`, as JSON.
+    fn view_get_min_max(
+        &self,
+        view_id: &str,
+        column_name: &str,
+    ) -> impl Future<Output = VirtualServerResult<(String, String)>> + Send {
+        let _ = (view_id, column_name);
+        async { unsupported("View::get_min_max") }
+    }
+
+    /// Delete view `view_id`.
+    fn view_delete(&self, view_id: &str) -> impl Future<Output = VirtualServerResult<()>> + Send;
+}
+
+/// A [`Session`] which answers a [`Client`]'s requests from a
+/// [`VirtualServer`]. Views created by this session are deleted when it is
+/// closed.
+pub struct VirtualSession<S> {
+    server: Arc<S>,
+    callback: ProxyCallback,
+    views: Mutex<HashSet<String>>,
+}
+
+impl<S: VirtualServer> VirtualSession<S> {
+    pub fn new(
+        server: Arc<S>,
+        send_response: impl Fn(&[u8]) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        VirtualSession {
+            server,
+            callback: Arc::new(send_response),
+            views: Mutex::default(),
+        }
+    }
+
+    /// Answer `req`, or [`None`] for subscriptions which never fire.
+    async fn dispatch(
+        &self,
+        entity_id: &str,
+        req: ClientReq,
+    ) -> VirtualServerResult<Option<ClientResp>> {
+        let server = &self.server;
+        Ok(Some(match req {
+            ClientReq::GetFeaturesReq(_) => ClientResp::GetFeaturesResp(server.get_features()),
+            ClientReq::GetHostedTablesReq(_) => {
+                let table_infos = server
+                    .get_hosted_tables()
+                    .await?
+                    .into_iter()
+                    .map(|entity_id| HostedTable {
+                        entity_id,
+                        index: None,
+                        limit: None,
+                    })
+                    .collect();
+
+                ClientResp::GetHostedTablesResp(GetHostedTablesResp { table_infos })
+            },
+            ClientReq::RemoveHostedTablesUpdateReq(_) => {
+                ClientResp::RemoveHostedTablesUpdateResp(RemoveHostedTablesUpdateResp {})
+            },
+            ClientReq::TableSchemaReq(_) => {
+                let schema = server
+                    .table_schema(entity_id)
+                    .await?
+                    .into_iter()
+                    .map(|(name, column_type)| schema::KeyTypePair {
+                        name,
+                        r#type: column_type as i32,
+                    })
+                    .collect();
+
+                ClientResp::TableSchemaResp(TableSchemaResp {
+                    schema: Some(Schema { schema }),
+                })
+            },
+            ClientReq::TableSizeReq(_) => ClientResp::TableSizeResp(TableSizeResp {
+                size: server.table_size(entity_id).await?,
+            }),
+            ClientReq::TableValidateExprReq(req) => ClientResp::TableValidateExprResp(
+                server
+                    .table_validate_expr(entity_id, req.column_to_expr)
+                    .await?,
+            ),
+            ClientReq::TableMakeViewReq(req) => {
+                let config = view_config_update(req.config);
+                server
+                    .table_make_view(entity_id, &req.view_id, config)
+                    .await?;
+
+                self.views.lock().unwrap().insert(req.view_id.clone());
+                ClientResp::TableMakeViewResp(TableMakeViewResp {
+                    view_id: req.view_id,
+                })
+            },
+            ClientReq::ViewGetConfigReq(_) => {
+                let config = server.view_get_config(entity_id).await?;
+                ClientResp::ViewGetConfigResp(ViewGetConfigResp {
+                    config: Some(ViewConfigUpdate::from(config).into()),
+                })
+            },
+            ClientReq::ViewSchemaReq(_) => ClientResp::ViewSchemaResp(ViewSchemaResp {
+                schema: to_proto_schema(server.view_schema(entity_id).await?),
+            }),
+            ClientReq::ViewExpressionSchemaReq(_) => {
+                let schema = server.view_expression_schema(entity_id).await?;
+                ClientResp::ViewExpressionSchemaResp(ViewExpressionSchemaResp {
+                    schema: to_proto_schema(schema),
+                })
+            },
+            ClientReq::ViewColumnPathsReq(_) => {
+                ClientResp::ViewColumnPathsResp(ViewColumnPathsResp {
+                    paths: server.view_column_paths(entity_id).await?,
+                })
+            },
+            ClientReq::ViewDimensionsReq(_) => {
+                ClientResp::ViewDimensionsResp(server.view_dimensions(entity_id).await?)
+            },
+            ClientReq::ViewToArrowReq(req) => {
+                let viewport = req.viewport.unwrap_or_default();
+                ClientResp::ViewToArrowResp(ViewToArrowResp {
+                    arrow: server.view_to_arrow(entity_id, viewport).await?,
+                })
+            },
+            ClientReq::ViewToColumnsStringReq(req) => {
+                let viewport = req.viewport.unwrap_or_default();
+                ClientResp::ViewToColumnsStringResp(ViewToColumnsStringResp {
+                    json_string: server.view_to_columns_string(entity_id, viewport).await?,
+                })
+            },
+            ClientReq::ViewToRowsStringReq(req) => {
+                let viewport = req.viewport.unwrap_or_default();
+                ClientResp::ViewToRowsStringResp(ViewToRowsStringResp {
+                    json_string: server.view_to_rows_string(entity_id, viewport).await?,
+                })
+            },
+            ClientReq::ViewToCsvReq(req) => {
+                let viewport = req.viewport.unwrap_or_default();
+                ClientResp::ViewToCsvResp(ViewToCsvResp {
+                    csv: server.view_to_csv(entity_id, viewport).await?,
+                })
+            },
+            ClientReq::ViewGetMinMaxReq(req) => {
+                let (min, max) = server.view_get_min_max(entity_id, &req.column_name).await?;
+
+                ClientResp::ViewGetMinMaxResp(ViewGetMinMaxResp { min, max })
+            },
+            ClientReq::ViewDeleteReq(_) => {
+                server.view_delete(entity_id).await?;
+                self.views.lock().unwrap().remove(entity_id);
+                ClientResp::ViewDeleteResp(ViewDeleteResp {})
+            },
+
+            // Virtual tables never update (or get deleted), so these
+            // subscriptions are accepted but never fire.
+            ClientReq::ViewOnUpdateReq(_)
+            | ClientReq::ViewOnDeleteReq(_)
+            | ClientReq::TableOnDeleteReq(_) => return Ok(None),
+            ClientReq::ViewRemoveOnUpdateReq(_) => {
+                ClientResp::ViewRemoveOnUpdateResp(ViewRemoveOnUpdateResp {})
+            },
+            ClientReq::ViewRemoveDeleteReq(_) => {
+                ClientResp::ViewRemoveDeleteResp(ViewRemoveDeleteResp {})
+            },
+            ClientReq::TableRemoveDeleteReq(_) => {
+                ClientResp::TableRemoveDeleteResp(TableRemoveDeleteResp {})
+            },
+            req => return unsupported(req.kind()),
+        }))
+    }
+}
+
+impl<S: VirtualServer> Session<ClientError> for VirtualSession<S> {
+    async fn handle_request(&self, request: &[u8]) -> Result<(), ClientError> {
+        let Request {
+            msg_id,
+            entity_id,
+            client_req,
+        } = Request::decode(request)?;
+
+        let Some(req) = client_req else {
+            return Err(ClientError::Internal(
+                "VirtualSession::handle_request: invalid request".to_string(),
+            ));
+        };
+
+        let view_found = !is_view_req(&req) || self.views.lock().unwrap().contains(&entity_id);
+        let client_resp = if !view_found {
+            Some(server_error("View not found", StatusCode::ViewNotFound))
+        } else {
+            match self.dispatch(&entity_id, req).await {
+                Ok(resp) => resp,
+                Err(err) => Some(server_error(err.to_string(), StatusCode::ServerError)),
+            }
+        };
+
+        if let Some(client_resp) = client_resp {
+            let response = Response {
+                msg_id,
+                entity_id,
+                client_resp: Some(client_resp),
+            };
+
+            encode(response, self.callback.clone())?;
+        }
+
+        Ok(())
+    }
+
+    async fn poll(&self) -> Result<(), ClientError> {
+        Ok(())
+    }
+
+    async fn close(self) {
+        let views = std::mem::take(&mut *self.views.lock().unwrap());
+        for view_id in views {
+            if let Err(err) = self.server.view_delete(&view_id).await {
+                tracing::error!("Failed to delete view {}: {}", view_id, err);
+            }
+        }
+    }
+}
+
+fn is_view_req(req: &ClientReq) -> bool {
+    matches!(
+        req,
+        ClientReq::ViewGetConfigReq(_)
+            | ClientReq::ViewSchemaReq(_)
+            | ClientReq::ViewExpressionSchemaReq(_)
+            | ClientReq::ViewColumnPathsReq(_)
+            | ClientReq::ViewDimensionsReq(_)
+            | ClientReq::ViewToArrowReq(_)
+            | ClientReq::ViewToColumnsStringReq(_)
+            | ClientReq::ViewToRowsStringReq(_)
+            | ClientReq::ViewToCsvReq(_)
+            | ClientReq::ViewGetMinMaxReq(_)
+            | ClientReq::ViewDeleteReq(_)
+    )
+}
+
+fn server_error(message: impl Into<String>, status_code: StatusCode) -> ClientResp {
+    ClientResp::ServerError(ServerError {
+        message: message.into(),
+        status_code: status_code as i32,
+    })
+}
+
+fn to_proto_schema(schema: HashMap<String, ColumnType>) -> HashMap<String, i32> {
+    schema.into_iter().map(|(k, v)| (k, v as i32)).collect()
+}
+
+/// Convert a requested config, in which missing `columns` means all of them.
+fn view_config_update(config: Option<crate::proto::ViewConfig>) -> ViewConfigUpdate {
+    let mut config = config.unwrap_or_default();
+    let default_columns = !matches!(
+        config.columns.as_ref().and_then(|x| x.opt_columns.as_ref()),
+        Some(OptColumns::Columns(_))
+    );
+
+    if default_columns {
+        config.columns = Some(ColumnsUpdate {
+            opt_columns: Some(OptColumns::Columns(Columns { columns: vec![] })),
+        });
+    }
+
+    let mut update = ViewConfigUpdate::from(ViewConfig::from(config));
+    if default_columns {
+        update.columns = None;
+    }
+
+    update
+}
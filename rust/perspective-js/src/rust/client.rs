@@ -17,7 +17,7 @@ use std::sync::Arc;
 use derivative::Derivative;
 use futures::channel::oneshot;
 use futures::future::LocalBoxFuture;
-use js_sys::{Function, Uint8Array};
+use js_sys::{Array, Function, Uint8Array};
 use macro_rules_attribute::apply;
 #[cfg(doc)]
 use perspective_client::SystemInfo;
@@ -33,6 +33,9 @@ extern "C" {
     #[derive(Clone)]
     #[wasm_bindgen(typescript_type = "TableInitOptions")]
     pub type JsTableInitOptions;
+
+    #[wasm_bindgen(typescript_type = "[Table, IngestReport]")]
+    pub type JsTableWithReport;
 }
 
 #[wasm_bindgen]
@@ -275,6 +278,24 @@ impl Client {
         Ok(Table(self.client.table(args, options).await?))
     }
 
+    #[apply(inherit_docs)]
+    #[inherit_doc = "client/table_with_report.md"]
+    #[wasm_bindgen]
+    pub async fn table_with_report(
+        &self,
+        value: &JsTableInitData,
+        options: Option<JsTableInitOptions>,
+    ) -> ApiResult<JsTableWithReport> {
+        let options = options
+            .into_serde_ext::<Option<TableInitOptions>>()?
+            .unwrap_or_default();
+
+        let args = TableData::from_js_value(value, options.format)?;
+        let (table, report) = self.client.table_with_report(args, options).await?;
+        let report = JsValue::from_serde_ext(&report)?;
+        Ok(Array::of2(&Table(table).into(), &report).unchecked_into())
+    }
+
     #[apply(inherit_docs)]
     #[inherit_doc = "client/terminate.md"]
     #[wasm_bindgen]
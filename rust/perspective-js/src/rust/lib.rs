@@ -40,6 +40,8 @@ export type * from "../../src/ts/ts-rs/ViewConfigUpdate.d.ts";
 export type * from "../../src/ts/ts-rs/ViewOnUpdateResp.d.ts";
 export type * from "../../src/ts/ts-rs/OnUpdateOptions.d.ts";
 export type * from "../../src/ts/ts-rs/UpdateOptions.d.ts";
+export type * from "../../src/ts/ts-rs/ParquetCompression.d.ts";
+export type * from "../../src/ts/ts-rs/IngestReport.d.ts";
 
 import type {ViewWindow} from "../../src/ts/ts-rs/ViewWindow.d.ts";
 import type {TableInitOptions} from "../../src/ts/ts-rs/TableInitOptions.d.ts";
@@ -47,6 +49,8 @@ import type {ViewConfigUpdate} from "../../src/ts/ts-rs/ViewConfigUpdate.d.ts";
 import type * as on_update_args from "../../src/ts/ts-rs/ViewOnUpdateResp.d.ts";
 import type {OnUpdateOptions} from "../../src/ts/ts-rs/OnUpdateOptions.d.ts";
 import type {UpdateOptions} from "../../src/ts/ts-rs/UpdateOptions.d.ts";
+import type {ParquetCompression} from "../../src/ts/ts-rs/ParquetCompression.d.ts";
+import type {IngestReport} from "../../src/ts/ts-rs/IngestReport.d.ts";
 "#;
 
 #[cfg(feature = "export-init")]
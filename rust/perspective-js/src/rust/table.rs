@@ -114,6 +114,9 @@ pub(crate) impl UpdateData {
                 Some(TableReadFormat::Ndjson) => {
                     Ok(Some(UpdateData::Ndjson(value.as_string().into_apierror()?)))
                 },
+                Some(TableReadFormat::Parquet) => Ok(Some(UpdateData::Parquet(
+                    value.as_string().into_apierror()?.into_bytes().into(),
+                ))),
             }
         } else if value.is_instance_of::<ArrayBuffer>() {
             let uint8array = Uint8Array::new(value);
@@ -129,6 +132,7 @@ pub(crate) impl UpdateData {
                 Some(TableReadFormat::Ndjson) => {
                     Ok(Some(UpdateData::Ndjson(String::from_utf8(slice)?)))
                 },
+                Some(TableReadFormat::Parquet) => Ok(Some(UpdateData::Parquet(slice.into()))),
                 None | Some(TableReadFormat::Arrow) => Ok(Some(UpdateData::Arrow(slice.into()))),
             }
         } else if let Some(uint8array) = value.dyn_ref::<Uint8Array>() {
@@ -144,6 +148,7 @@ pub(crate) impl UpdateData {
                 Some(TableReadFormat::Ndjson) => {
                     Ok(Some(UpdateData::Ndjson(String::from_utf8(slice)?)))
                 },
+                Some(TableReadFormat::Parquet) => Ok(Some(UpdateData::Parquet(slice.into()))),
                 None | Some(TableReadFormat::Arrow) => Ok(Some(UpdateData::Arrow(slice.into()))),
             }
         } else if value.is_instance_of::<Array>() {
@@ -197,6 +202,9 @@ extern "C" {
 
     #[wasm_bindgen(typescript_type = "UpdateOptions")]
     pub type JsUpdateOptions;
+
+    #[wasm_bindgen(typescript_type = "IngestReport")]
+    pub type JsIngestReport;
 }
 
 #[wasm_bindgen]
@@ -345,6 +353,23 @@ impl Table {
         Ok(())
     }
 
+    #[apply(inherit_docs)]
+    #[inherit_doc = "table/update_with_report.md"]
+    #[wasm_bindgen]
+    pub async fn update_with_report(
+        &self,
+        input: &JsTableInitData,
+        options: Option<JsUpdateOptions>,
+    ) -> ApiResult<JsIngestReport> {
+        let options = options
+            .into_serde_ext::<Option<UpdateOptions>>()?
+            .unwrap_or_default();
+
+        let input = UpdateData::from_js_value(input, options.format)?;
+        let report = self.0.update_with_report(input, options).await?;
+        Ok(JsValue::from_serde_ext(&report)?.unchecked_into())
+    }
+
     #[apply(inherit_docs)]
     #[inherit_doc = "table/view.md"]
     #[wasm_bindgen]
@@ -360,6 +385,15 @@ impl Table {
         Ok(View(view))
     }
 
+    #[apply(inherit_docs)]
+    #[inherit_doc = "table/query.md"]
+    #[wasm_bindgen]
+    pub async fn query(&self, sql: String) -> ApiResult<Array> {
+        let (view, window) = self.0.query(&sql).await?;
+        let window = JsViewWindow::from(window);
+        Ok(Array::of2(&View(view).into(), &window.into()))
+    }
+
     #[apply(inherit_docs)]
     #[inherit_doc = "table/validate_expressions.md"]
     #[wasm_bindgen]
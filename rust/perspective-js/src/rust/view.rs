@@ -12,7 +12,7 @@
 
 use js_sys::{Array, ArrayBuffer, Function, Object};
 use macro_rules_attribute::apply;
-use perspective_client::{assert_view_api, OnUpdateOptions, ViewWindow};
+use perspective_client::{assert_view_api, OnUpdateOptions, ParquetCompression, ViewWindow};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 
@@ -32,6 +32,9 @@ unsafe extern "C" {
     #[wasm_bindgen(typescript_type = "OnUpdateOptions")]
     pub type JsOnUpdateOptions;
 
+    #[wasm_bindgen(typescript_type = "ParquetCompression")]
+    pub type JsParquetCompression;
+
 }
 
 impl From<ViewWindow> for JsViewWindow {
@@ -141,6 +144,26 @@ impl View {
             .unchecked_into())
     }
 
+    #[apply(inherit_docs)]
+    #[inherit_doc = "view/to_parquet.md"]
+    #[wasm_bindgen]
+    pub async fn to_parquet(
+        &self,
+        window: Option<JsViewWindow>,
+        compression: Option<JsParquetCompression>,
+    ) -> ApiResult<ArrayBuffer> {
+        let window = window.into_serde_ext::<Option<ViewWindow>>()?;
+        let compression = compression.into_serde_ext::<Option<ParquetCompression>>()?;
+        let parquet = self
+            .0
+            .to_parquet(window.unwrap_or_default(), compression.unwrap_or_default())
+            .await?;
+
+        Ok(js_sys::Uint8Array::from(&parquet[..])
+            .buffer()
+            .unchecked_into())
+    }
+
     #[apply(inherit_docs)]
     #[inherit_doc = "view/to_columns_string.md"]
     #[wasm_bindgen]
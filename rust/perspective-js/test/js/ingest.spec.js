@@ -0,0 +1,55 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import { test, expect } from "@finos/perspective-test";
+import perspective from "./perspective_client";
+
+((perspective) => {
+    test.describe("Ingest report", function () {
+        test("reports accepted and rejected rows of an update", async function () {
+            const table = await perspective.table({ x: "integer" });
+            const report = await table.update_with_report(
+                [{ x: 1 }, { x: "abc" }, { x: "3" }],
+                { ingest: { mode: "lenient" } }
+            );
+
+            expect(report.accepted).toEqual([0, 2]);
+            expect(report.rejected.map((x) => x.row)).toEqual([1]);
+            expect(report.coerced.map((x) => x.row)).toEqual([2]);
+            expect(await table.size()).toEqual(2);
+            await table.delete();
+        });
+
+        test("creates a table with a report", async function () {
+            const [table, report] = await perspective.table_with_report(
+                [{ x: 1 }, { x: true }],
+                { ingest: { mode: "lenient" } }
+            );
+
+            expect(report.accepted).toEqual([0]);
+            expect(report.rejected.map((x) => x.column)).toEqual(["x"]);
+            expect(await table.size()).toEqual(1);
+            await table.delete();
+        });
+
+        test("rejects CSV input", async function () {
+            const table = await perspective.table({ x: "integer" });
+            await expect(
+                table.update_with_report("x\n1", {
+                    ingest: { mode: "lenient" },
+                })
+            ).rejects.toThrow();
+
+            await table.delete();
+        });
+    });
+})(perspective);
@@ -0,0 +1,61 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import { test, expect } from "@finos/perspective-test";
+import perspective from "./perspective_client";
+
+((perspective) => {
+    test.describe("Query", function () {
+        test("compiles SQL to a view and window", async function () {
+            const table = await perspective.table({
+                x: [1, 2, 3, 4],
+                y: ["a", "b", "b", "c"],
+            });
+
+            const [view, window] = await table.query(
+                "SELECT x * 10 AS z FROM t WHERE x > 1 ORDER BY x DESC LIMIT 2"
+            );
+
+            expect(await view.to_columns(window)).toEqual({ z: [40, 30] });
+            await view.delete();
+            await table.delete();
+        });
+
+        test("skips the total row of a GROUP BY", async function () {
+            const table = await perspective.table({
+                x: [1, 2, 3, 4],
+                y: ["a", "b", "b", "c"],
+            });
+
+            const [view, window] = await table.query(
+                "SELECT y, SUM(x) AS total FROM t GROUP BY y ORDER BY total DESC LIMIT 1"
+            );
+
+            expect(await view.to_columns(window)).toEqual({
+                __ROW_PATH__: [["b"]],
+                total: [5],
+            });
+
+            await view.delete();
+            await table.delete();
+        });
+
+        test("rejects unsupported SQL", async function () {
+            const table = await perspective.table({ x: [1], y: ["a"] });
+            await expect(
+                table.query("SELECT x, y, COUNT(*) FROM t GROUP BY x, y")
+            ).rejects.toThrow();
+
+            await table.delete();
+        });
+    });
+})(perspective);
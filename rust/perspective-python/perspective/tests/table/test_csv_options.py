@@ -0,0 +1,44 @@
+#  ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+#  ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+#  ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+#  ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+#  ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+#  ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+#  ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+#  ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+#  ┃ This file is part of the Perspective library, distributed under the terms ┃
+#  ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+#  ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import perspective as psp
+
+client = psp.Server().new_local_client()
+Table = client.table
+
+
+class TestCsvOptions(object):
+    def test_csv_delimiter_and_decimal_comma(self):
+        tbl = Table(
+            "x;y\n1,5;a\n2,5;b",
+            csv_options={"delimiter": ";", "decimal_point": ","},
+        )
+        assert tbl.schema() == {"x": "float", "y": "string"}
+        assert tbl.view().to_columns() == {"x": [1.5, 2.5], "y": ["a", "b"]}
+
+    def test_csv_null_values(self):
+        tbl = Table("x,y\n1,NA\n-,b", csv_options={"null_values": ["NA", "-"]})
+        assert tbl.view().to_columns() == {"x": [1, None], "y": [None, "b"]}
+
+    def test_csv_column_types(self):
+        tbl = Table("zip,x\n02134,1\n10001,2", csv_options={"column_types": {"zip": "string"}})
+        assert tbl.schema() == {"zip": "string", "x": "integer"}
+        assert tbl.view().to_columns()["zip"] == ["02134", "10001"]
+
+    def test_csv_no_header(self):
+        tbl = Table(
+            "1\ta\n2\tb",
+            csv_options={"delimiter": "\t", "header": False, "column_names": ["x", "y"]},
+        )
+        assert tbl.view().to_columns() == {"x": [1, 2], "y": ["a", "b"]}
+        tbl.update("3\tc", csv_options={"delimiter": "\t", "header": False, "column_names": ["x", "y"]})
+        assert tbl.size() == 3
@@ -0,0 +1,50 @@
+#  ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+#  ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+#  ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+#  ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+#  ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+#  ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+#  ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+#  ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+#  ┃ This file is part of the Perspective library, distributed under the terms ┃
+#  ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+#  ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+from pytest import raises
+
+import perspective as psp
+
+client = psp.Server().new_local_client()
+Table = client.table
+
+
+class TestIngestReport(object):
+    def test_update_with_report(self):
+        tbl = Table({"x": "integer"})
+        report = tbl.update_with_report(
+            [{"x": 1}, {"x": "abc"}, {"x": "3"}],
+            ingest_options={"mode": "lenient"},
+        )
+        assert report["accepted"] == [0, 2]
+        assert [x["row"] for x in report["rejected"]] == [1]
+        assert [x["row"] for x in report["coerced"]] == [2]
+        assert tbl.size() == 2
+
+    def test_table_with_report(self):
+        tbl, report = client.table_with_report(
+            [{"x": 1}, {"x": True}], ingest_options={"mode": "lenient"}
+        )
+        assert report["accepted"] == [0]
+        assert [x["column"] for x in report["rejected"]] == ["x"]
+        assert tbl.size() == 1
+
+    def test_update_ingest_strict(self):
+        tbl = Table({"x": "integer"})
+        with raises(psp.PerspectiveError):
+            tbl.update([{"x": "abc"}], ingest_options={"mode": "strict"})
+        assert tbl.size() == 0
+
+    def test_ingest_rejects_csv(self):
+        tbl = Table({"x": "integer"})
+        with raises(psp.PerspectiveError):
+            tbl.update_with_report("x\n1", ingest_options={"mode": "lenient"})
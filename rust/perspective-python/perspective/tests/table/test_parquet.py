@@ -0,0 +1,48 @@
+#  ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+#  ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+#  ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+#  ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+#  ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+#  ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+#  ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+#  ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+#  ┃ This file is part of the Perspective library, distributed under the terms ┃
+#  ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+#  ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+from pytest import raises
+
+import perspective as psp
+
+client = psp.Server().new_local_client()
+Table = client.table
+
+
+class TestParquet(object):
+    def test_to_parquet_roundtrip(self):
+        tbl = Table({"x": [1, 2, 3], "y": ["a", "b", "c"]})
+        parquet = tbl.view().to_parquet()
+        assert parquet[:4] == b"PAR1"
+        tbl2 = Table(parquet, format="parquet")
+        assert tbl2.view().to_columns() == {"x": [1, 2, 3], "y": ["a", "b", "c"]}
+
+    def test_to_parquet_compression(self):
+        tbl = Table({"x": list(range(1000))})
+        for compression in ["uncompressed", "snappy", "gzip", "lz4", "zstd", "brotli"]:
+            parquet = tbl.view().to_parquet(compression=compression)
+            tbl2 = Table(parquet, format="parquet")
+            assert tbl2.size() == 1000
+
+    def test_parquet_column_projection(self):
+        tbl = Table({"x": [1, 2], "y": ["a", "b"], "z": [1.5, 2.5]})
+        parquet = tbl.view().to_parquet()
+        tbl2 = Table(parquet, format="parquet", parquet_options={"columns": ["z", "x"]})
+        assert tbl2.columns() == ["x", "z"]
+        tbl2.update(parquet, format="parquet", parquet_options={"columns": ["x", "z"]})
+        assert tbl2.size() == 4
+
+    def test_parquet_row_group_out_of_range(self):
+        tbl = Table({"x": [1, 2]})
+        parquet = tbl.view().to_parquet()
+        with raises(psp.PerspectiveError):
+            Table(parquet, format="parquet", parquet_options={"row_groups": [3]})
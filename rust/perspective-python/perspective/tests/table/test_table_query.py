@@ -0,0 +1,48 @@
+#  ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+#  ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+#  ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+#  ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+#  ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+#  ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+#  ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+#  ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+#  ┃ This file is part of the Perspective library, distributed under the terms ┃
+#  ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+#  ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+import pytest
+
+import perspective as psp
+
+client = psp.Server().new_local_client()
+Table = client.table
+
+
+class TestTableQuery(object):
+    def test_table_query(self):
+        t = Table({"x": [1, 2, 3, 4], "y": ["a", "b", "b", "c"]})
+        view, window = t.query(
+            "SELECT x * 10 AS z FROM t WHERE x > 1 ORDER BY x DESC LIMIT 2"
+        )
+
+        assert view.to_columns(**window) == {"z": [40, 30]}
+        view.delete()
+        t.delete()
+
+    def test_table_query_group_by(self):
+        t = Table({"x": [1, 2, 3, 4], "y": ["a", "b", "b", "c"]})
+        view, window = t.query(
+            "SELECT y, SUM(x) AS total FROM t GROUP BY y "
+            "ORDER BY total DESC LIMIT 1"
+        )
+
+        assert view.to_columns(**window) == {"__ROW_PATH__": [["b"]], "total": [5]}
+        view.delete()
+        t.delete()
+
+    def test_table_query_rejects_unsupported_sql(self):
+        t = Table({"x": [1, 2, 3, 4], "y": ["a", "b", "b", "c"]})
+        with pytest.raises(psp.PerspectiveError):
+            t.query("SELECT x, y, COUNT(*) FROM t GROUP BY x, y")
+
+        t.delete()
@@ -17,9 +17,9 @@ use std::sync::Arc;
 use async_lock::RwLock;
 use futures::FutureExt;
 use perspective_client::{
-    Client, OnUpdateMode, OnUpdateOptions, Table, TableData, TableInitOptions, TableReadFormat,
-    UpdateData, UpdateOptions, View, ViewOnUpdateResp, ViewWindow, assert_table_api,
-    assert_view_api,
+    Client, IngestReport, OnUpdateMode, OnUpdateOptions, ParquetCompression, Table, TableData,
+    TableInitOptions, TableReadFormat, UpdateData, UpdateOptions, View, ViewOnUpdateResp,
+    ViewWindow, assert_table_api, assert_view_api,
 };
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
@@ -49,6 +49,72 @@ impl AsyncClient {
             close_cb: Arc::default(),
         }
     }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn table_inner(
+        &self,
+        input: Py<PyAny>,
+        limit: Option<u32>,
+        index: Option<Py<PyString>>,
+        name: Option<Py<PyString>>,
+        format: Option<Py<PyString>>,
+        csv_options: Option<Py<PyDict>>,
+        parquet_options: Option<Py<PyDict>>,
+        ingest_options: Option<Py<PyDict>>,
+    ) -> PyResult<(AsyncTable, IngestReport)> {
+        let client = self.client.clone();
+        let py_client = Python::with_gil(|_| self.clone());
+        let table = Python::with_gil(|py| {
+            let mut options = TableInitOptions {
+                name: name.map(|x| x.extract::<String>(py)).transpose()?,
+                csv: csv_options.map(|x| depythonize(x.bind(py))).transpose()?,
+                parquet: parquet_options
+                    .map(|x| depythonize(x.bind(py)))
+                    .transpose()?,
+                ingest: ingest_options
+                    .map(|x| depythonize(x.bind(py)))
+                    .transpose()?,
+                ..TableInitOptions::default()
+            };
+
+            let format = TableReadFormat::parse(format.map(|x| x.to_string()))
+                .map_err(PyPerspectiveError::new_err)?;
+
+            match (limit, index) {
+                (None, None) => {},
+                (None, Some(index)) => {
+                    options.index = Some(index.extract::<String>(py)?);
+                },
+                (Some(limit), None) => options.limit = Some(limit),
+                (Some(_), Some(_)) => {
+                    Err(PyValueError::new_err("Cannot set both `limit` and `index`"))?
+                },
+            };
+
+            let input = input.into_bound(py);
+            let input_data = if pyarrow::is_arrow_table(py, &input)? {
+                pyarrow::to_arrow_bytes(py, &input)?.into_any()
+            } else if pandas::is_pandas_df(py, &input)? {
+                pandas::pandas_to_arrow_bytes(py, &input)?.into_any()
+            } else if polars::is_polars_df(py, &input)? || polars::is_polars_lf(py, &input)? {
+                polars::polars_to_arrow_bytes(py, &input)?.into_any()
+            } else {
+                input
+            };
+
+            let table_data = TableData::from_py(input_data, format)?;
+            let table = client.table_with_report(table_data, options);
+            Ok::<_, PyErr>(table)
+        })?;
+
+        let (table, report) = table.await.into_pyerr()?;
+        let table = AsyncTable {
+            table: Arc::new(table),
+            client: py_client,
+        };
+
+        Ok((table, report))
+    }
 }
 
 #[pymethods]
@@ -91,7 +157,8 @@ impl AsyncClient {
             .into_pyerr()
     }
 
-    #[pyo3(signature=(input, limit=None, index=None, name=None, format=None))]
+    #[pyo3(signature=(input, limit=None, index=None, name=None, format=None, csv_options=None, parquet_options=None, ingest_options=None))]
+    #[allow(clippy::too_many_arguments)]
     pub async fn table(
         &self,
         input: Py<PyAny>,
@@ -99,50 +166,55 @@ impl AsyncClient {
         index: Option<Py<PyString>>,
         name: Option<Py<PyString>>,
         format: Option<Py<PyString>>,
+        csv_options: Option<Py<PyDict>>,
+        parquet_options: Option<Py<PyDict>>,
+        ingest_options: Option<Py<PyDict>>,
     ) -> PyResult<AsyncTable> {
-        let client = self.client.clone();
-        let py_client = Python::with_gil(|_| self.clone());
-        let table = Python::with_gil(|py| {
-            let mut options = TableInitOptions {
-                name: name.map(|x| x.extract::<String>(py)).transpose()?,
-                ..TableInitOptions::default()
-            };
-
-            let format = TableReadFormat::parse(format.map(|x| x.to_string()))
-                .map_err(PyPerspectiveError::new_err)?;
-
-            match (limit, index) {
-                (None, None) => {},
-                (None, Some(index)) => {
-                    options.index = Some(index.extract::<String>(py)?);
-                },
-                (Some(limit), None) => options.limit = Some(limit),
-                (Some(_), Some(_)) => {
-                    Err(PyValueError::new_err("Cannot set both `limit` and `index`"))?
-                },
-            };
-
-            let input = input.into_bound(py);
-            let input_data = if pyarrow::is_arrow_table(py, &input)? {
-                pyarrow::to_arrow_bytes(py, &input)?.into_any()
-            } else if pandas::is_pandas_df(py, &input)? {
-                pandas::pandas_to_arrow_bytes(py, &input)?.into_any()
-            } else if polars::is_polars_df(py, &input)? || polars::is_polars_lf(py, &input)? {
-                polars::polars_to_arrow_bytes(py, &input)?.into_any()
-            } else {
-                input
-            };
-
-            let table_data = TableData::from_py(input_data, format)?;
-            let table = client.table(table_data, options);
-            Ok::<_, PyErr>(table)
-        })?;
-
-        let table = table.await.into_pyerr()?;
-        Ok(AsyncTable {
-            table: Arc::new(table),
-            client: py_client,
-        })
+        let (table, _) = self
+            .table_inner(
+                input,
+                limit,
+                index,
+                name,
+                format,
+                csv_options,
+                parquet_options,
+                ingest_options,
+            )
+            .await?;
+
+        Ok(table)
+    }
+
+    #[pyo3(signature=(input, limit=None, index=None, name=None, format=None, csv_options=None, parquet_options=None, ingest_options=None))]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn table_with_report(
+        &self,
+        input: Py<PyAny>,
+        limit: Option<u32>,
+        index: Option<Py<PyString>>,
+        name: Option<Py<PyString>>,
+        format: Option<Py<PyString>>,
+        csv_options: Option<Py<PyDict>>,
+        parquet_options: Option<Py<PyDict>>,
+        ingest_options: Option<Py<PyDict>>,
+    ) -> PyResult<(AsyncTable, Py<PyAny>)> {
+        let (table, report) = self
+            .table_inner(
+                input,
+                limit,
+                index,
+                name,
+                format,
+                csv_options,
+                parquet_options,
+                ingest_options,
+            )
+            .await?;
+
+        let report =
+            Python::with_gil(|py| Ok::<_, PyErr>(pythonize::pythonize(py, &report)?.unbind()))?;
+        Ok((table, report))
     }
 
     pub async fn open_table(&self, name: String) -> PyResult<AsyncTable> {
@@ -224,6 +296,61 @@ pub struct AsyncTable {
 
 assert_table_api!(AsyncTable);
 
+impl AsyncTable {
+    async fn update_inner(
+        &self,
+        input: Py<PyAny>,
+        port_id: Option<u32>,
+        format: Option<String>,
+        csv_options: Option<Py<PyDict>>,
+        parquet_options: Option<Py<PyDict>>,
+        ingest_options: Option<Py<PyDict>>,
+    ) -> PyResult<IngestReport> {
+        let input_data: Py<PyAny> = Python::with_gil(|py| {
+            let input = input.into_bound(py);
+            let data = if pyarrow::is_arrow_table(py, &input)? {
+                pyarrow::to_arrow_bytes(py, &input)?.into_any()
+            } else if pandas::is_pandas_df(py, &input)? {
+                pandas::pandas_to_arrow_bytes(py, &input)?.into_any()
+            } else if polars::is_polars_df(py, &input)? || polars::is_polars_lf(py, &input)? {
+                polars::polars_to_arrow_bytes(py, &input)?.into_any()
+            } else {
+                input
+            };
+            Ok(data.unbind()) as PyResult<Py<PyAny>>
+        })?;
+
+        let table = &self.table;
+        let format = TableReadFormat::parse(format).map_err(PyPerspectiveError::new_err)?;
+        let table_data =
+            Python::with_gil(|py| UpdateData::from_py(input_data.into_bound(py), format))?;
+        let (csv, parquet, ingest) = Python::with_gil(|py| {
+            let csv = csv_options.map(|x| depythonize(x.bind(py))).transpose()?;
+            let parquet = parquet_options
+                .map(|x| depythonize(x.bind(py)))
+                .transpose()?;
+            let ingest = ingest_options
+                .map(|x| depythonize(x.bind(py)))
+                .transpose()?;
+
+            Ok::<_, PyErr>((csv, parquet, ingest))
+        })?;
+
+        let options = UpdateOptions {
+            port_id,
+            format,
+            csv,
+            parquet,
+            ingest,
+        };
+
+        table
+            .update_with_report(table_data, options)
+            .await
+            .into_pyerr()
+    }
+}
+
 #[pymethods]
 impl AsyncTable {
     pub fn get_index(&self) -> Option<String> {
@@ -307,36 +434,65 @@ impl AsyncTable {
         table.replace(table_data).await.into_pyerr()
     }
 
-    #[pyo3(signature=(input, port_id=None, format=None))]
+    #[pyo3(signature=(input, port_id=None, format=None, csv_options=None, parquet_options=None, ingest_options=None))]
     pub async fn update(
         &self,
         input: Py<PyAny>,
         port_id: Option<u32>,
         format: Option<String>,
+        csv_options: Option<Py<PyDict>>,
+        parquet_options: Option<Py<PyDict>>,
+        ingest_options: Option<Py<PyDict>>,
     ) -> PyResult<()> {
-        let input_data: Py<PyAny> = Python::with_gil(|py| {
-            let input = input.into_bound(py);
-            let data = if pyarrow::is_arrow_table(py, &input)? {
-                pyarrow::to_arrow_bytes(py, &input)?.into_any()
-            } else if pandas::is_pandas_df(py, &input)? {
-                pandas::pandas_to_arrow_bytes(py, &input)?.into_any()
-            } else if polars::is_polars_df(py, &input)? || polars::is_polars_lf(py, &input)? {
-                polars::polars_to_arrow_bytes(py, &input)?.into_any()
-            } else {
-                input
-            };
-            Ok(data.unbind()) as PyResult<Py<PyAny>>
-        })?;
+        self.update_inner(
+            input,
+            port_id,
+            format,
+            csv_options,
+            parquet_options,
+            ingest_options,
+        )
+        .await?;
 
-        let table = &self.table;
-        let format = TableReadFormat::parse(format).map_err(PyPerspectiveError::new_err)?;
-        let table_data =
-            Python::with_gil(|py| UpdateData::from_py(input_data.into_bound(py), format))?;
-        let options = UpdateOptions { port_id, format };
-        table.update(table_data, options).await.into_pyerr()?;
         Ok(())
     }
 
+    #[pyo3(signature=(input, port_id=None, format=None, csv_options=None, parquet_options=None, ingest_options=None))]
+    pub async fn update_with_report(
+        &self,
+        input: Py<PyAny>,
+        port_id: Option<u32>,
+        format: Option<String>,
+        csv_options: Option<Py<PyDict>>,
+        parquet_options: Option<Py<PyDict>>,
+        ingest_options: Option<Py<PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        let report = self
+            .update_inner(
+                input,
+                port_id,
+                format,
+                csv_options,
+                parquet_options,
+                ingest_options,
+            )
+            .await?;
+
+        Python::with_gil(|py| Ok(pythonize::pythonize(py, &report)?.unbind()))
+    }
+
+    pub async fn query(&self, sql: String) -> PyResult<(AsyncView, Py<PyAny>)> {
+        let (view, window) = self.table.query(&sql).await.into_pyerr()?;
+        let window =
+            Python::with_gil(|py| Ok::<_, PyErr>(pythonize::pythonize(py, &window)?.unbind()))?;
+        let view = AsyncView {
+            view: Arc::new(view),
+            client: self.client.clone(),
+        };
+
+        Ok((view, window))
+    }
+
     pub async fn validate_expressions(&self, expressions: Py<PyAny>) -> PyResult<Py<PyAny>> {
         let expressions = Python::with_gil(|py| depythonize(expressions.bind(py)))?;
         let records = self
@@ -539,6 +695,26 @@ impl AsyncView {
         Ok(Python::with_gil(|py| PyBytes::new(py, &arrow).into()))
     }
 
+    #[pyo3(signature=(compression=None, window=None))]
+    pub async fn to_parquet(
+        &self,
+        compression: Option<String>,
+        window: Option<Py<PyDict>>,
+    ) -> PyResult<Py<PyBytes>> {
+        let window: ViewWindow = Python::with_gil(|py| window.map(|x| depythonize(x.bind(py))))
+            .transpose()?
+            .unwrap_or_default();
+        let compression = ParquetCompression::parse(compression)
+            .map_err(PyPerspectiveError::new_err)?
+            .unwrap_or_default();
+        let parquet = self
+            .view
+            .to_parquet(window, compression)
+            .await
+            .into_pyerr()?;
+        Ok(Python::with_gil(|py| PyBytes::new(py, &parquet).into()))
+    }
+
     #[pyo3(signature=(window=None))]
     pub async fn to_csv(&self, window: Option<Py<PyDict>>) -> PyResult<String> {
         let window: ViewWindow = Python::with_gil(|py| window.map(|x| depythonize(x.bind(py))))
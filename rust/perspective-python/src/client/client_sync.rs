@@ -111,7 +111,8 @@ impl Client {
 
     #[apply(inherit_doc)]
     #[inherit_doc = "client/table.md"]
-    #[pyo3(signature = (input, limit=None, index=None, name=None, format=None))]
+    #[pyo3(signature = (input, limit=None, index=None, name=None, format=None, csv_options=None, parquet_options=None, ingest_options=None))]
+    #[allow(clippy::too_many_arguments)]
     pub fn table(
         &self,
         py: Python<'_>,
@@ -120,14 +121,59 @@ impl Client {
         index: Option<Py<PyString>>,
         name: Option<Py<PyString>>,
         format: Option<Py<PyString>>,
+        csv_options: Option<Py<PyDict>>,
+        parquet_options: Option<Py<PyDict>>,
+        ingest_options: Option<Py<PyDict>>,
     ) -> PyResult<Table> {
         Ok(Table(
             self.0
-                .table(input, limit, index, name, format)
+                .table(
+                    input,
+                    limit,
+                    index,
+                    name,
+                    format,
+                    csv_options,
+                    parquet_options,
+                    ingest_options,
+                )
                 .py_block_on(py)?,
         ))
     }
 
+    #[apply(inherit_doc)]
+    #[inherit_doc = "client/table_with_report.md"]
+    #[pyo3(signature = (input, limit=None, index=None, name=None, format=None, csv_options=None, parquet_options=None, ingest_options=None))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn table_with_report(
+        &self,
+        py: Python<'_>,
+        input: Py<PyAny>,
+        limit: Option<u32>,
+        index: Option<Py<PyString>>,
+        name: Option<Py<PyString>>,
+        format: Option<Py<PyString>>,
+        csv_options: Option<Py<PyDict>>,
+        parquet_options: Option<Py<PyDict>>,
+        ingest_options: Option<Py<PyDict>>,
+    ) -> PyResult<(Table, Py<PyAny>)> {
+        let (table, report) = self
+            .0
+            .table_with_report(
+                input,
+                limit,
+                index,
+                name,
+                format,
+                csv_options,
+                parquet_options,
+                ingest_options,
+            )
+            .py_block_on(py)?;
+
+        Ok((Table(table), report))
+    }
+
     #[apply(inherit_doc)]
     #[inherit_doc = "client/open_table.md"]
     pub fn open_table(&self, py: Python<'_>, name: String) -> PyResult<Table> {
@@ -270,6 +316,13 @@ impl Table {
         table.validate_expressions(expression).py_block_on(py)
     }
 
+    #[apply(inherit_doc)]
+    #[inherit_doc = "table/query.md"]
+    pub fn query(&self, py: Python<'_>, sql: String) -> PyResult<(View, Py<PyAny>)> {
+        let (view, window) = self.0.query(sql).py_block_on(py)?;
+        Ok((View(view), window))
+    }
+
     #[apply(inherit_doc)]
     #[inherit_doc = "table/view.md"]
     #[pyo3(signature = (**config))]
@@ -297,15 +350,54 @@ impl Table {
 
     #[apply(inherit_doc)]
     #[inherit_doc = "table/update.md"]
-    #[pyo3(signature = (input, port_id=None, format=None))]
+    #[pyo3(signature = (input, port_id=None, format=None, csv_options=None, parquet_options=None, ingest_options=None))]
+    #[allow(clippy::too_many_arguments)]
     pub fn update(
         &self,
         py: Python<'_>,
         input: Py<PyAny>,
         port_id: Option<u32>,
         format: Option<String>,
+        csv_options: Option<Py<PyDict>>,
+        parquet_options: Option<Py<PyDict>>,
+        ingest_options: Option<Py<PyDict>>,
     ) -> PyResult<()> {
-        self.0.update(input, port_id, format).py_block_on(py)
+        self.0
+            .update(
+                input,
+                port_id,
+                format,
+                csv_options,
+                parquet_options,
+                ingest_options,
+            )
+            .py_block_on(py)
+    }
+
+    #[apply(inherit_doc)]
+    #[inherit_doc = "table/update_with_report.md"]
+    #[pyo3(signature = (input, port_id=None, format=None, csv_options=None, parquet_options=None, ingest_options=None))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_with_report(
+        &self,
+        py: Python<'_>,
+        input: Py<PyAny>,
+        port_id: Option<u32>,
+        format: Option<String>,
+        csv_options: Option<Py<PyDict>>,
+        parquet_options: Option<Py<PyDict>>,
+        ingest_options: Option<Py<PyDict>>,
+    ) -> PyResult<Py<PyAny>> {
+        self.0
+            .update_with_report(
+                input,
+                port_id,
+                format,
+                csv_options,
+                parquet_options,
+                ingest_options,
+            )
+            .py_block_on(py)
     }
 }
 
@@ -424,6 +516,18 @@ impl View {
         self.0.to_arrow(window).py_block_on(py)
     }
 
+    #[apply(inherit_doc)]
+    #[inherit_doc = "view/to_parquet.md"]
+    #[pyo3(signature = (compression=None, **window))]
+    pub fn to_parquet(
+        &self,
+        py: Python<'_>,
+        compression: Option<String>,
+        window: Option<Py<PyDict>>,
+    ) -> PyResult<Py<PyBytes>> {
+        self.0.to_parquet(compression, window).py_block_on(py)
+    }
+
     #[apply(inherit_doc)]
     #[inherit_doc = "view/delete.md"]
     pub fn delete(&self, py: Python<'_>) -> PyResult<()> {
@@ -35,6 +35,7 @@ fn from_arrow(
             Ok(Some(UpdateData::JsonColumns(String::from_utf8(vec)?)))
         },
         None | Some(TableReadFormat::Arrow) => Ok(Some(UpdateData::Arrow(vec.into()))),
+        Some(TableReadFormat::Parquet) => Ok(Some(UpdateData::Parquet(vec.into()))),
     }
 }
 
@@ -49,6 +50,7 @@ fn from_string(
         Some(TableReadFormat::Ndjson) => Ok(Some(UpdateData::Ndjson(string))),
         Some(TableReadFormat::ColumnsString) => Ok(Some(UpdateData::JsonColumns(string))),
         Some(TableReadFormat::Arrow) => Ok(Some(UpdateData::Arrow(string.into_bytes().into()))),
+        Some(TableReadFormat::Parquet) => Ok(Some(UpdateData::Parquet(string.into_bytes().into()))),
     }
 }
 
@@ -0,0 +1,213 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use perspective_client::proto::request::ClientReq;
+use perspective_client::proto::response::ClientResp;
+use perspective_client::proto::{
+    GetHostedTablesReq, Request, Response, TableSizeReq, ViewDeleteReq,
+};
+use perspective_client::SessionInfo;
+use prost::Message;
+
+use crate::ffi;
+use crate::server::{Server, ServerError};
+use crate::shard::{find_response, is_success};
+
+/// Responses to requests made by the [`Server`] itself (rather than a
+/// [`crate::Session`]'s client) use this message id, and are not forwarded.
+const ADMIN_MSG_ID: u32 = 0;
+
+impl Server {
+    /// List the open [`crate::Session`]s of this [`Server`], with the views
+    /// each has created and their `on_update` subscriptions.
+    pub fn sessions(&self) -> Vec<SessionInfo> {
+        self.registry.sessions()
+    }
+
+    /// The names and row counts of this [`Server`]'s hosted tables, read
+    /// directly from its engine(s). Unlike querying them from a
+    /// [`crate::LocalClient`], this does not open a [`crate::Session`] or
+    /// record request metrics.
+    pub async fn table_sizes(&self) -> Result<Vec<(String, usize)>, ServerError> {
+        let mut sizes = vec![];
+        for shard in 0..self.shards.len() {
+            let (shard_sizes, batches) = self.shards[shard]
+                .run(None, |engine| {
+                    let engine_id = engine.new_session();
+                    let result = engine_table_sizes(engine, engine_id);
+                    engine.close_session(engine_id);
+                    result
+                })
+                .await??;
+
+            // Reading a table's size processes its pending updates, which
+            // may produce `on_update` responses for other sessions.
+            for batch in batches {
+                self.dispatch(shard, batch).await?;
+            }
+
+            sizes.extend(shard_sizes);
+        }
+
+        Ok(sizes)
+    }
+
+    /// Forcibly close a [`crate::Session`] by id, as reported by
+    /// [`Server::sessions`], deleting its views. Subsequent requests to the
+    /// evicted [`crate::Session`] will fail, and its eventual
+    /// [`crate::Session::close`] is a no-op.
+    pub async fn close_session(&self, session_id: u32) -> Result<(), ServerError> {
+        if !self.registry.remove(session_id) {
+            return Err(format!("Unknown session {}", session_id).into());
+        }
+
+        let engine_ids = self
+            .engine_sessions
+            .read()
+            .await
+            .iter()
+            .filter(|(_, id)| **id == session_id)
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+
+        for (shard, engine_id) in engine_ids {
+            self.shards[shard]
+                .run(Some(session_id), move |engine| {
+                    engine.close_session(engine_id)
+                })
+                .await?;
+
+            self.engine_sessions
+                .write()
+                .await
+                .remove(&(shard, engine_id));
+        }
+
+        self.router.close_session(session_id);
+        self.callbacks.write().await.remove(&session_id);
+        Ok(())
+    }
+
+    /// Forcibly delete any [`crate::Session`]'s view by name, as reported by
+    /// [`Server::sessions`].
+    pub async fn delete_view(&self, view_id: &str) -> Result<(), ServerError> {
+        let owner = self
+            .registry
+            .view_owner(view_id)
+            .ok_or_else(|| format!("Unknown view {}", view_id))?;
+
+        let req = Request {
+            msg_id: ADMIN_MSG_ID,
+            entity_id: view_id.to_owned(),
+            client_req: Some(ClientReq::ViewDeleteReq(ViewDeleteReq {})),
+        };
+
+        let shard = self.router.view_shard(view_id).unwrap_or_default();
+        let engine_id = self
+            .engine_sessions
+            .read()
+            .await
+            .iter()
+            .find(|((s, _), id)| *s == shard && **id == owner)
+            .map(|((_, engine_id), _)| *engine_id)
+            .ok_or("Unknown session")?;
+
+        let bytes = ffi::Request::from(req.encode_to_vec().as_slice());
+        let responses = self.shards[shard]
+            .run(Some(owner), move |engine| {
+                engine.handle_request(engine_id, &bytes)
+            })
+            .await?;
+
+        if is_success(&responses, engine_id, ADMIN_MSG_ID)? {
+            self.router.commit(owner, shard, &req);
+        }
+
+        self.registry.observe(owner, &req);
+        for response in responses.iter_responses() {
+            let Some(id) = self.session_id(shard, &response).await else {
+                continue;
+            };
+
+            if id == owner {
+                let resp = Response::decode(response.msg())?;
+                if resp.msg_id == ADMIN_MSG_ID {
+                    if let Some(ClientResp::ServerError(err)) = resp.client_resp {
+                        return Err(err.message.into());
+                    }
+
+                    continue;
+                }
+            }
+
+            self.send(id, response.msg()).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Make a request on behalf of the [`Server`] from the engine session
+/// `engine_id`, returning its response along with the engine's
+/// [`ffi::ResponseBatch`], which may contain responses for other sessions.
+fn engine_request(
+    engine: &ffi::Server,
+    engine_id: u32,
+    entity_id: &str,
+    client_req: ClientReq,
+) -> Result<(ClientResp, ffi::ResponseBatch), ServerError> {
+    let req = Request {
+        msg_id: ADMIN_MSG_ID,
+        entity_id: entity_id.to_owned(),
+        client_req: Some(client_req),
+    };
+
+    let bytes = ffi::Request::from(req.encode_to_vec().as_slice());
+    let batch = engine.handle_request(engine_id, &bytes);
+    match find_response(&batch, engine_id, ADMIN_MSG_ID)?.and_then(|x| x.client_resp) {
+        Some(ClientResp::ServerError(err)) => Err(err.message.into()),
+        Some(resp) => Ok((resp, batch)),
+        None => Err("No response from engine".into()),
+    }
+}
+
+/// The names and row counts of the tables hosted by `engine`.
+#[allow(clippy::type_complexity)]
+fn engine_table_sizes(
+    engine: &ffi::Server,
+    engine_id: u32,
+) -> Result<(Vec<(String, usize)>, Vec<ffi::ResponseBatch>), ServerError> {
+    let hosted_tables = GetHostedTablesReq { subscribe: false };
+    let (resp, batch) = engine_request(
+        engine,
+        engine_id,
+        "",
+        ClientReq::GetHostedTablesReq(hosted_tables),
+    )?;
+
+    let ClientResp::GetHostedTablesResp(resp) = resp else {
+        return Err("Unexpected response to `GetHostedTablesReq`".into());
+    };
+
+    let mut sizes = vec![];
+    let mut batches = vec![batch];
+    for table in resp.table_infos {
+        let req = ClientReq::TableSizeReq(TableSizeReq {});
+        let (resp, batch) = engine_request(engine, engine_id, &table.entity_id, req)?;
+        batches.push(batch);
+        if let ClientResp::TableSizeResp(resp) = resp {
+            sizes.push((table.entity_id, resp.size as usize));
+        }
+    }
+
+    Ok((sizes, batches))
+}
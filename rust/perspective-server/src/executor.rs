@@ -0,0 +1,285 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::{HashMap, VecDeque};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+
+use futures::channel::oneshot;
+
+use crate::ffi;
+use crate::server::ServerError;
+
+/// How a [`crate::Server`] schedules calls into its engine, which are
+/// synchronous and may take arbitrarily long (e.g. a large `group_by`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExecutionStrategy {
+    /// Call the engine directly from the calling `async` task. This is the
+    /// lowest-latency option, but it blocks the executor thread for the
+    /// duration of the call.
+    #[default]
+    Inline,
+
+    /// Run engine calls on a single dedicated OS thread owned by the
+    /// [`crate::Server`].
+    WorkerThread,
+
+    /// Run engine calls on the [`tokio`] blocking thread pool, via
+    /// [`tokio::task::spawn_blocking`]. Calls are run one at a time by a single
+    /// blocking task, which is spawned when calls are queued and exits when the
+    /// queue is empty, so at most one blocking thread is busy with the engine
+    /// at a time.
+    #[cfg(feature = "tokio")]
+    BlockingPool,
+}
+
+/// Queue key for an engine call. Calls which belong to a session are keyed by
+/// that session's id, while server-wide calls (e.g. `poll`) share a key.
+type QueueKey = Option<u32>;
+
+type Job = Box<dyn FnOnce(&ffi::Server) + Send>;
+
+/// Run `job` against `engine`, containing a panic to the job which raised
+/// it. Its caller sees the dropped response channel as an error, while the
+/// executor keeps servicing the rest of the queue.
+fn run_job(job: Job, engine: &ffi::Server) {
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| job(engine)));
+}
+
+/// A round-robin queue of pending engine calls, one FIFO per session, so a
+/// session with a deep backlog of requests can't starve other sessions.
+struct FairQueue<J> {
+    queues: HashMap<QueueKey, VecDeque<J>>,
+    order: VecDeque<QueueKey>,
+}
+
+impl<J> Default for FairQueue<J> {
+    fn default() -> Self {
+        FairQueue {
+            queues: HashMap::default(),
+            order: VecDeque::default(),
+        }
+    }
+}
+
+impl<J> FairQueue<J> {
+    fn push(&mut self, key: QueueKey, job: J) {
+        let queue = self.queues.entry(key).or_default();
+        if queue.is_empty() {
+            self.order.push_back(key);
+        }
+
+        queue.push_back(job);
+    }
+
+    fn len(&self) -> usize {
+        self.queues.values().map(|x| x.len()).sum()
+    }
+
+    fn pop(&mut self) -> Option<J> {
+        let key = self.order.pop_front()?;
+        let queue = self.queues.get_mut(&key)?;
+        let job = queue.pop_front();
+        if queue.is_empty() {
+            self.queues.remove(&key);
+        } else {
+            self.order.push_back(key);
+        }
+
+        job
+    }
+}
+
+/// The pending engine calls of an [`Executor`], and whether a
+/// [`ExecutionStrategy::BlockingPool`] task is currently draining them.
+#[derive(Default)]
+struct JobQueue {
+    jobs: FairQueue<Job>,
+    draining: bool,
+}
+
+/// Shared state between an [`Executor`] and whichever thread(s) run its
+/// jobs. The engine lock guarantees calls are never concurrent, regardless
+/// of strategy.
+struct ExecutorState {
+    engine: Mutex<ffi::Server>,
+    queue: Mutex<JobQueue>,
+}
+
+impl ExecutorState {
+    /// Run exactly one queued job. Every [`ExecutionStrategy::WorkerThread`]
+    /// call to [`Executor::run`] schedules exactly one of these, so the queue
+    /// always drains.
+    fn run_next(&self) {
+        let engine = self.engine.lock().expect("Engine lock poisoned");
+        let job = self.queue.lock().expect("Queue lock poisoned").jobs.pop();
+        if let Some(job) = job {
+            run_job(job, &engine);
+        }
+    }
+
+    /// Run queued jobs until the queue is empty. The `draining` flag is
+    /// cleared under the same lock as the final (empty) `pop`, so a job
+    /// queued concurrently is either run here or schedules a new drain.
+    #[cfg(feature = "tokio")]
+    fn drain(&self) {
+        loop {
+            let job = {
+                let mut queue = self.queue.lock().expect("Queue lock poisoned");
+                let job = queue.jobs.pop();
+                queue.draining = job.is_some();
+                job
+            };
+
+            match job {
+                Some(job) => run_job(job, &self.engine.lock().expect("Engine lock poisoned")),
+                None => break,
+            }
+        }
+    }
+}
+
+/// Dispatches engine calls according to an [`ExecutionStrategy`].
+pub(crate) struct Executor {
+    strategy: ExecutionStrategy,
+    state: Arc<ExecutorState>,
+    worker: Option<std::sync::mpsc::Sender<()>>,
+}
+
+impl Executor {
+    /// Fails if the [`ExecutionStrategy::WorkerThread`] thread could not be
+    /// started.
+    pub(crate) fn new(strategy: ExecutionStrategy) -> Result<Self, ServerError> {
+        let state = Arc::new(ExecutorState {
+            engine: Mutex::new(ffi::Server::new()),
+            queue: Mutex::default(),
+        });
+
+        let worker = if strategy == ExecutionStrategy::WorkerThread {
+            let (sender, receiver) = std::sync::mpsc::channel::<()>();
+            let state = state.clone();
+            std::thread::Builder::new()
+                .name("perspective-engine".to_owned())
+                .spawn(move || {
+                    while receiver.recv().is_ok() {
+                        state.run_next();
+                    }
+                })?;
+
+            Some(sender)
+        } else {
+            None
+        };
+
+        Ok(Executor {
+            strategy,
+            state,
+            worker,
+        })
+    }
+
+    /// The number of engine calls waiting to run.
+    pub(crate) fn queue_depth(&self) -> usize {
+        self.state
+            .queue
+            .lock()
+            .expect("Queue lock poisoned")
+            .jobs
+            .len()
+    }
+
+    /// Run `f` against the engine, on behalf of `session_id` (or the server
+    /// itself if `None`), resolving when `f` has completed.
+    pub(crate) async fn run<F, T>(&self, session_id: Option<u32>, f: F) -> Result<T, ServerError>
+    where
+        F: FnOnce(&ffi::Server) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        if self.strategy == ExecutionStrategy::Inline {
+            let engine = self.state.engine.lock().expect("Engine lock poisoned");
+            return Ok(f(&engine));
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        let job: Job = Box::new(move |engine| {
+            let _ = sender.send(f(engine));
+        });
+
+        {
+            let mut queue = self.state.queue.lock().expect("Queue lock poisoned");
+            match self.strategy {
+                ExecutionStrategy::Inline => unreachable!(),
+                ExecutionStrategy::WorkerThread => {
+                    let worker = self.worker.as_ref().ok_or("Engine thread not running")?;
+                    queue.jobs.push(session_id, job);
+                    drop(queue);
+                    worker.send(())?;
+                },
+                #[cfg(feature = "tokio")]
+                ExecutionStrategy::BlockingPool => {
+                    queue.jobs.push(session_id, job);
+                    if !queue.draining {
+                        queue.draining = true;
+                        let state = self.state.clone();
+                        tokio::task::spawn_blocking(move || state.drain());
+                    }
+                },
+            };
+        }
+
+        Ok(receiver
+            .await
+            .map_err(|_| "Engine terminated before responding")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fair_queue_round_robins_sessions() {
+        let mut queue = FairQueue::default();
+        queue.push(Some(1), 10);
+        queue.push(Some(1), 11);
+        queue.push(Some(1), 12);
+        queue.push(Some(2), 20);
+        queue.push(None, 0);
+        let order: Vec<_> = std::iter::from_fn(|| queue.pop()).collect();
+        assert_eq!(order, vec![10, 20, 0, 11, 12]);
+    }
+
+    fn assert_survives_panic(executor: &Executor) {
+        let result = futures::executor::block_on(async {
+            let panicked = executor.run(Some(1), |_| panic!("Job panicked")).await;
+            assert!(panicked.is_err());
+            executor.run(Some(1), |_| 42).await
+        });
+
+        assert_eq!(result.unwrap(), 42);
+    }
+
+    #[test]
+    fn test_worker_thread_survives_panicking_job() {
+        assert_survives_panic(&Executor::new(ExecutionStrategy::WorkerThread).unwrap());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_blocking_pool_survives_panicking_job() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let _guard = runtime.enter();
+        assert_survives_panic(&Executor::new(ExecutionStrategy::BlockingPool).unwrap());
+    }
+}
@@ -55,15 +55,8 @@ impl Response {
     }
 }
 
-impl Drop for Response {
-    fn drop(&mut self) {
-        unsafe {
-            let resp = &*self.0;
-            psp_free(resp.data_ptr as *const u8);
-        }
-    }
-}
-
+/// The responses to a single engine call. Each [`Response`]'s message is
+/// owned by the batch, so a batch may be iterated more than once.
 #[repr(transparent)]
 pub struct ResponseBatch(*const CppResponseBatch);
 
@@ -80,6 +73,13 @@ impl ResponseBatch {
 
 impl Drop for ResponseBatch {
     fn drop(&mut self) {
+        for response in self.iter_responses() {
+            unsafe {
+                let resp = &*response.0;
+                psp_free(resp.data_ptr as *const u8);
+            }
+        }
+
         unsafe {
             let batch = &*self.0;
             psp_free(batch.entries_ptr as *const u8);
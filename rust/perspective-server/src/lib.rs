@@ -12,11 +12,23 @@
 
 extern crate link_cplusplus;
 
+mod admin;
+mod executor;
 mod ffi;
 mod local_client;
 mod local_session;
+mod metrics;
+#[cfg(feature = "tokio")]
+mod poll_loop;
 mod server;
+mod sessions;
+mod shard;
 
+pub use executor::ExecutionStrategy;
 pub use local_client::LocalClient;
 pub use local_session::LocalSession;
-pub use server::{Server, ServerError, SessionHandler};
+pub use metrics::{Histogram, ServerMetrics};
+#[cfg(feature = "tokio")]
+pub use poll_loop::PollLoopOptions;
+pub use server::{Server, ServerError, ServerOptions, SessionHandler};
+pub use shard::ShardPlacement;
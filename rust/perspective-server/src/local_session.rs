@@ -10,10 +10,20 @@
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
 
+use std::time::Instant;
+
+use futures::future::join_all;
+use perspective_client::proto::request::ClientReq;
+use perspective_client::proto::response::ClientResp;
+use perspective_client::proto::{
+    self, Request, Response, ServerCloseSessionResp, ServerDeleteViewResp, ServerSessionsResp,
+};
 use perspective_client::Session;
+use prost::Message;
 
 use crate::ffi;
 use crate::server::{Server, ServerError};
+use crate::shard::{find_response, is_success, merge_responses, Route};
 
 /// A struct for implementing [`perspective_client::Session`] against an
 /// same-process [`Server`] instance.
@@ -23,6 +33,7 @@ use crate::server::{Server, ServerError};
 #[derive(Debug)]
 pub struct LocalSession {
     pub(crate) id: u32,
+    pub(crate) engine_ids: Vec<u32>,
     pub(crate) server: Server,
     pub(crate) closed: bool,
 }
@@ -35,54 +46,199 @@ impl Drop for LocalSession {
     }
 }
 
-impl Session<ServerError> for LocalSession {
-    async fn handle_request(&self, request: &[u8]) -> Result<(), ServerError> {
-        let request = ffi::Request::from(request);
-        let responses = self.server.server.handle_request(self.id, &request);
-        for response in responses.iter_responses() {
-            let cb = self
+impl LocalSession {
+    /// Grant (or revoke) this [`LocalSession`] access to the administrative
+    /// requests `ServerSessionsReq`, `ServerCloseSessionReq` and
+    /// `ServerDeleteViewReq`, which expose [`Server::sessions`],
+    /// [`Server::close_session`] and [`Server::delete_view`] to its
+    /// [`perspective_client::Client`]. Sessions are not administrators by
+    /// default.
+    pub fn set_admin(&self, is_admin: bool) {
+        self.server.registry.set_admin(self.id, is_admin);
+    }
+
+    /// Attach an authenticated identity (e.g. a user name) to this
+    /// [`LocalSession`], as reported by [`Server::sessions`].
+    pub fn set_principal(&self, principal: Option<String>) {
+        self.server.registry.set_principal(self.id, principal);
+    }
+
+    /// The identity attached to this [`LocalSession`] by
+    /// [`LocalSession::set_principal`], if any.
+    pub fn principal(&self) -> Option<String> {
+        self.server.registry.principal(self.id)
+    }
+
+    /// Handle a request intended for the [`Server`] itself rather than the
+    /// engine, returning `None` if `req` is not such a request.
+    async fn handle_admin_request(&self, req: &Request) -> Option<ClientResp> {
+        let is_admin = self.server.registry.is_admin(self.id);
+        let result = match &req.client_req {
+            Some(
+                ClientReq::ServerSessionsReq(_)
+                | ClientReq::ServerCloseSessionReq(_)
+                | ClientReq::ServerDeleteViewReq(_),
+            ) if !is_admin => Err("Unauthorized".into()),
+            Some(ClientReq::ServerSessionsReq(_)) => {
+                Ok(ClientResp::ServerSessionsResp(ServerSessionsResp {
+                    sessions: self
+                        .server
+                        .sessions()
+                        .into_iter()
+                        .map(|x| x.into())
+                        .collect(),
+                }))
+            },
+            Some(ClientReq::ServerCloseSessionReq(close)) => self
+                .server
+                .close_session(close.session_id)
+                .await
+                .map(|_| ClientResp::ServerCloseSessionResp(ServerCloseSessionResp {})),
+            Some(ClientReq::ServerDeleteViewReq(delete)) => self
                 .server
-                .callbacks
-                .read()
+                .delete_view(&delete.view_id)
                 .await
-                .get(&response.client_id())
-                .cloned();
+                .map(|_| ClientResp::ServerDeleteViewResp(ServerDeleteViewResp {})),
+            _ => return None,
+        };
 
-            if let Some(f) = cb {
-                f(response.msg()).await?;
-            }
+        Some(result.unwrap_or_else(|e: ServerError| {
+            ClientResp::ServerError(proto::ServerError {
+                message: e.to_string(),
+                status_code: proto::StatusCode::ServerError as i32,
+            })
+        }))
+    }
+
+    /// Send an encoded request to the engine of a single shard.
+    async fn call(&self, shard: usize, request: &[u8]) -> Result<ffi::ResponseBatch, ServerError> {
+        let engine_id = self.engine_ids[shard];
+        let request = ffi::Request::from(request);
+        self.server.shards[shard]
+            .run(Some(self.id), move |engine| {
+                engine.handle_request(engine_id, &request)
+            })
+            .await
+    }
+
+    /// Update the [`Server`]'s routes and metrics from the engine's
+    /// `responses` to `req`, before they are dispatched.
+    fn observe_responses(
+        &self,
+        shard: usize,
+        req: &Request,
+        responses: &ffi::ResponseBatch,
+    ) -> Result<(), ServerError> {
+        let engine_id = self.engine_ids[shard];
+        let router = &self.server.router;
+        if router.is_tracked(req) && is_success(responses, engine_id, req.msg_id)? {
+            router.commit(self.id, shard, req);
+        }
+
+        match &req.client_req {
+            Some(ClientReq::TableUpdateReq(_)) => {
+                if let Some(Response {
+                    client_resp: Some(ClientResp::TableUpdateResp(resp)),
+                    ..
+                }) = find_response(responses, engine_id, req.msg_id)?
+                {
+                    self.server
+                        .metrics
+                        .record_update(&req.entity_id, resp.num_rows);
+                }
+            },
+            Some(ClientReq::TableDeleteReq(_)) => {
+                if is_success(responses, engine_id, req.msg_id)? {
+                    self.server.metrics.remove_table(&req.entity_id);
+                }
+            },
+            _ => (),
         }
 
         Ok(())
     }
 
-    async fn poll(&self) -> Result<(), ServerError> {
-        let responses = self.server.server.poll();
-        for response in responses.iter_responses() {
-            let cb = self
-                .server
-                .callbacks
-                .read()
-                .await
-                .get(&response.client_id())
-                .cloned();
+    /// Send a request to every shard, merging the responses to this
+    /// [`Session`] for `msg_id` into a single response.
+    async fn broadcast(&self, msg_id: u32, request: &[u8]) -> Result<(), ServerError> {
+        let batches =
+            join_all((0..self.engine_ids.len()).map(|shard| self.call(shard, request))).await;
+
+        let mut merged = None;
+        for (shard, responses) in batches.into_iter().enumerate() {
+            for response in responses?.iter_responses() {
+                let id = self.server.session_id(shard, &response).await;
+                let resp = if id == Some(self.id) {
+                    Some(Response::decode(response.msg())?).filter(|x| x.msg_id == msg_id)
+                } else {
+                    None
+                };
 
-            if let Some(f) = cb {
-                f(response.msg()).await?;
+                match (resp, id) {
+                    (Some(resp), _) => merged = merge_responses(merged, resp),
+                    (None, Some(id)) => self.server.send(id, response.msg()).await?,
+                    (None, None) => (),
+                }
             }
         }
 
+        if let Some(resp) = merged {
+            self.server.send(self.id, &resp.encode_to_vec()).await?;
+        }
+
         Ok(())
     }
+}
+
+impl Session<ServerError> for LocalSession {
+    async fn handle_request(&self, request: &[u8]) -> Result<(), ServerError> {
+        if !self.server.registry.contains(self.id) {
+            return Err("`Session` was closed by the `Server`".into());
+        }
+
+        let req = Request::decode(request)?;
+        if let Some(resp) = self.handle_admin_request(&req).await {
+            let resp = Response {
+                msg_id: req.msg_id,
+                entity_id: req.entity_id,
+                client_resp: Some(resp),
+            };
+
+            return self.server.send(self.id, &resp.encode_to_vec()).await;
+        }
+
+        let start = Instant::now();
+        self.server.registry.observe(self.id, &req);
+        match self.server.router.route(&req) {
+            Route::Shard(shard) => {
+                let responses = self.call(shard, request).await?;
+                self.observe_responses(shard, &req, &responses)?;
+                self.server.dispatch(shard, responses).await?
+            },
+            Route::Broadcast(msg_id) => self.broadcast(msg_id, request).await?,
+        };
+
+        if let Some(client_req) = &req.client_req {
+            self.server
+                .metrics
+                .record_request(client_req, start.elapsed());
+        }
+
+        #[cfg(feature = "tokio")]
+        self.server.poll_notify.notify_one();
+        Ok(())
+    }
+
+    async fn poll(&self) -> Result<(), ServerError> {
+        self.server.poll().await
+    }
 
     async fn close(mut self) {
         self.closed = true;
-        self.server.server.close_session(self.id);
-        self.server
-            .callbacks
-            .write()
-            .await
-            .remove(&self.id)
-            .expect("Already closed");
+        if !self.server.registry.contains(self.id) {
+            tracing::debug!("`Session` already closed by the `Server`");
+        } else if let Err(e) = self.server.close_session(self.id).await {
+            tracing::error!("Failed to close `Session`: {}", e);
+        }
     }
 }
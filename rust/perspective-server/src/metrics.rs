@@ -0,0 +1,130 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use perspective_client::proto::request::ClientReq;
+
+/// Upper bounds (in seconds) of the [`Histogram`] buckets used for request
+/// latencies.
+const LATENCY_BUCKETS: [f64; 10] = [0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 1.0, 5.0, 10.0];
+
+/// A cumulative histogram, in the style of a Prometheus histogram.
+#[derive(Clone, Debug)]
+pub struct Histogram {
+    /// `(upper_bound, count)` pairs, where `count` is the number of
+    /// observations less-than-or-equal-to `upper_bound`.
+    pub buckets: Vec<(f64, u64)>,
+    pub sum: f64,
+    pub count: u64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Histogram {
+            buckets: LATENCY_BUCKETS.iter().map(|x| (*x, 0)).collect(),
+            sum: 0.0,
+            count: 0,
+        }
+    }
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        for (bound, count) in self.buckets.iter_mut() {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// A snapshot of a [`crate::Server`]'s runtime metrics, from
+/// [`crate::Server::metrics`].
+#[derive(Clone, Debug, Default)]
+pub struct ServerMetrics {
+    /// Latency of [`crate::Session::handle_request`] in seconds, by request
+    /// kind (e.g. `"view_to_arrow"`).
+    pub request_latencies: HashMap<&'static str, Histogram>,
+
+    /// The number of successful `Table::update` calls, by table name. A
+    /// table's entry is removed when it is deleted.
+    pub table_updates: HashMap<String, u64>,
+
+    /// The number of rows received by successful `Table::update` calls, by
+    /// table name. A table's entry is removed when it is deleted.
+    pub table_rows_updated: HashMap<String, u64>,
+
+    /// The number of engine calls waiting to be scheduled, for each shard.
+    /// Always `0` for [`crate::ExecutionStrategy::Inline`].
+    pub queue_depth: Vec<usize>,
+}
+
+#[derive(Default)]
+pub(crate) struct MetricsRecorder(RwLock<ServerMetrics>);
+
+impl MetricsRecorder {
+    pub(crate) fn record_request(&self, req: &ClientReq, elapsed: Duration) {
+        let mut metrics = self.0.write().expect("Metrics lock poisoned");
+        metrics
+            .request_latencies
+            .entry(req.kind())
+            .or_default()
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Record a successful `Table::update` of `num_rows` rows.
+    pub(crate) fn record_update(&self, table_id: &str, num_rows: u32) {
+        let mut metrics = self.0.write().expect("Metrics lock poisoned");
+        *metrics
+            .table_updates
+            .entry(table_id.to_owned())
+            .or_default() += 1;
+
+        *metrics
+            .table_rows_updated
+            .entry(table_id.to_owned())
+            .or_default() += num_rows as u64;
+    }
+
+    /// Forget the per-table metrics of a deleted table.
+    pub(crate) fn remove_table(&self, table_id: &str) {
+        let mut metrics = self.0.write().expect("Metrics lock poisoned");
+        metrics.table_updates.remove(table_id);
+        metrics.table_rows_updated.remove(table_id);
+    }
+
+    pub(crate) fn snapshot(&self) -> ServerMetrics {
+        self.0.read().expect("Metrics lock poisoned").clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_is_cumulative() {
+        let mut histogram = Histogram::default();
+        histogram.observe(0.002);
+        histogram.observe(0.5);
+        assert_eq!(histogram.buckets[0], (0.001, 0));
+        assert_eq!(histogram.buckets[1], (0.005, 1));
+        assert_eq!(histogram.buckets[7], (1.0, 2));
+        assert_eq!(histogram.count, 2);
+    }
+}
@@ -0,0 +1,63 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+
+use crate::server::Server;
+
+/// Options for [`Server::spawn_poll_loop`].
+#[derive(Clone, Debug, Default)]
+pub struct PollLoopOptions {
+    /// Poll at least this often, even if no requests have been handled.
+    /// Defaults to only polling after requests.
+    pub interval: Option<Duration>,
+
+    /// After a request is handled, wait this long before polling, so that a
+    /// burst of requests (e.g. many small `Table::update` calls) is flushed
+    /// by a single poll. Defaults to polling immediately.
+    pub coalesce: Option<Duration>,
+}
+
+impl Server {
+    /// Spawn a [`tokio`] task which calls [`Server::poll`] whenever a
+    /// [`crate::Session`] handles a request, and/or on a fixed interval, per
+    /// [`PollLoopOptions`]. This delivers e.g. `View::on_update` callbacks to
+    /// every [`crate::Session`] promptly, even those which are not currently
+    /// sending requests of their own.
+    ///
+    /// The task holds a reference to this [`Server`] and runs until the
+    /// returned [`JoinHandle`] is aborted.
+    pub fn spawn_poll_loop(&self, options: PollLoopOptions) -> JoinHandle<()> {
+        let server = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let notified = server.poll_notify.notified();
+                match options.interval {
+                    Some(interval) => {
+                        let _ = tokio::time::timeout(interval, notified).await;
+                    },
+                    None => notified.await,
+                };
+
+                if let Some(coalesce) = options.coalesce {
+                    tokio::time::sleep(coalesce).await;
+                }
+
+                if let Err(e) = server.poll().await {
+                    tracing::error!("Poll loop error: {}", e);
+                }
+            }
+        })
+    }
+}
@@ -12,16 +12,21 @@
 
 use std::collections::HashMap;
 use std::error::Error;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 
 use async_lock::RwLock;
-use futures::future::BoxFuture;
+use futures::future::{join_all, BoxFuture};
 use futures::Future;
 pub use perspective_client::Session;
 
+use crate::executor::{ExecutionStrategy, Executor};
 use crate::ffi;
 use crate::local_client::LocalClient;
 use crate::local_session::LocalSession;
+use crate::metrics::{MetricsRecorder, ServerMetrics};
+use crate::sessions::SessionRegistry;
+use crate::shard::{Router, ShardPlacement};
 
 pub type ServerError = Box<dyn Error + Send + Sync>;
 
@@ -42,13 +47,44 @@ pub trait SessionHandler: Send + Sync {
     ) -> impl Future<Output = Result<(), ServerError>> + Send + 'a;
 }
 
+/// Options for constructing a [`Server`] via [`Server::new`].
+#[derive(Clone, Debug, Default)]
+pub struct ServerOptions {
+    /// How engine calls made on behalf of this [`Server`]'s [`Session`]s are
+    /// scheduled. Defaults to [`ExecutionStrategy::Inline`].
+    pub execution: ExecutionStrategy,
+
+    /// The number of independent engine instances ("shards") this [`Server`]
+    /// runs, each with its own [`ExecutionStrategy`] scheduler. Each
+    /// [`perspective_client::Table`] (and its views) lives on exactly one
+    /// shard, so with a threaded [`ExecutionStrategy`], requests for tables on
+    /// different shards are processed in parallel. Defaults to `1`.
+    pub shards: Option<usize>,
+
+    /// How tables are assigned to shards, when `shards` is greater than `1`.
+    pub placement: ShardPlacement,
+}
+
 /// An instance of a Perspective server. Each [`Server`] instance is separate,
 /// and does not share [`perspective_client::Table`] (or other) data with other
 /// [`Server`]s.
 #[derive(Clone)]
 pub struct Server {
-    pub(crate) server: Arc<ffi::Server>,
+    pub(crate) shards: Arc<Vec<Executor>>,
+    pub(crate) router: Arc<Router>,
     pub(crate) callbacks: Arc<RwLock<HashMap<u32, SessionCallback>>>,
+
+    /// Maps each shard's engine-assigned session id to the [`Server`]-wide
+    /// session id used as a key for `callbacks`.
+    pub(crate) engine_sessions: Arc<RwLock<HashMap<(usize, u32), u32>>>,
+    pub(crate) registry: Arc<SessionRegistry>,
+    pub(crate) metrics: Arc<MetricsRecorder>,
+    id_gen: Arc<AtomicU32>,
+
+    /// Notified whenever a request is handled, which may have left data that
+    /// needs to be flushed by [`Server::poll`].
+    #[cfg(feature = "tokio")]
+    pub(crate) poll_notify: Arc<tokio::sync::Notify>,
 }
 
 impl std::fmt::Debug for Server {
@@ -61,13 +97,112 @@ impl std::fmt::Debug for Server {
 
 impl Default for Server {
     fn default() -> Self {
-        let server = Arc::new(ffi::Server::new());
-        let callbacks = Arc::default();
-        Self { server, callbacks }
+        Self::new(ServerOptions::default()).expect("Inline engine failed to start")
     }
 }
 
 impl Server {
+    /// Create a new [`Server`] with [`ServerOptions`].
+    ///
+    /// The engine itself is synchronous, so by default a long-running request
+    /// (e.g. a `group_by` over millions of rows) blocks the `async` executor
+    /// thread it was called from. Use [`ExecutionStrategy::WorkerThread`] (or
+    /// `BlockingPool` with the `tokio` feature) to move engine calls off the
+    /// executor; pending calls are then queued per-[`Session`] and serviced
+    /// round-robin, so one busy [`Session`] can't starve the others.
+    ///
+    /// When [`ServerOptions::shards`] is greater than `1`, requests are routed
+    /// to the shard hosting their `entity_id`, while server-wide requests such
+    /// as [`perspective_client::Client::get_hosted_table_names`] are
+    /// aggregated across all shards.
+    ///
+    /// Fails if the engine could not be started, e.g. because an
+    /// [`ExecutionStrategy::WorkerThread`] thread could not be spawned.
+    pub fn new(options: ServerOptions) -> Result<Self, ServerError> {
+        let num_shards = options.shards.unwrap_or(1).max(1);
+        let shards = (0..num_shards)
+            .map(|_| Executor::new(options.execution))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            shards: Arc::new(shards),
+            router: Arc::new(Router::new(num_shards, options.placement)),
+            callbacks: Arc::default(),
+            engine_sessions: Arc::default(),
+            registry: Arc::default(),
+            metrics: Arc::default(),
+            id_gen: Arc::new(AtomicU32::new(1)),
+            #[cfg(feature = "tokio")]
+            poll_notify: Arc::default(),
+        })
+    }
+
+    /// A snapshot of this [`Server`]'s request latency, update and queue
+    /// metrics. See also [`Server::sessions`] for per-[`Session`] metrics.
+    pub fn metrics(&self) -> ServerMetrics {
+        let mut metrics = self.metrics.snapshot();
+        metrics.queue_depth = self.shards.iter().map(|x| x.queue_depth()).collect();
+        metrics
+    }
+
+    /// Flush any pending messages for every [`Session`] of this [`Server`].
+    /// This is the same operation as [`Session::poll`], which may be called
+    /// from any [`Session`] with the same effect.
+    ///
+    /// See also `Server::spawn_poll_loop` (with the `tokio` feature), which
+    /// calls this method in the background.
+    pub async fn poll(&self) -> Result<(), ServerError> {
+        let batches = join_all(
+            self.shards
+                .iter()
+                .map(|shard| shard.run(None, |engine| engine.poll())),
+        )
+        .await;
+
+        for (shard, responses) in batches.into_iter().enumerate() {
+            self.dispatch(shard, responses?).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Look up the [`Server`]-wide id of the [`Session`] a response from
+    /// `shard` is addressed to.
+    pub(crate) async fn session_id(&self, shard: usize, response: &ffi::Response) -> Option<u32> {
+        self.engine_sessions
+            .read()
+            .await
+            .get(&(shard, response.client_id()))
+            .cloned()
+    }
+
+    /// Send a response message to the callback of the [`Session`] `id`.
+    pub(crate) async fn send(&self, id: u32, msg: &[u8]) -> Result<(), ServerError> {
+        let cb = self.callbacks.read().await.get(&id).cloned();
+        if let Some(f) = cb {
+            self.registry.record_bytes_sent(id, msg.len());
+            f(msg).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Route a [`ffi::ResponseBatch`] from `shard` to the callbacks of the
+    /// [`Session`]s each response is addressed to.
+    pub(crate) async fn dispatch(
+        &self,
+        shard: usize,
+        responses: ffi::ResponseBatch,
+    ) -> Result<(), ServerError> {
+        for response in responses.iter_responses() {
+            if let Some(id) = self.session_id(shard, &response).await {
+                self.send(id, response.msg()).await?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// An alternative method for creating a new [`Session`] for this
     /// [`Server`], from a callback closure instead of a via a trait.
     /// See [`Server::new_session`] for details.
@@ -81,7 +216,24 @@ impl Server {
     where
         F: for<'a> Fn(&'a [u8]) -> BoxFuture<'a, Result<(), ServerError>> + 'static + Sync + Send,
     {
-        let id = self.server.new_session();
+        let id = self.id_gen.fetch_add(1, Ordering::Relaxed);
+        let engine_ids = join_all(
+            self.shards
+                .iter()
+                .map(|shard| shard.run(Some(id), |engine| engine.new_session())),
+        )
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Engine terminated");
+
+        let mut engine_sessions = self.engine_sessions.write().await;
+        for (shard, engine_id) in engine_ids.iter().enumerate() {
+            engine_sessions.insert((shard, *engine_id), id);
+        }
+
+        drop(engine_sessions);
+        self.registry.insert(id);
         let server = self.clone();
         self.callbacks
             .write()
@@ -90,6 +242,7 @@ impl Server {
 
         LocalSession {
             id,
+            engine_ids,
             server,
             closed: false,
         }
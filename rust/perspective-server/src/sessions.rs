@@ -0,0 +1,190 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::SystemTime;
+
+use perspective_client::proto::request::ClientReq;
+use perspective_client::proto::Request;
+use perspective_client::{SessionInfo, SessionViewInfo};
+
+/// Book-keeping for a single session, for [`crate::Server::sessions`].
+struct SessionState {
+    connected_since: SystemTime,
+    views: HashMap<String, SessionViewInfo>,
+    bytes_sent: u64,
+    is_admin: bool,
+    principal: Option<String>,
+}
+
+fn on_update_count<'a>(
+    sessions: &'a mut HashMap<u32, SessionState>,
+    view_id: &str,
+) -> Option<&'a mut u32> {
+    sessions
+        .values_mut()
+        .find_map(|state| state.views.get_mut(view_id))
+        .map(|view| &mut view.on_update_subscriptions)
+}
+
+/// Tracks the [`SessionInfo`] of every open session of a [`crate::Server`],
+/// by observing the requests each session handles.
+#[derive(Default)]
+pub(crate) struct SessionRegistry(RwLock<HashMap<u32, SessionState>>);
+
+impl SessionRegistry {
+    pub(crate) fn insert(&self, session_id: u32) {
+        self.0
+            .write()
+            .expect("Registry lock poisoned")
+            .insert(session_id, SessionState {
+                connected_since: SystemTime::now(),
+                views: HashMap::default(),
+                bytes_sent: 0,
+                is_admin: false,
+                principal: None,
+            });
+    }
+
+    pub(crate) fn remove(&self, session_id: u32) -> bool {
+        self.0
+            .write()
+            .expect("Registry lock poisoned")
+            .remove(&session_id)
+            .is_some()
+    }
+
+    pub(crate) fn contains(&self, session_id: u32) -> bool {
+        self.0
+            .read()
+            .expect("Registry lock poisoned")
+            .contains_key(&session_id)
+    }
+
+    pub(crate) fn set_admin(&self, session_id: u32, is_admin: bool) {
+        if let Some(state) = self
+            .0
+            .write()
+            .expect("Registry lock poisoned")
+            .get_mut(&session_id)
+        {
+            state.is_admin = is_admin;
+        }
+    }
+
+    pub(crate) fn is_admin(&self, session_id: u32) -> bool {
+        self.0
+            .read()
+            .expect("Registry lock poisoned")
+            .get(&session_id)
+            .map(|x| x.is_admin)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn set_principal(&self, session_id: u32, principal: Option<String>) {
+        if let Some(state) = self
+            .0
+            .write()
+            .expect("Registry lock poisoned")
+            .get_mut(&session_id)
+        {
+            state.principal = principal;
+        }
+    }
+
+    pub(crate) fn principal(&self, session_id: u32) -> Option<String> {
+        self.0
+            .read()
+            .expect("Registry lock poisoned")
+            .get(&session_id)
+            .and_then(|x| x.principal.clone())
+    }
+
+    pub(crate) fn record_bytes_sent(&self, session_id: u32, len: usize) {
+        if let Some(state) = self
+            .0
+            .write()
+            .expect("Registry lock poisoned")
+            .get_mut(&session_id)
+        {
+            state.bytes_sent += len as u64;
+        }
+    }
+
+    /// The id of the session which created the view `view_id`.
+    pub(crate) fn view_owner(&self, view_id: &str) -> Option<u32> {
+        let sessions = self.0.read().expect("Registry lock poisoned");
+        sessions
+            .iter()
+            .find(|(_, state)| state.views.contains_key(view_id))
+            .map(|(id, _)| *id)
+    }
+
+    /// Update the registry for a request which `session_id` is about to
+    /// handle.
+    pub(crate) fn observe(&self, session_id: u32, req: &Request) {
+        let mut sessions = self.0.write().expect("Registry lock poisoned");
+
+        match &req.client_req {
+            Some(ClientReq::TableMakeViewReq(make_view)) => {
+                if let Some(state) = sessions.get_mut(&session_id) {
+                    state
+                        .views
+                        .insert(make_view.view_id.clone(), SessionViewInfo {
+                            view_id: make_view.view_id.clone(),
+                            table_id: req.entity_id.clone(),
+                            config: make_view
+                                .config
+                                .clone()
+                                .map(|x| x.into())
+                                .unwrap_or_default(),
+                            on_update_subscriptions: 0,
+                        });
+                }
+            },
+            Some(ClientReq::ViewDeleteReq(_)) => {
+                for state in sessions.values_mut() {
+                    state.views.remove(&req.entity_id);
+                }
+            },
+            Some(ClientReq::ViewOnUpdateReq(_)) => {
+                if let Some(count) = on_update_count(&mut sessions, &req.entity_id) {
+                    *count += 1;
+                }
+            },
+            Some(ClientReq::ViewRemoveOnUpdateReq(_)) => {
+                if let Some(count) = on_update_count(&mut sessions, &req.entity_id) {
+                    *count = count.saturating_sub(1);
+                }
+            },
+            _ => (),
+        }
+    }
+
+    pub(crate) fn sessions(&self) -> Vec<SessionInfo> {
+        let sessions = self.0.read().expect("Registry lock poisoned");
+        let mut infos = sessions
+            .iter()
+            .map(|(id, state)| SessionInfo {
+                id: *id,
+                connected_since: state.connected_since,
+                views: state.views.values().cloned().collect(),
+                bytes_sent: state.bytes_sent,
+                principal: state.principal.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        infos.sort_by_key(|x| x.id);
+        infos
+    }
+}
@@ -0,0 +1,374 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, RwLock};
+
+use perspective_client::proto::request::ClientReq;
+use perspective_client::proto::response::ClientResp;
+use perspective_client::proto::{make_table_data, MakeTableData, Request, Response};
+use prost::Message;
+
+use crate::ffi;
+use crate::server::ServerError;
+
+type PlacementFn = Arc<dyn Fn(&str) -> Option<usize> + Send + Sync>;
+
+/// How a sharded [`crate::Server`] chooses which engine instance hosts a new
+/// [`perspective_client::Table`], by the table's name.
+#[derive(Clone, Default)]
+pub enum ShardPlacement {
+    /// Hash the table name.
+    #[default]
+    Hash,
+
+    /// Choose a shard index explicitly from the table name, falling back to
+    /// [`ShardPlacement::Hash`] when the function returns `None`. Indices
+    /// greater than the number of shards wrap around.
+    Explicit(PlacementFn),
+}
+
+impl std::fmt::Debug for ShardPlacement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShardPlacement::Hash => write!(f, "Hash"),
+            ShardPlacement::Explicit(_) => write!(f, "Explicit"),
+        }
+    }
+}
+
+impl ShardPlacement {
+    /// Create a [`ShardPlacement::Explicit`] from a closure.
+    pub fn explicit<F>(f: F) -> Self
+    where
+        F: Fn(&str) -> Option<usize> + Send + Sync + 'static,
+    {
+        ShardPlacement::Explicit(Arc::new(f))
+    }
+}
+
+/// Where a request should be sent.
+pub(crate) enum Route {
+    /// A request for a single entity, which lives on exactly one shard.
+    Shard(usize),
+
+    /// A server-wide request which must be sent to every shard, and whose
+    /// responses (for the message id) must be merged into one.
+    Broadcast(u32),
+}
+
+/// Tracks which shard each `entity_id` (table or view name) lives on.
+pub(crate) struct Router {
+    num_shards: usize,
+    placement: ShardPlacement,
+    tables: RwLock<HashMap<String, usize>>,
+    views: RwLock<HashMap<String, (usize, u32)>>,
+}
+
+impl Router {
+    pub(crate) fn new(num_shards: usize, placement: ShardPlacement) -> Self {
+        Router {
+            num_shards,
+            placement,
+            tables: RwLock::default(),
+            views: RwLock::default(),
+        }
+    }
+
+    fn place(&self, table_id: &str) -> usize {
+        let explicit = match &self.placement {
+            ShardPlacement::Hash => None,
+            ShardPlacement::Explicit(f) => f(table_id),
+        };
+
+        explicit.unwrap_or_else(|| {
+            let mut hasher = DefaultHasher::new();
+            table_id.hash(&mut hasher);
+            hasher.finish() as usize
+        }) % self.num_shards
+    }
+
+    fn table_shard(&self, table_id: &str) -> usize {
+        let tables = self.tables.read().expect("Router lock poisoned");
+        match tables.get(table_id) {
+            Some(shard) => *shard,
+            None => self.place(table_id),
+        }
+    }
+
+    pub(crate) fn view_shard(&self, view_id: &str) -> Option<usize> {
+        let views = self.views.read().expect("Router lock poisoned");
+        views.get(view_id).map(|(shard, _)| *shard)
+    }
+
+    /// Determine the [`Route`] for a [`Request`].
+    pub(crate) fn route(&self, req: &Request) -> Route {
+        if self.num_shards == 1 {
+            return Route::Shard(0);
+        }
+
+        match &req.client_req {
+            Some(
+                ClientReq::GetHostedTablesReq(_)
+                | ClientReq::RemoveHostedTablesUpdateReq(_)
+                | ClientReq::ServerSystemInfoReq(_),
+            ) => Route::Broadcast(req.msg_id),
+            Some(ClientReq::GetFeaturesReq(_)) | None => Route::Shard(0),
+            Some(ClientReq::MakeTableReq(make_table)) => {
+                // Tables created from a view must live on the view's shard.
+                let shard = match &make_table.data {
+                    Some(MakeTableData {
+                        data: Some(make_table_data::Data::FromView(view_id)),
+                        ..
+                    }) => self.view_shard(view_id),
+                    _ => None,
+                }
+                .unwrap_or_else(|| self.place(&req.entity_id));
+
+                Route::Shard(shard)
+            },
+            Some(ClientReq::TableMakeViewReq(_)) => Route::Shard(self.table_shard(&req.entity_id)),
+            Some(_) => Route::Shard(
+                self.view_shard(&req.entity_id)
+                    .unwrap_or_else(|| self.table_shard(&req.entity_id)),
+            ),
+        }
+    }
+
+    /// Whether [`Router::commit`] must be called when `req` succeeds, i.e.
+    /// `req` creates or destroys an entity.
+    pub(crate) fn is_tracked(&self, req: &Request) -> bool {
+        self.num_shards > 1
+            && matches!(
+                req.client_req,
+                Some(
+                    ClientReq::MakeTableReq(_)
+                        | ClientReq::TableMakeViewReq(_)
+                        | ClientReq::TableDeleteReq(_)
+                        | ClientReq::ViewDeleteReq(_)
+                )
+            )
+    }
+
+    /// Update the entity map for a [`Request`] made by the session
+    /// `session_id`, which the engine of `shard` has successfully completed.
+    pub(crate) fn commit(&self, session_id: u32, shard: usize, req: &Request) {
+        match &req.client_req {
+            Some(ClientReq::MakeTableReq(_)) => {
+                self.tables
+                    .write()
+                    .expect("Router lock poisoned")
+                    .insert(req.entity_id.clone(), shard);
+            },
+            Some(ClientReq::TableMakeViewReq(make_view)) => {
+                self.views
+                    .write()
+                    .expect("Router lock poisoned")
+                    .insert(make_view.view_id.clone(), (shard, session_id));
+            },
+            Some(ClientReq::TableDeleteReq(_)) => {
+                self.tables
+                    .write()
+                    .expect("Router lock poisoned")
+                    .remove(&req.entity_id);
+            },
+            Some(ClientReq::ViewDeleteReq(_)) => {
+                self.views
+                    .write()
+                    .expect("Router lock poisoned")
+                    .remove(&req.entity_id);
+            },
+            _ => (),
+        }
+    }
+
+    /// Forget the views owned by a closed session, which the engine deletes
+    /// along with the session.
+    pub(crate) fn close_session(&self, session_id: u32) {
+        self.views
+            .write()
+            .expect("Router lock poisoned")
+            .retain(|_, (_, owner)| *owner != session_id);
+    }
+}
+
+/// The response to the request `msg_id` from the engine session `engine_id`
+/// in `responses`, if any.
+pub(crate) fn find_response(
+    responses: &ffi::ResponseBatch,
+    engine_id: u32,
+    msg_id: u32,
+) -> Result<Option<Response>, ServerError> {
+    for response in responses.iter_responses() {
+        if response.client_id() == engine_id {
+            let resp = Response::decode(response.msg())?;
+            if resp.msg_id == msg_id {
+                return Ok(Some(resp));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Whether `responses` contains a successful response to the request
+/// `msg_id` from the engine session `engine_id`.
+pub(crate) fn is_success(
+    responses: &ffi::ResponseBatch,
+    engine_id: u32,
+    msg_id: u32,
+) -> Result<bool, ServerError> {
+    Ok(find_response(responses, engine_id, msg_id)?
+        .is_some_and(|resp| !matches!(resp.client_resp, Some(ClientResp::ServerError(_)))))
+}
+
+/// Merge the per-shard responses to a [`Route::Broadcast`] request into one
+/// response. Errors take precedence, `GetHostedTablesResp` table lists are
+/// concatenated and `ServerSystemInfoResp` heap sizes are summed.
+pub(crate) fn merge_responses(acc: Option<Response>, next: Response) -> Option<Response> {
+    let Some(mut acc) = acc else {
+        return Some(next);
+    };
+
+    match (&mut acc.client_resp, next.client_resp) {
+        (Some(ClientResp::ServerError(_)), _) => (),
+        (_, next @ Some(ClientResp::ServerError(_))) => acc.client_resp = next,
+        (
+            Some(ClientResp::GetHostedTablesResp(acc_tables)),
+            Some(ClientResp::GetHostedTablesResp(next_tables)),
+        ) => acc_tables.table_infos.extend(next_tables.table_infos),
+        (
+            Some(ClientResp::ServerSystemInfoResp(acc_info)),
+            Some(ClientResp::ServerSystemInfoResp(next_info)),
+        ) => acc_info.heap_size += next_info.heap_size,
+        _ => (),
+    };
+
+    Some(acc)
+}
+
+#[cfg(test)]
+mod tests {
+    use perspective_client::proto::{
+        GetHostedTablesResp, HostedTable, MakeTableReq, ServerSystemInfoResp, TableDeleteReq,
+        TableMakeViewReq, ViewDeleteReq,
+    };
+
+    use super::*;
+
+    fn request(entity_id: &str, client_req: ClientReq) -> Request {
+        Request {
+            msg_id: 1,
+            entity_id: entity_id.to_owned(),
+            client_req: Some(client_req),
+        }
+    }
+
+    fn shard(route: Route) -> usize {
+        match route {
+            Route::Shard(shard) => shard,
+            Route::Broadcast(_) => panic!("Expected single shard"),
+        }
+    }
+
+    #[test]
+    fn test_views_follow_their_table() {
+        let router = Router::new(4, ShardPlacement::explicit(|name| name.parse().ok()));
+        let make_table = request("3", ClientReq::MakeTableReq(MakeTableReq::default()));
+        assert_eq!(shard(router.route(&make_table)), 3);
+        router.commit(0, 3, &make_table);
+        let make_view = request(
+            "3",
+            ClientReq::TableMakeViewReq(TableMakeViewReq {
+                view_id: "view".to_owned(),
+                config: None,
+            }),
+        );
+
+        assert_eq!(shard(router.route(&make_view)), 3);
+        router.commit(0, 3, &make_view);
+        assert_eq!(router.view_shard("view"), Some(3));
+        let delete = request("view", ClientReq::ViewDeleteReq(ViewDeleteReq {}));
+        assert_eq!(shard(router.route(&delete)), 3);
+        router.commit(0, 3, &delete);
+        assert_eq!(router.view_shard("view"), None);
+    }
+
+    #[test]
+    fn test_deleted_tables_are_forgotten() {
+        let router = Router::new(4, ShardPlacement::explicit(|_| None));
+        let make_table = request("table", ClientReq::MakeTableReq(MakeTableReq::default()));
+        router.commit(0, 1, &make_table);
+        assert_eq!(router.table_shard("table"), 1);
+        let delete = request("table", ClientReq::TableDeleteReq(TableDeleteReq {}));
+        assert!(router.is_tracked(&delete));
+        router.commit(0, 1, &delete);
+        assert!(router.tables.read().unwrap().is_empty());
+        assert_eq!(router.table_shard("table"), router.place("table"));
+    }
+
+    #[test]
+    fn test_uncommitted_views_are_not_routed() {
+        let router = Router::new(2, ShardPlacement::default());
+        let make_view = request(
+            "table",
+            ClientReq::TableMakeViewReq(TableMakeViewReq {
+                view_id: "view".to_owned(),
+                config: None,
+            }),
+        );
+
+        router.route(&make_view);
+        assert_eq!(router.view_shard("view"), None);
+    }
+
+    #[test]
+    fn test_merge_hosted_tables() {
+        let resp = |name: &str| Response {
+            msg_id: 1,
+            entity_id: "".to_owned(),
+            client_resp: Some(ClientResp::GetHostedTablesResp(GetHostedTablesResp {
+                table_infos: vec![HostedTable {
+                    entity_id: name.to_owned(),
+                    index: None,
+                    limit: None,
+                }],
+            })),
+        };
+
+        let merged = merge_responses(merge_responses(None, resp("a")), resp("b"));
+        match merged.and_then(|x| x.client_resp) {
+            Some(ClientResp::GetHostedTablesResp(GetHostedTablesResp { table_infos })) => {
+                assert_eq!(table_infos.len(), 2)
+            },
+            _ => panic!("Expected GetHostedTablesResp"),
+        }
+    }
+
+    #[test]
+    fn test_merge_system_info() {
+        let resp = |heap_size: f64| Response {
+            msg_id: 1,
+            entity_id: "".to_owned(),
+            client_resp: Some(ClientResp::ServerSystemInfoResp(ServerSystemInfoResp {
+                heap_size,
+            })),
+        };
+
+        let merged = merge_responses(merge_responses(None, resp(1.0)), resp(2.0));
+        match merged.and_then(|x| x.client_resp) {
+            Some(ClientResp::ServerSystemInfoResp(info)) => assert_eq!(info.heap_size, 3.0),
+            _ => panic!("Expected ServerSystemInfoResp"),
+        }
+    }
+}
@@ -276,6 +276,7 @@ impl Component for ConfigSelector {
             },
             ConfigSelectorMsg::Close(index, DragTarget::Filter) => {
                 self.filter_dropdown.hide().unwrap();
+                let index = ctx.props().session.get_view_config().filter_index(index);
                 let mut filter = ctx.props().session.get_view_config().filter.clone();
                 filter.remove(index);
                 let config = ViewConfigUpdate {
@@ -336,15 +337,18 @@ impl Component for ConfigSelector {
             },
             ConfigSelectorMsg::SetFilterValue(index, input) => {
                 let mut filter = ctx.props().session.get_view_config().filter.clone();
+                let Some(clause) = filter.get_mut(index).and_then(|x| x.as_filter_mut()) else {
+                    return false;
+                };
 
                 // TODO Can't special case these - need to make this part of the
                 // Features API.
-                let update = if filter[index].op() == "in" || filter[index].op() == "not in" {
-                    let current = filter[index].term().to_string();
+                let update = if clause.op() == "in" || clause.op() == "not in" {
+                    let current = clause.term().to_string();
                     let mut tokens = current.split(',').collect::<Vec<_>>();
                     tokens.pop();
                     tokens.push(&input);
-                    *filter[index].term_mut() = FilterTerm::Array(
+                    *clause.term_mut() = FilterTerm::Array(
                         tokens
                             .iter()
                             .map(|x| Scalar::String(x.trim().to_owned()))
@@ -357,7 +361,7 @@ impl Component for ConfigSelector {
                         ..ViewConfigUpdate::default()
                     }
                 } else {
-                    *filter[index].term_mut() = FilterTerm::Scalar(Scalar::String(input));
+                    *clause.term_mut() = FilterTerm::Scalar(Scalar::String(input));
                     let filter = Some(filter);
                     ViewConfigUpdate {
                         filter,
@@ -407,11 +411,9 @@ impl Component for ConfigSelector {
             ConfigSelectorMsg::New(DragTarget::Filter, InPlaceColumn::Column(column)) => {
                 let mut view_config = ctx.props().session.get_view_config().clone();
                 let op = ctx.props().default_op(column.as_str()).unwrap_or_default();
-                view_config.filter.push(Filter::new(
-                    &column,
-                    &op,
-                    FilterTerm::Scalar(Scalar::Null),
-                ));
+                view_config
+                    .filter
+                    .push(Filter::new(&column, &op, FilterTerm::Scalar(Scalar::Null)).into());
 
                 let update = ViewConfigUpdate {
                     filter: Some(view_config.filter),
@@ -481,13 +483,16 @@ impl Component for ConfigSelector {
             ConfigSelectorMsg::New(DragTarget::Filter, InPlaceColumn::Expression(col)) => {
                 let mut view_config = ctx.props().session.get_view_config().clone();
                 let column = col.name.as_ref();
-                view_config.filter.push(Filter::new(
-                    column,
-                    &ctx.props()
-                        .default_op(col.name.as_ref())
-                        .unwrap_or_default(),
-                    FilterTerm::Scalar(Scalar::Null),
-                ));
+                view_config.filter.push(
+                    Filter::new(
+                        column,
+                        &ctx.props()
+                            .default_op(col.name.as_ref())
+                            .unwrap_or_default(),
+                        FilterTerm::Scalar(Scalar::Null),
+                    )
+                    .into(),
+                );
 
                 view_config.expressions.insert(&col);
                 let update = ViewConfigUpdate {
@@ -634,13 +639,13 @@ impl Component for ConfigSelector {
                     allow_duplicates=true
                     parent={ctx.link().clone()}
                     {column_dropdown}
-                    exclude={config.filter.iter().map(|x| x.column().to_string()).collect::<HashSet<_>>()}
+                    exclude={config.filter.iter().flat_map(|x| x.filters()).map(|x| x.column().to_string()).collect::<HashSet<_>>()}
                     dragdrop={&ctx.props().dragdrop}
                     is_dragover={ctx.props().dragdrop.is_dragover(DragTarget::Filter).map(|(index, name)| {
                         (index, Filter::new(&name, "", FilterTerm::Scalar(Scalar::Null)))
                     })}
                 >
-                    { for config.filter.iter().enumerate().map(|(idx, filter)| {
+                    { for config.filter.iter().enumerate().filter_map(|(idx, x)| Some((idx, x.as_filter()?))).map(|(idx, filter)| {
                             let filter_keydown = ctx.link()
                                 .callback(move |txt| ConfigSelectorMsg::SetFilterValue(idx, txt));
 
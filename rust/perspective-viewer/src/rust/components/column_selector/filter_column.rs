@@ -134,7 +134,10 @@ impl FilterColumnProps {
     /// - `op` The new `FilterOp`.
     fn update_filter_op(&self, op: String) {
         let mut filter = self.session.get_view_config().filter.clone();
-        let filter_column = &mut filter.get_mut(self.idx).expect("Filter on no column");
+        let filter_column = filter
+            .get_mut(self.idx)
+            .and_then(|x| x.as_filter_mut())
+            .expect("Filter on no column");
         *filter_column.op_mut() = op;
         let update = ViewConfigUpdate {
             filter: Some(filter),
@@ -152,7 +155,10 @@ impl FilterColumnProps {
     /// - `val` The new filter value.
     fn update_filter_input(&self, val: String) {
         let mut filter = self.session.get_view_config().filter.clone();
-        let filter_column = &mut filter.get_mut(self.idx).expect("Filter on no column");
+        let filter_column = filter
+            .get_mut(self.idx)
+            .and_then(|x| x.as_filter_mut())
+            .expect("Filter on no column");
 
         // TODO This belongs in the Features API.
         let filter_input = if filter_column.op() == "in" || filter_column.op() == "not in" {
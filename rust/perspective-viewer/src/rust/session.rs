@@ -269,7 +269,11 @@ impl Session {
                 .unwrap_or_default()
         }) || config.group_by.iter().any(|col| col == name)
             || config.split_by.iter().any(|col| col == name)
-            || config.filter.iter().any(|col| col.column() == name)
+            || config
+                .filter
+                .iter()
+                .flat_map(|x| x.filters())
+                .any(|col| col.column() == name)
             || config.sort.iter().any(|col| col.0 == name)
     }
 
@@ -626,7 +630,7 @@ impl Session {
             }
         }
 
-        for filter in config.filter.iter() {
+        for filter in config.filter.iter().flat_map(|x| x.filters()) {
             // TODO check filter op
             if all_columns.contains(filter.column()) || expression_names.contains(filter.column()) {
                 let _existed = view_columns.insert(filter.column());
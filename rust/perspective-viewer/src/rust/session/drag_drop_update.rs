@@ -94,7 +94,9 @@ pub impl ViewConfig {
                 update.sort = Some(config.sort.clone());
             },
             DragEffect::Move(DragTarget::Filter) => {
-                config.filter.retain(|x| x.column() != column);
+                config
+                    .filter
+                    .retain(|x| x.as_filter().is_none_or(|x| x.column() != column));
                 update.filter = Some(config.filter.clone());
             },
         }
@@ -162,14 +164,15 @@ pub impl ViewConfig {
                 update.sort = Some(config.sort);
             },
             DragTarget::Filter => {
-                let index = std::cmp::min(index, config.filter.len());
+                let index = config.filter_index(index);
                 config.filter.insert(
                     index,
                     Filter::new(
                         &column,
                         features.default_op(col_type).unwrap_or(""),
                         FilterTerm::Scalar(Scalar::Null),
-                    ),
+                    )
+                    .into(),
                 );
                 update.filter = Some(config.filter);
             },
@@ -107,13 +107,7 @@ pub impl ViewConfig {
         // TODO expression editing can change type, which may invalidate filters
         let filter = filter
             .into_iter()
-            .map(|x| {
-                if x.column() == old_expr.name {
-                    Filter::new(new_expr.name.as_ref(), x.op(), x.term().clone())
-                } else {
-                    x
-                }
-            })
+            .map(|x| rename_filter(x, &old_expr.name, &new_expr.name))
             .collect::<Vec<_>>();
 
         ViewConfigUpdate {
@@ -129,3 +123,21 @@ pub impl ViewConfig {
         }
     }
 }
+
+/// Rename `old` to `new` in a filter clause, recursing into nested groups.
+fn rename_filter(clause: FilterClause, old: &str, new: &str) -> FilterClause {
+    match clause {
+        FilterClause::Filter(x) if x.column() == old => {
+            Filter::new(new, x.op(), x.term().clone()).into()
+        },
+        FilterClause::Group(x) => FilterGroup::new(
+            x.filter_op,
+            x.filter
+                .into_iter()
+                .map(|x| rename_filter(x, old, new))
+                .collect(),
+        )
+        .into(),
+        x => x,
+    }
+}
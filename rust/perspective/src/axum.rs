@@ -9,112 +9,122 @@
 // ┃ This file is part of the Perspective library, distributed under the terms ┃
 // ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
 // ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
-
 use std::net::SocketAddr;
 
 use axum::extract::connect_info::ConnectInfo;
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
 use axum::extract::State;
+use axum::http::HeaderMap;
+#[cfg(any(feature = "rest", feature = "sse"))]
+use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::routing::{get, MethodRouter};
-use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
-use futures::future::{select, Either};
-use futures::{FutureExt, SinkExt, StreamExt};
+use futures::{future, SinkExt, StreamExt};
 
-use crate::client::Session;
-use crate::server::{LocalSession, Server, SessionHandler};
+#[cfg(any(feature = "rest", feature = "sse"))]
+use crate::client::ClientError;
+pub use crate::connection::WebsocketConfig;
+use crate::connection::{serve_websocket_messages, IncomingMessage, OutgoingMessage};
+use crate::server::Server;
 
 /// A local error synonym for this module only.
 type PerspectiveWSError = Box<dyn std::error::Error + Send + Sync>;
 
-/// We must share access to the [`WebSocket`] for both sending and receiving
-/// messages, a message flow which this enum is used to model. When the
-/// client disconnects or the message stream is unrecoverably errored,
-/// we must emit an _end_ message as well.
-enum PerspectiveWSMessage {
-    Incoming(Vec<u8>),
-    Outgoing(Vec<u8>),
-    End,
+/// This handler is responsible for the beginning-to-end lifecycle of a
+/// single WebSocket connection to an [`axum`] server.
+///
+/// Messages will come in from the [`axum::extract::ws::WebSocket`] in binary
+/// form via [`Message::Binary`], where they'll be routed to
+/// [`perspective::Session::handle_request`]. The server may generate
+/// one or more responses, which it will then send back over the socket.
+/// See [`crate::connection::serve_connection`] for the underlying message
+/// loop.
+pub fn websocket_handler() -> MethodRouter<Server> {
+    websocket_handler_with_config(WebsocketConfig::default())
 }
 
-/// A new-type wrapper for an [`UnboundedSender`], whic bypasses the orphan
-/// instance rule allowing us to write a [`SessionHandler`] impl for this
-/// struct.
-#[derive(Clone)]
-struct PerspectiveWSConnection(UnboundedSender<Vec<u8>>);
+/// As [`websocket_handler`], with keepalive, limits and connection checks
+/// configured by a [`WebsocketConfig`].
+pub fn websocket_handler_with_config(config: WebsocketConfig) -> MethodRouter<Server> {
+    get(
+        move |mut ws: WebSocketUpgrade,
+              State(server): State<Server>,
+              ConnectInfo(addr): ConnectInfo<SocketAddr>,
+              headers: HeaderMap| async move {
+            let (protocol, principal) = match config.accept(&headers).await {
+                Ok(accepted) => accepted,
+                Err(status) => {
+                    tracing::warn!("{addr} Rejected, {status}");
+                    return status.into_response();
+                },
+            };
 
-/// The [`SessionHandler`] implementation provides a method for a
-/// [`Session`] to send messages to this
-/// [`axum::extract::ws::WebSocket`], which may (or may
-/// not) be solicited (e.g. within the async call stack of
-/// [`perspective::Session::handle_request`]).
-impl SessionHandler for PerspectiveWSConnection {
-    async fn send_response<'a>(&'a mut self, resp: &'a [u8]) -> Result<(), PerspectiveWSError> {
-        Ok(self.0.send(resp.to_vec()).await?)
-    }
-}
+            if let Some(size) = config.max_frame_size {
+                ws = ws.max_frame_size(size);
+            }
 
-/// The inner message loop handles the full-duplex stream of messages
-/// between the [`perspective::Client`] and [`Session`]. When this
-/// funciton returns, messages are no longer processed.
-async fn process_message_loop(
-    socket: &mut WebSocket,
-    receiver: &mut UnboundedReceiver<Vec<u8>>,
-    session: &mut LocalSession,
-) -> Result<(), PerspectiveWSError> {
-    use Either::*;
-    use Message::*;
-    use PerspectiveWSMessage::*;
+            if let Some(size) = config.max_message_size {
+                ws = ws.max_message_size(size);
+            }
 
-    loop {
-        let msg = match select(socket.recv().boxed(), receiver.next()).await {
-            Right((Some(bytes), _)) => Ok(Outgoing(bytes)),
-            Left((Some(Ok(Binary(bytes))), _)) => Ok(Incoming(bytes)),
-            Right((None, _)) | Left((None | Some(Ok(Close(_))), _)) => Ok(End),
-            Left((Some(Ok(_)), _)) => Err("Unexpected message type".to_string()),
-            Left((Some(Err(err)), _)) => Err(format!("{}", err)),
-        }?;
+            if let Some(protocol) = protocol {
+                ws = ws.protocols([protocol]);
+            }
 
-        match msg {
-            End => break,
-            Outgoing(bytes) => socket.send(Binary(bytes)).await?,
-            Incoming(bytes) => {
-                session.handle_request(&bytes).await?;
-                session.poll().await?
-            },
-        }
-    }
+            tracing::info!("{addr} Connected.");
+            ws.on_upgrade(move |socket| async move {
+                if let Err(msg) = serve_socket(&server, socket, &config, principal).await {
+                    tracing::error!("Internal error {}", msg);
+                }
 
-    Ok(())
+                tracing::info!("{addr} Disconnected.");
+            })
+        },
+    )
 }
 
-/// This handler is responsible for the beginning-to-end lifecycle of a
-/// single WebSocket connection to an [`axum`] server.
-///
-/// Messages will come in from the [`axum::extract::ws::WebSocket`] in binary
-/// form via [`Message::Binary`], where they'll be routed to
-/// [`perspective::Session::handle_request`]. The server may generate
-/// one or more responses, which it will then send back to
-/// the [`axum::extract::ws::WebSocket::send`] method via its
-/// [`SessionHandler`] impl.
-pub fn websocket_handler() -> MethodRouter<Server> {
-    async fn websocket_handler_internal(
-        ws: WebSocketUpgrade,
-        State(server): State<Server>,
-        ConnectInfo(addr): ConnectInfo<SocketAddr>,
-    ) -> impl IntoResponse {
-        tracing::info!("{addr} Connected.");
-        ws.on_upgrade(move |mut socket| async move {
-            let (send, mut receiver) = unbounded::<Vec<u8>>();
-            let mut session = server.new_session(PerspectiveWSConnection(send)).await;
-            if let Err(msg) = process_message_loop(&mut socket, &mut receiver, &mut session).await {
-                tracing::error!("Internal error {}", msg);
-            }
+/// Serve an upgraded [`WebSocket`] to `server`, translating its messages
+/// for [`serve_websocket_messages`].
+async fn serve_socket(
+    server: &Server,
+    socket: WebSocket,
+    config: &WebsocketConfig,
+    principal: Option<String>,
+) -> Result<(), PerspectiveWSError> {
+    let (sink, stream) = socket.split();
+    let stream = stream.map(|msg| match msg {
+        Ok(Message::Binary(bytes)) => Ok(IncomingMessage::Binary(bytes)),
+        Ok(Message::Text(_)) => Ok(IncomingMessage::Text),
+        Ok(Message::Ping(_)) => Ok(IncomingMessage::Ping),
+        Ok(Message::Pong(_)) => Ok(IncomingMessage::Pong),
+        Ok(Message::Close(_)) => Ok(IncomingMessage::Close),
+        Err(err) => Err(PerspectiveWSError::from(err)),
+    });
 
-            tracing::info!("{addr} Disconnected.");
-            session.close().await;
+    let sink = sink.with(|msg| {
+        future::ok::<_, PerspectiveWSError>(match msg {
+            OutgoingMessage::Binary(bytes) => Message::Binary(bytes),
+            OutgoingMessage::Ping => Message::Ping(vec![]),
         })
-    }
+    });
 
-    get(websocket_handler_internal)
+    serve_websocket_messages(server, stream, Box::pin(sink), config, principal).await
+}
+
+/// The HTTP status for a failed request. Errors the engine raises for a
+/// request are the caller's fault (e.g. an unknown column or malformed data),
+/// while any other failure to complete it is the server's.
+#[cfg(any(feature = "rest", feature = "sse"))]
+pub(crate) fn client_error_status(err: &ClientError) -> StatusCode {
+    match err {
+        ClientError::Internal(_)
+        | ClientError::JsonError(_)
+        | ClientError::IngestRejected(_)
+        | ClientError::SqlError(_)
+        | ClientError::BadTableOptions
+        | ClientError::Utf8(_) => StatusCode::BAD_REQUEST,
+        ClientError::ViewNotFound => StatusCode::NOT_FOUND,
+        ClientError::NotImplemented(_) => StatusCode::NOT_IMPLEMENTED,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
 }
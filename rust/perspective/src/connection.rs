@@ -0,0 +1,491 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! A transport-neutral message loop for serving a [`Server`] to a remote
+//! [`perspective_client::Client`], over any full-duplex stream of binary
+//! frames.
+//!
+//! The framework-specific handlers in this crate (e.g.
+//! [`crate::axum::websocket_handler`]) are thin adapters over
+//! [`serve_connection`], and it can be used in the same way to serve a
+//! [`Server`] from other frameworks. The WebSocket handlers additionally
+//! share a [`WebsocketConfig`] and the keepalive loop which enforces it.
+
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+use std::future::Future;
+use std::ops::Deref;
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+use std::sync::Arc;
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+use std::time::Duration;
+
+#[cfg(feature = "axum-ws")]
+use axum::http::header::{ORIGIN, SEC_WEBSOCKET_PROTOCOL};
+#[cfg(feature = "axum-ws")]
+use axum::http::{HeaderMap, StatusCode};
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures::future::{select, Either};
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+use futures::future::{self, BoxFuture};
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+use futures::{stream, FutureExt};
+use futures::{Sink, SinkExt, Stream, StreamExt};
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+use tokio::time::{interval_at, sleep_until, Instant, Interval, MissedTickBehavior};
+#[cfg(all(feature = "tungstenite-ws", not(feature = "axum-ws")))]
+use tokio_tungstenite::tungstenite::http::header::{ORIGIN, SEC_WEBSOCKET_PROTOCOL};
+#[cfg(all(feature = "tungstenite-ws", not(feature = "axum-ws")))]
+use tokio_tungstenite::tungstenite::http::{HeaderMap, StatusCode};
+
+use crate::client::Session;
+use crate::server::{LocalSession, Server, SessionHandler};
+
+/// A local error synonym for this module only.
+type PerspectiveWSError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A new-type wrapper for an [`UnboundedSender`], whic bypasses the orphan
+/// instance rule allowing us to write a [`SessionHandler`] impl for this
+/// struct.
+#[derive(Clone)]
+struct PerspectiveWSConnection(UnboundedSender<Vec<u8>>);
+
+/// The [`SessionHandler`] implementation provides a method for a
+/// [`Session`] to send messages to this connection, which may (or may
+/// not) be solicited (e.g. within the async call stack of
+/// [`Session::handle_request`]).
+impl SessionHandler for PerspectiveWSConnection {
+    async fn send_response<'a>(&'a mut self, resp: &'a [u8]) -> Result<(), PerspectiveWSError> {
+        Ok(self.0.send(resp.to_vec()).await?)
+    }
+}
+
+/// Closes the connection's [`LocalSession`] when dropped, so that it is
+/// closed even if the future serving the connection is cancelled (e.g. by a
+/// client abort or server shutdown) rather than run to completion.
+struct SessionGuard(Option<LocalSession>);
+
+impl SessionGuard {
+    async fn close(mut self) {
+        if let Some(session) = self.0.take() {
+            session.close().await;
+        }
+    }
+}
+
+impl Deref for SessionGuard {
+    type Target = LocalSession;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref().expect("Session closed")
+    }
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        if let Some(session) = self.0.take() {
+            match tokio::runtime::Handle::try_current() {
+                Ok(handle) => {
+                    handle.spawn(session.close());
+                },
+                Err(_) => futures::executor::block_on(session.close()),
+            }
+        }
+    }
+}
+
+/// The inner message loop handles the full-duplex stream of messages
+/// between the [`perspective_client::Client`] and [`Session`]. When this
+/// funciton returns, messages are no longer processed.
+async fn process_message_loop<R, W>(
+    stream: &mut R,
+    sink: &mut W,
+    receiver: &mut UnboundedReceiver<Vec<u8>>,
+    session: &LocalSession,
+) -> Result<(), PerspectiveWSError>
+where
+    R: Stream<Item = Result<Vec<u8>, PerspectiveWSError>> + Unpin,
+    W: Sink<Vec<u8>> + Unpin,
+    W::Error: Into<PerspectiveWSError>,
+{
+    use Either::*;
+
+    loop {
+        match select(stream.next(), receiver.next()).await {
+            Left((Some(Ok(bytes)), _)) => {
+                session.handle_request(&bytes).await?;
+                session.poll().await?
+            },
+            Right((Some(bytes), _)) => sink.send(bytes).await.map_err(Into::into)?,
+            Left((Some(Err(err)), _)) => return Err(err),
+            Left((None, _)) | Right((None, _)) => return Ok(()),
+        }
+    }
+}
+
+/// Serve a single connection from a [`perspective_client::Client`] to
+/// `server`, returning when the connection closes.
+///
+/// Incoming request frames are read from `stream`, which should end when the
+/// connection closes and should skip transport control frames (e.g.
+/// WebSocket pings). Response frames are written to `sink`. A new
+/// [`Session`] is created for the connection, and closed when this function
+/// returns (whether or not the connection errored) or its future is dropped.
+pub async fn serve_connection<R, W>(
+    server: &Server,
+    stream: R,
+    sink: W,
+) -> Result<(), PerspectiveWSError>
+where
+    R: Stream<Item = Result<Vec<u8>, PerspectiveWSError>> + Unpin,
+    W: Sink<Vec<u8>> + Unpin,
+    W::Error: Into<PerspectiveWSError>,
+{
+    serve_connection_with_principal(server, None, stream, sink).await
+}
+
+/// As [`serve_connection`], additionally attaching an authenticated
+/// `principal` to the connection's [`Session`] via
+/// [`LocalSession::set_principal`].
+pub async fn serve_connection_with_principal<R, W>(
+    server: &Server,
+    principal: Option<String>,
+    mut stream: R,
+    mut sink: W,
+) -> Result<(), PerspectiveWSError>
+where
+    R: Stream<Item = Result<Vec<u8>, PerspectiveWSError>> + Unpin,
+    W: Sink<Vec<u8>> + Unpin,
+    W::Error: Into<PerspectiveWSError>,
+{
+    let (send, mut receiver) = unbounded::<Vec<u8>>();
+    let session = server.new_session(PerspectiveWSConnection(send)).await;
+    session.set_principal(principal);
+    let session = SessionGuard(Some(session));
+    let result = process_message_loop(&mut stream, &mut sink, &mut receiver, &session).await;
+    session.close().await;
+    result
+}
+
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+type OnConnectCallback = Arc<
+    dyn Fn(HeaderMap) -> BoxFuture<'static, Result<Option<String>, PerspectiveWSError>>
+        + Send
+        + Sync,
+>;
+
+/// Options for a WebSocket endpoint, e.g.
+/// `perspective::axum::websocket_handler_with_config` or
+/// `perspective::tower::WebsocketService::with_config`. By default, no
+/// limits or checks are applied beyond those of the underlying WebSocket
+/// implementation.
+///
+/// ```rust,ignore
+/// let config = WebsocketConfig::default()
+///     .ping_interval(Duration::from_secs(30))
+///     .idle_timeout(Duration::from_secs(600))
+///     .allowed_origins(["https://example.com"]);
+///
+/// let app = Router::new().route("/ws", websocket_handler_with_config(config));
+/// ```
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+#[derive(Clone, Default)]
+pub struct WebsocketConfig {
+    ping_interval: Option<Duration>,
+    pong_timeout: Option<Duration>,
+    idle_timeout: Option<Duration>,
+    pub(crate) max_frame_size: Option<usize>,
+    pub(crate) max_message_size: Option<usize>,
+    allowed_origins: Option<Vec<String>>,
+    protocols: Vec<String>,
+    on_connect: Option<OnConnectCallback>,
+}
+
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+impl std::fmt::Debug for WebsocketConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebsocketConfig")
+            .field("ping_interval", &self.ping_interval)
+            .field("pong_timeout", &self.pong_timeout)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("max_frame_size", &self.max_frame_size)
+            .field("max_message_size", &self.max_message_size)
+            .field("allowed_origins", &self.allowed_origins)
+            .field("protocols", &self.protocols)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+impl WebsocketConfig {
+    /// Send a ping to the client at this interval, disconnecting it if a
+    /// pong is not received within the `pong_timeout`.
+    pub fn ping_interval(mut self, interval: Duration) -> Self {
+        self.ping_interval = Some(interval);
+        self
+    }
+
+    /// How long to wait for a pong before disconnecting. Defaults to the
+    /// `ping_interval`.
+    pub fn pong_timeout(mut self, timeout: Duration) -> Self {
+        self.pong_timeout = Some(timeout);
+        self
+    }
+
+    /// Disconnect clients which send no requests for this duration.
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// The maximum size of a single incoming WebSocket frame, in bytes.
+    pub fn max_frame_size(mut self, size: usize) -> Self {
+        self.max_frame_size = Some(size);
+        self
+    }
+
+    /// The maximum size of a single incoming (possibly fragmented) message,
+    /// in bytes.
+    pub fn max_message_size(mut self, size: usize) -> Self {
+        self.max_message_size = Some(size);
+        self
+    }
+
+    /// Reject connections whose `Origin` header is not one of `origins`
+    /// with `403 Forbidden`.
+    pub fn allowed_origins<I, S>(mut self, origins: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_origins = Some(origins.into_iter().map(|x| x.into()).collect());
+        self
+    }
+
+    /// Require clients to request one of `protocols` via
+    /// `Sec-WebSocket-Protocol`, rejecting them with `400 Bad Request`
+    /// otherwise.
+    pub fn protocols<I, S>(mut self, protocols: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.protocols = protocols.into_iter().map(|x| x.into()).collect();
+        self
+    }
+
+    /// A hook invoked with the request headers of every new connection, e.g.
+    /// to authenticate it. Returning `Err` rejects the connection with `401
+    /// Unauthorized`, while `Ok(Some(principal))` attaches `principal` to the
+    /// connection's [`crate::server::LocalSession`] (see
+    /// [`crate::server::Server::sessions`]).
+    pub fn on_connect<F, T>(mut self, on_connect: F) -> Self
+    where
+        F: Fn(HeaderMap) -> T + Send + Sync + 'static,
+        T: Future<Output = Result<Option<String>, PerspectiveWSError>> + Send + 'static,
+    {
+        self.on_connect = Some(Arc::new(move |headers| on_connect(headers).boxed()));
+        self
+    }
+
+    /// Check the upgrade request `headers` against this config, returning
+    /// the protocol to accept (if any) and the connection's principal, or
+    /// the rejection status for invalid requests.
+    pub(crate) async fn accept(
+        &self,
+        headers: &HeaderMap,
+    ) -> Result<(Option<String>, Option<String>), StatusCode> {
+        if let Some(origins) = &self.allowed_origins {
+            let origin = headers.get(ORIGIN).and_then(|x| x.to_str().ok());
+            if !origin.is_some_and(|x| origins.iter().any(|y| y == x)) {
+                return Err(StatusCode::FORBIDDEN);
+            }
+        }
+
+        let protocol = if self.protocols.is_empty() {
+            None
+        } else {
+            let protocol = headers
+                .get_all(SEC_WEBSOCKET_PROTOCOL)
+                .iter()
+                .filter_map(|x| x.to_str().ok())
+                .flat_map(|x| x.split(','))
+                .map(str::trim)
+                .find(|x| self.protocols.iter().any(|y| y == x));
+
+            match protocol {
+                Some(protocol) => Some(protocol.to_owned()),
+                None => return Err(StatusCode::BAD_REQUEST),
+            }
+        };
+
+        let principal = match &self.on_connect {
+            None => None,
+            Some(on_connect) => match on_connect(headers.clone()).await {
+                Ok(principal) => principal,
+                Err(msg) => {
+                    tracing::warn!("Rejected, {msg}");
+                    return Err(StatusCode::UNAUTHORIZED);
+                },
+            },
+        };
+
+        Ok((protocol, principal))
+    }
+}
+
+/// Resolves at `deadline`, or never if there isn't one.
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+async fn sleep_until_opt(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => sleep_until(deadline).await,
+        None => future::pending().await,
+    }
+}
+
+/// Resolves at the next tick of `interval`, or never if there isn't one.
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+async fn tick_opt(interval: &mut Option<Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        },
+        None => future::pending().await,
+    }
+}
+
+/// A WebSocket message received by [`serve_websocket_messages`], translated
+/// from the message type of the underlying WebSocket implementation.
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+pub(crate) enum IncomingMessage {
+    Binary(Vec<u8>),
+    Text,
+    Ping,
+    Pong,
+    Close,
+}
+
+/// A WebSocket message sent by [`serve_websocket_messages`], to be
+/// translated to the message type of the underlying WebSocket
+/// implementation.
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+pub(crate) enum OutgoingMessage {
+    Binary(Vec<u8>),
+    Ping,
+}
+
+/// The read half of a WebSocket, which yields incoming binary frames and
+/// enforces the keepalive and idle timeouts of a [`WebsocketConfig`]. A
+/// timeout is yielded as an error, ending the connection.
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+struct SocketReader<S> {
+    stream: S,
+    outgoing: UnboundedSender<OutgoingMessage>,
+    ping_interval: Option<Interval>,
+    pong_timeout: Option<Duration>,
+    pong_deadline: Option<Instant>,
+    idle_timeout: Option<Duration>,
+    last_request: Instant,
+}
+
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+impl<S> SocketReader<S>
+where
+    S: Stream<Item = Result<IncomingMessage, PerspectiveWSError>> + Unpin,
+{
+    fn new(
+        stream: S,
+        outgoing: UnboundedSender<OutgoingMessage>,
+        config: &WebsocketConfig,
+    ) -> Self {
+        let ping_interval = config.ping_interval.map(|period| {
+            let mut interval = interval_at(Instant::now() + period, period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            interval
+        });
+
+        Self {
+            stream,
+            outgoing,
+            ping_interval,
+            pong_timeout: config.pong_timeout.or(config.ping_interval),
+            pong_deadline: None,
+            idle_timeout: config.idle_timeout,
+            last_request: Instant::now(),
+        }
+    }
+
+    async fn next(&mut self) -> Option<Result<Vec<u8>, PerspectiveWSError>> {
+        loop {
+            let idle_deadline = self.idle_timeout.map(|x| self.last_request + x);
+            tokio::select! {
+                msg = self.stream.next() => match msg {
+                    Some(Ok(IncomingMessage::Binary(bytes))) => {
+                        self.last_request = Instant::now();
+                        return Some(Ok(bytes));
+                    },
+                    Some(Ok(IncomingMessage::Pong)) => self.pong_deadline = None,
+                    Some(Ok(IncomingMessage::Ping)) => (),
+                    Some(Ok(IncomingMessage::Text)) => return Some(Err("Unexpected message type".into())),
+                    Some(Err(err)) => return Some(Err(err)),
+                    None | Some(Ok(IncomingMessage::Close)) => return None,
+                },
+                _ = tick_opt(&mut self.ping_interval) => {
+                    if self.pong_deadline.is_none() {
+                        self.pong_deadline = self.pong_timeout.map(|x| Instant::now() + x);
+                        if self.outgoing.unbounded_send(OutgoingMessage::Ping).is_err() {
+                            return None;
+                        }
+                    }
+                },
+                _ = sleep_until_opt(self.pong_deadline) => return Some(Err("Pong timeout".into())),
+                _ = sleep_until_opt(idle_deadline) => return Some(Err("Idle timeout".into())),
+            }
+        }
+    }
+}
+
+/// Serve a WebSocket connection to `server`, given its incoming and
+/// outgoing messages, enforcing the keepalive and idle timeouts of `config`
+/// and attaching `principal` to the connection's [`LocalSession`]. The
+/// frame and message size limits of `config` must already have been applied
+/// to the socket.
+///
+/// Outgoing messages (including pings) are written by a single writer,
+/// which finishes once the connection's session has been closed.
+#[cfg(any(feature = "axum-ws", feature = "tungstenite-ws"))]
+pub(crate) async fn serve_websocket_messages<R, W>(
+    server: &Server,
+    stream: R,
+    sink: W,
+    config: &WebsocketConfig,
+    principal: Option<String>,
+) -> Result<(), PerspectiveWSError>
+where
+    R: Stream<Item = Result<IncomingMessage, PerspectiveWSError>> + Unpin,
+    W: Sink<OutgoingMessage> + Unpin,
+    W::Error: Into<PerspectiveWSError>,
+{
+    let (outgoing, receiver) = unbounded::<OutgoingMessage>();
+    let reader = SocketReader::new(stream, outgoing.clone(), config);
+    let stream = stream::unfold(reader, |mut reader| async move {
+        reader.next().await.map(|msg| (msg, reader))
+    });
+
+    let writer = receiver.map(Ok).forward(sink);
+    let outgoing =
+        outgoing.with(|bytes| future::ok::<_, PerspectiveWSError>(OutgoingMessage::Binary(bytes)));
+    let session =
+        serve_connection_with_principal(server, principal, Box::pin(stream), Box::pin(outgoing));
+
+    let (result, written) = future::join(session, writer).await;
+    result.and(written.map_err(Into::into))
+}
@@ -0,0 +1,387 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Host a [`Table`] backed by a file on disk, which follows the file as it
+//! changes. Appends to line-oriented files (CSV and NDJSON) are loaded
+//! incrementally with [`Table::update`], while any other change reloads the
+//! whole file with [`Table::replace`].
+//!
+//! ```rust,ignore
+//! let client = server.new_local_client();
+//! let file = FileTable::open(&client, "logs/fills.csv", FileTableOptions::default()).await?;
+//! // The hosted table "fills" follows the file until `file` is dropped.
+//! ```
+//!
+//! Appends are read with [`source::tail_file`]'s reader, and loaded in
+//! batches with [`TableSourceExt::ingest`]. Rewrites are detected with a
+//! [`notify`] watcher where the platform supports one, and by polling the
+//! file's length and modification time every
+//! [`FileTableOptions::poll_interval`] regardless, which also covers files
+//! which are replaced by rename (which a watcher on the original file misses).
+
+use std::future::Future;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, BufReader};
+use tokio::sync::mpsc::{UnboundedReceiver, unbounded_channel};
+use tokio::task::JoinHandle;
+use tokio::time::{Interval, MissedTickBehavior};
+
+use crate::client::{Client, Table, TableInitOptions, UpdateData, UpdateOptions};
+use crate::source::{self, LineFormat, OnError, SourcePolicy, TableSourceExt, TailOptions};
+
+/// A local error synonym for this module only.
+type FileTableError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The number of leading bytes compared to tell an append from a rewrite.
+const HEAD_LEN: usize = 4096;
+
+/// The format of a [`FileTable`]'s file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileFormat {
+    Csv,
+    Ndjson,
+    Arrow,
+    Parquet,
+}
+
+impl FileFormat {
+    /// Infer the format from `path`'s extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "csv" => Some(FileFormat::Csv),
+            "ndjson" | "jsonl" => Some(FileFormat::Ndjson),
+            "arrow" | "feather" | "ipc" => Some(FileFormat::Arrow),
+            "parquet" => Some(FileFormat::Parquet),
+            _ => None,
+        }
+    }
+
+    /// The format of appended lines, for formats whose lines can be loaded on
+    /// their own.
+    fn line_format(&self) -> Option<LineFormat> {
+        match self {
+            FileFormat::Csv => Some(LineFormat::Csv),
+            FileFormat::Ndjson => Some(LineFormat::Ndjson),
+            _ => None,
+        }
+    }
+
+    fn to_update_data(self, bytes: Vec<u8>) -> Result<UpdateData, FileTableError> {
+        Ok(match self {
+            FileFormat::Csv => UpdateData::Csv(String::from_utf8(bytes)?),
+            FileFormat::Ndjson => UpdateData::Ndjson(String::from_utf8(bytes)?),
+            FileFormat::Arrow => UpdateData::Arrow(bytes.into()),
+            FileFormat::Parquet => UpdateData::Parquet(bytes.into()),
+        })
+    }
+}
+
+/// Options for [`FileTable::open`].
+#[derive(Clone, Debug)]
+pub struct FileTableOptions {
+    /// Inferred from the file's extension if [`None`].
+    pub format: Option<FileFormat>,
+
+    /// The `index`, `limit` and format options for the [`Table`]. The name
+    /// defaults to the file's stem.
+    pub table: TableInitOptions,
+
+    /// How often to check the file, whether or not a watcher is running.
+    pub poll_interval: Duration,
+
+    /// Use a [`notify`] watcher in addition to polling.
+    pub watch: bool,
+}
+
+impl Default for FileTableOptions {
+    fn default() -> Self {
+        Self {
+            format: None,
+            table: TableInitOptions::default(),
+            poll_interval: Duration::from_secs(1),
+            watch: true,
+        }
+    }
+}
+
+/// A [`Table`] which follows a file on disk. The file stops being followed
+/// when this is dropped, but the [`Table`] itself stays hosted.
+pub struct FileTable {
+    table: Table,
+    task: JoinHandle<()>,
+}
+
+impl FileTable {
+    /// Load the file at `path` into a new [`Table`], and follow it.
+    pub async fn open(
+        client: &Client,
+        path: impl AsRef<Path>,
+        options: FileTableOptions,
+    ) -> Result<Self, FileTableError> {
+        let path = path.as_ref().to_path_buf();
+        let format = options
+            .format
+            .or_else(|| FileFormat::from_path(&path))
+            .ok_or_else(|| format!("Unknown file format {}", path.display()))?;
+
+        let mut table_options = options.table.clone();
+        if table_options.name.is_none() {
+            table_options.name = path.file_stem().map(|x| x.to_string_lossy().into_owned());
+        }
+
+        let update_options = UpdateOptions {
+            csv: table_options.csv.clone(),
+            parquet: table_options.parquet.clone(),
+            ..UpdateOptions::default()
+        };
+
+        let mut state = FileState::new(path, format, update_options, options.poll_interval);
+        let (data, reader) = state.load().await?;
+        let table = client.table(data.into(), table_options).await?;
+        let task = tokio::spawn(state.follow(table.clone(), reader, options.watch));
+        Ok(FileTable { table, task })
+    }
+
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+}
+
+impl Drop for FileTable {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// A [`notify`] watcher's events, if it is running.
+type FileWatcher = Option<(RecommendedWatcher, UnboundedReceiver<()>)>;
+
+/// What has been loaded from the file so far.
+struct FileState {
+    path: PathBuf,
+    format: FileFormat,
+    update_options: UpdateOptions,
+    poll_interval: Duration,
+
+    /// The bytes loaded, which for line formats ends at the last complete
+    /// line.
+    offset: u64,
+
+    /// The length and modification time when last checked.
+    seen: (u64, Option<SystemTime>),
+
+    /// The first [`HEAD_LEN`] bytes when last loaded.
+    head: Vec<u8>,
+
+    /// The CSV header line, prepended to appended lines.
+    header: Option<String>,
+}
+
+impl FileState {
+    fn new(
+        path: PathBuf,
+        format: FileFormat,
+        update_options: UpdateOptions,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            path,
+            format,
+            update_options,
+            poll_interval,
+            offset: 0,
+            seen: (0, None),
+            head: vec![],
+            header: None,
+        }
+    }
+
+    /// Read the whole file and reset the state to match it. For line formats,
+    /// also returns a reader positioned after the last complete line, from
+    /// which appends are tailed.
+    async fn load(&mut self) -> Result<(UpdateData, Option<BufReader<File>>), FileTableError> {
+        let mut file = File::open(&self.path).await?;
+        let meta = file.metadata().await?;
+        let mut bytes = vec![];
+        file.read_to_end(&mut bytes).await?;
+        if self.format.line_format().is_some() {
+            let end = bytes.iter().rposition(|x| *x == b'\n').map_or(0, |x| x + 1);
+            bytes.truncate(end);
+        }
+
+        // A rewrite may be caught half-written, so leave the state as-is and
+        // retry on the next check.
+        if bytes.is_empty() {
+            return Err(format!("No complete data in {}", self.path.display()).into());
+        }
+
+        self.offset = bytes.len() as u64;
+        self.seen = (meta.len(), meta.modified().ok());
+        self.head = bytes[..bytes.len().min(HEAD_LEN)].to_vec();
+        self.header = match bytes.iter().position(|x| *x == b'\n') {
+            Some(end) if self.format == FileFormat::Csv => {
+                Some(String::from_utf8_lossy(&bytes[..end]).trim_end().to_owned())
+            },
+            _ => None,
+        };
+
+        let reader = if self.format.line_format().is_some() {
+            file.seek(SeekFrom::Start(self.offset)).await?;
+            Some(BufReader::new(file))
+        } else {
+            None
+        };
+
+        Ok((self.format.to_update_data(bytes)?, reader))
+    }
+
+    /// Whether the file was rewritten since it was last loaded, rather than
+    /// appended to (appends to line formats are left to [`FileState::tail`]).
+    async fn is_rewritten(&mut self) -> Result<bool, FileTableError> {
+        let meta = tokio::fs::metadata(&self.path).await?;
+        let seen = (meta.len(), meta.modified().ok());
+        if seen == self.seen {
+            return Ok(false);
+        }
+
+        self.seen = seen;
+        if self.format.line_format().is_none() {
+            return Ok(true);
+        }
+
+        let mut head = vec![];
+        File::open(&self.path)
+            .await?
+            .take(HEAD_LEN as u64)
+            .read_to_end(&mut head)
+            .await?;
+
+        Ok(meta.len() < self.offset || !head.starts_with(&self.head))
+    }
+
+    /// Load the lines appended after `reader`'s position into `table`. Never
+    /// completes, so that it runs until the file is rewritten.
+    fn tail(
+        &self,
+        table: &Table,
+        reader: Option<BufReader<File>>,
+    ) -> impl Future<Output = ()> + Send + 'static {
+        let table = table.clone();
+        let path = self.path.clone();
+        let lines = reader
+            .zip(self.format.line_format())
+            .map(|(reader, format)| {
+                let options = TailOptions {
+                    format,
+                    from_start: false,
+                    poll_interval: self.poll_interval,
+                };
+
+                source::tail_reader(reader, self.header.clone(), options)
+            });
+
+        let policy = SourcePolicy {
+            on_error: OnError::Skip,
+            update_options: self.update_options.clone(),
+            ..SourcePolicy::default()
+        };
+
+        async move {
+            if let Some(lines) = lines {
+                if let Err(err) = table.ingest(lines, policy).await {
+                    tracing::warn!("Failed to tail {}: {}", path.display(), err);
+                }
+            }
+
+            futures::future::pending().await
+        }
+    }
+
+    async fn follow(mut self, table: Table, mut reader: Option<BufReader<File>>, watch: bool) {
+        let mut interval = tokio::time::interval(self.poll_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut watcher = if watch { self.watch() } else { None };
+        loop {
+            let mut tail = Box::pin(self.tail(&table, reader.take()));
+            loop {
+                tokio::select! {
+                    () = &mut tail => (),
+                    () = Self::changed(&mut watcher, &mut interval) => (),
+                }
+
+                match self.is_rewritten().await {
+                    Ok(true) => break,
+                    Ok(false) => (),
+                    Err(err) => {
+                        tracing::warn!("Failed to check {}: {}", self.path.display(), err)
+                    },
+                }
+            }
+
+            drop(tail);
+            tracing::debug!("Reloading {}", self.path.display());
+            let result = match self.load().await {
+                Ok((data, next)) => {
+                    reader = next;
+                    table.replace(data).await.map_err(FileTableError::from)
+                },
+                Err(err) => Err(err),
+            };
+
+            if let Err(err) = result {
+                tracing::warn!("Failed to reload {}: {}", self.path.display(), err);
+            }
+        }
+    }
+
+    /// Wait for the next watcher event or poll, whichever is first.
+    async fn changed(watcher: &mut FileWatcher, interval: &mut Interval) {
+        match watcher {
+            Some((_, rx)) => {
+                tokio::select! {
+                    Some(()) = rx.recv() => while rx.try_recv().is_ok() {},
+                    _ = interval.tick() => (),
+                }
+            },
+            None => {
+                interval.tick().await;
+            },
+        }
+    }
+
+    /// A [`notify`] watcher for the file, or [`None`] to only poll.
+    fn watch(&self) -> FileWatcher {
+        let (tx, rx) = unbounded_channel();
+        let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if event.is_ok() {
+                let _ = tx.send(());
+            }
+        });
+
+        match watcher.and_then(|mut watcher| {
+            watcher.watch(&self.path, RecursiveMode::NonRecursive)?;
+            Ok(watcher)
+        }) {
+            Ok(watcher) => Some((watcher, rx)),
+            Err(err) => {
+                tracing::warn!("Polling {}, watch failed: {}", self.path.display(), err);
+                None
+            },
+        }
+    }
+}
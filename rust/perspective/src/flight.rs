@@ -0,0 +1,374 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! An [Arrow Flight](https://arrow.apache.org/docs/format/Flight.html)
+//! endpoint for the tables hosted by a [`Server`], so they can be read and
+//! appended to by any Arrow Flight client (e.g. `pyarrow.flight`, DuckDB)
+//! without a Perspective client.
+//!
+//! - `ListFlights` and `GetFlightInfo` describe the hosted tables, each
+//!   identified by a single-element descriptor path of its name.
+//! - `GetSchema` maps a table's [`ColumnType`]s to Arrow types, matching the
+//!   Arrow emitted by [`crate::client::View::to_arrow`].
+//! - `DoGet` takes a [`FlightTicket`] (JSON encoded) and streams the record
+//!   batches of a view of the table.
+//! - `DoPut` appends the record batches it receives to the table named by the
+//!   first message's descriptor, via [`crate::client::Table::update`].
+//!
+//! ```rust,ignore
+//! tonic::transport::Server::builder()
+//!     .add_service(PerspectiveFlightService::new(server).into_service())
+//!     .serve("127.0.0.1:50051".parse()?)
+//!     .await?;
+//! ```
+
+use std::io::Cursor;
+use std::sync::Arc;
+
+use arrow_array::RecordBatch;
+use arrow_flight::decode::FlightRecordBatchStream;
+use arrow_flight::encode::FlightDataEncoderBuilder;
+use arrow_flight::error::FlightError;
+use arrow_flight::flight_service_server::{FlightService, FlightServiceServer};
+use arrow_flight::{
+    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightEndpoint, FlightInfo,
+    HandshakeRequest, HandshakeResponse, PollInfo, PutResult, SchemaAsIpc, SchemaResult, Ticket,
+};
+use arrow_ipc::reader::StreamReader;
+use arrow_ipc::writer::{IpcWriteOptions, StreamWriter};
+use arrow_schema::{ArrowError, DataType, Field, Schema, TimeUnit};
+use futures::stream::{self, BoxStream};
+use futures::{future, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::client::config::ViewConfigUpdate;
+use crate::client::{
+    Client, ClientError, ColumnType, Table, UpdateData, UpdateOptions, ViewWindow,
+};
+use crate::server::Server;
+
+/// The (JSON encoded) contents of a `DoGet` [`Ticket`]: a table name and an
+/// optional view config, e.g. `{"table": "trades", "config": {"group_by":
+/// ["sym"]}}`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct FlightTicket {
+    pub table: String,
+
+    #[serde(default)]
+    pub config: Option<ViewConfigUpdate>,
+}
+
+impl FlightTicket {
+    pub fn to_ticket(&self) -> Result<Ticket, serde_json::Error> {
+        Ok(Ticket::new(serde_json::to_vec(self)?))
+    }
+}
+
+/// The Arrow type of a [`ColumnType`], as emitted by
+/// [`crate::client::View::to_arrow`].
+fn data_type(column_type: ColumnType) -> DataType {
+    match column_type {
+        ColumnType::String => {
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+        },
+        ColumnType::Date => DataType::Date32,
+        ColumnType::Datetime => DataType::Timestamp(TimeUnit::Millisecond, None),
+        ColumnType::Integer => DataType::Int32,
+        ColumnType::Float => DataType::Float64,
+        ColumnType::Boolean => DataType::Boolean,
+    }
+}
+
+fn internal<E: ToString>(err: E) -> Status {
+    Status::internal(err.to_string())
+}
+
+/// Errors the engine raises for a request are the caller's fault (e.g. an
+/// unknown column or malformed data), while any other failure to complete it
+/// is the server's.
+fn client_error(err: ClientError) -> Status {
+    match err {
+        ClientError::Internal(_)
+        | ClientError::JsonError(_)
+        | ClientError::IngestRejected(_)
+        | ClientError::SqlError(_)
+        | ClientError::BadTableOptions
+        | ClientError::Utf8(_) => Status::invalid_argument(err.to_string()),
+        ClientError::ViewNotFound => Status::not_found(err.to_string()),
+        ClientError::NotImplemented(_) => Status::unimplemented(err.to_string()),
+        _ => Status::internal(err.to_string()),
+    }
+}
+
+/// The table name of a [`FlightDescriptor`] path.
+fn descriptor_table(descriptor: Option<&FlightDescriptor>) -> Result<String, Status> {
+    descriptor
+        .and_then(|x| x.path.first().cloned())
+        .ok_or_else(|| Status::invalid_argument("Expected a table name descriptor path"))
+}
+
+async fn open_table(client: &Client, name: &str) -> Result<Table, Status> {
+    let names = client
+        .get_hosted_table_names()
+        .await
+        .map_err(client_error)?;
+    if names.iter().any(|x| x == name) {
+        client
+            .open_table(name.to_owned())
+            .await
+            .map_err(client_error)
+    } else {
+        Err(Status::not_found(format!("Unknown table {}", name)))
+    }
+}
+
+async fn table_schema(table: &Table) -> Result<Schema, Status> {
+    let schema = table.schema().await.map_err(client_error)?;
+    let fields = table
+        .columns()
+        .await
+        .map_err(client_error)?
+        .into_iter()
+        .filter_map(|name| {
+            let column_type = *schema.get(&name)?;
+            Some(Field::new(name, data_type(column_type), true))
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Schema::new(fields))
+}
+
+async fn flight_info(client: &Client, name: String) -> Result<FlightInfo, Status> {
+    let table = open_table(client, &name).await?;
+    let schema = table_schema(&table).await?;
+    let size = table.size().await.map_err(client_error)?;
+    let ticket = FlightTicket {
+        table: name.clone(),
+        config: None,
+    };
+
+    let endpoint = FlightEndpoint::new().with_ticket(ticket.to_ticket().map_err(internal)?);
+    Ok(FlightInfo::new()
+        .try_with_schema(&schema)
+        .map_err(internal)?
+        .with_descriptor(FlightDescriptor::new_path(vec![name]))
+        .with_endpoint(endpoint)
+        .with_total_records(size as i64))
+}
+
+/// Query the record batches of a view of a table, deleting the view after.
+async fn query(
+    client: &Client,
+    ticket: FlightTicket,
+) -> Result<(Arc<Schema>, Vec<RecordBatch>), Status> {
+    let table = open_table(client, &ticket.table).await?;
+    let view = table.view(ticket.config).await.map_err(client_error)?;
+    let arrow = view.to_arrow(ViewWindow::default()).await;
+    view.delete().await.map_err(client_error)?;
+    let reader =
+        StreamReader::try_new(Cursor::new(arrow.map_err(client_error)?), None).map_err(internal)?;
+
+    let schema = reader.schema();
+    let batches = reader.collect::<Result<Vec<_>, _>>().map_err(internal)?;
+    Ok((schema, batches))
+}
+
+/// Encode record batches as an Arrow IPC stream, for [`UpdateData::Arrow`].
+fn to_ipc(schema: &Schema, batches: &[RecordBatch]) -> Result<Vec<u8>, ArrowError> {
+    let mut writer = StreamWriter::try_new(vec![], schema)?;
+    for batch in batches {
+        writer.write(batch)?;
+    }
+
+    writer.finish()?;
+    writer.into_inner()
+}
+
+/// An Arrow Flight service for the tables hosted by a [`Server`]. See the
+/// module documentation for the supported methods. Each call is made via a
+/// temporary [`crate::server::LocalClient`].
+#[derive(Clone, Debug)]
+pub struct PerspectiveFlightService {
+    server: Server,
+}
+
+impl PerspectiveFlightService {
+    pub fn new(server: Server) -> Self {
+        Self { server }
+    }
+
+    /// Wrap this service in a [`FlightServiceServer`], for use with
+    /// [`tonic::transport::Server::add_service`].
+    pub fn into_service(self) -> FlightServiceServer<Self> {
+        FlightServiceServer::new(self)
+    }
+}
+
+#[tonic::async_trait]
+impl FlightService for PerspectiveFlightService {
+    type DoActionStream = BoxStream<'static, Result<arrow_flight::Result, Status>>;
+    type DoExchangeStream = BoxStream<'static, Result<FlightData, Status>>;
+    type DoGetStream = BoxStream<'static, Result<FlightData, Status>>;
+    type DoPutStream = BoxStream<'static, Result<PutResult, Status>>;
+    type HandshakeStream = BoxStream<'static, Result<HandshakeResponse, Status>>;
+    type ListActionsStream = BoxStream<'static, Result<ActionType, Status>>;
+    type ListFlightsStream = BoxStream<'static, Result<FlightInfo, Status>>;
+
+    async fn handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        Err(Status::unimplemented("Handshake is not supported"))
+    }
+
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        let client = self.server.new_local_client();
+        let result = async {
+            let mut infos = vec![];
+            for name in client
+                .get_hosted_table_names()
+                .await
+                .map_err(client_error)?
+            {
+                infos.push(flight_info(&client, name).await);
+            }
+
+            Ok::<_, Status>(infos)
+        }
+        .await;
+
+        client.close().await;
+        Ok(Response::new(stream::iter(result?).boxed()))
+    }
+
+    async fn get_flight_info(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let name = descriptor_table(Some(request.get_ref()))?;
+        let client = self.server.new_local_client();
+        let result = flight_info(&client, name).await;
+        client.close().await;
+        Ok(Response::new(result?))
+    }
+
+    async fn poll_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<PollInfo>, Status> {
+        Err(Status::unimplemented("PollFlightInfo is not supported"))
+    }
+
+    async fn get_schema(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        let name = descriptor_table(Some(request.get_ref()))?;
+        let client = self.server.new_local_client();
+        let result = async { table_schema(&open_table(&client, &name).await?).await }.await;
+        client.close().await;
+        let options = IpcWriteOptions::default();
+        let schema = SchemaAsIpc::new(&result?, &options)
+            .try_into()
+            .map_err(internal)?;
+
+        Ok(Response::new(schema))
+    }
+
+    async fn do_get(
+        &self,
+        request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        let ticket: FlightTicket = serde_json::from_slice(&request.get_ref().ticket)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let client = self.server.new_local_client();
+        let result = query(&client, ticket).await;
+        client.close().await;
+        let (schema, batches) = result?;
+        let batches = stream::iter(batches.into_iter().map(Ok::<_, FlightError>));
+        let data = FlightDataEncoderBuilder::new()
+            .with_schema(schema)
+            .build(batches)
+            .map_err(Status::from);
+
+        Ok(Response::new(data.boxed()))
+    }
+
+    async fn do_put(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        let mut input = request.into_inner();
+        let first = input
+            .message()
+            .await?
+            .ok_or_else(|| Status::invalid_argument("Expected FlightData"))?;
+
+        let name = descriptor_table(first.flight_descriptor.as_ref())?;
+        let data = stream::once(future::ok(first))
+            .chain(input)
+            .map_err(FlightError::from);
+
+        let mut batches = FlightRecordBatchStream::new_from_flight_data(data);
+        let mut received = vec![];
+        while let Some(batch) = batches.try_next().await? {
+            received.push(batch);
+        }
+
+        if let Some(schema) = batches.schema().cloned() {
+            let arrow = to_ipc(&schema, &received).map_err(internal)?;
+            let client = self.server.new_local_client();
+            let result = async {
+                let table = open_table(&client, &name).await?;
+                let data = UpdateData::Arrow(arrow.into());
+                table
+                    .update(data, UpdateOptions::default())
+                    .await
+                    .map_err(client_error)
+            }
+            .await;
+
+            client.close().await;
+            result?;
+        }
+
+        Ok(Response::new(
+            stream::once(future::ok(PutResult::default())).boxed(),
+        ))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        Err(Status::unimplemented("DoExchange is not supported"))
+    }
+
+    async fn do_action(
+        &self,
+        _request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        Err(Status::unimplemented("DoAction is not supported"))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        Ok(Response::new(stream::empty().boxed()))
+    }
+}
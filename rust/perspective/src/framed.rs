@@ -0,0 +1,343 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! A minimal framed transport for same-host IPC, over TCP or Unix domain
+//! sockets. Each message is a `u32` (big-endian) length prefix followed by
+//! the encoded `Request` or `Response`. Frames may be up to
+//! [`DEFAULT_MAX_FRAME_LENGTH`] bytes, which can be changed (on either end,
+//! up to [`MAX_FRAME_LENGTH`]) with a [`FramedConfig`].
+//!
+//! ```rust,ignore
+//! let listener = tokio::net::UnixListener::bind("/tmp/perspective.sock")?;
+//! tokio::spawn(framed::serve_unix(server, listener, shutdown_signal()));
+//!
+//! let client = framed::connect_unix("/tmp/perspective.sock").await?;
+//! let table = client.open_table("trades".to_owned()).await?;
+//! client.close().await;
+//! ```
+
+use std::future::Future;
+use std::io;
+use std::ops::Deref;
+#[cfg(unix)]
+use std::path::Path;
+
+use bytes::Bytes;
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures::{future, SinkExt, Stream, StreamExt, TryStreamExt};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::TcpListenerStream;
+#[cfg(unix)]
+use tokio_stream::wrappers::UnixListenerStream;
+use tokio_util::codec::{Framed, LengthDelimitedCodec};
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+
+use crate::client::utils::PerspectiveResultExt;
+use crate::client::{Client, ClientHandler};
+use crate::connection::serve_connection;
+use crate::server::Server;
+
+/// A local error synonym for this module only.
+type PerspectiveFramedError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The default maximum frame length, 64 MiB. The codec reserves a frame's
+/// declared length before reading it, so this bounds what a single length
+/// prefix from an untrusted peer can make the server allocate.
+pub const DEFAULT_MAX_FRAME_LENGTH: usize = 64 * 1024 * 1024;
+
+/// The largest frame length a `u32` length prefix can describe.
+pub const MAX_FRAME_LENGTH: usize = u32::MAX as usize;
+
+/// Options for either end of a framed connection.
+#[derive(Clone, Copy, Debug)]
+pub struct FramedConfig {
+    max_frame_length: usize,
+}
+
+impl Default for FramedConfig {
+    fn default() -> Self {
+        Self {
+            max_frame_length: DEFAULT_MAX_FRAME_LENGTH,
+        }
+    }
+}
+
+impl FramedConfig {
+    /// The maximum length of a single incoming or outgoing frame (i.e. an
+    /// encoded `Request` or `Response`, which may carry an entire Arrow or
+    /// CSV load or `to_arrow` result), in bytes. A longer frame ends the
+    /// connection. Defaults to [`DEFAULT_MAX_FRAME_LENGTH`], and is capped at
+    /// [`MAX_FRAME_LENGTH`].
+    pub fn max_frame_length(mut self, length: usize) -> Self {
+        self.max_frame_length = length.min(MAX_FRAME_LENGTH);
+        self
+    }
+
+    fn codec(&self) -> LengthDelimitedCodec {
+        LengthDelimitedCodec::builder()
+            .max_frame_length(self.max_frame_length)
+            .new_codec()
+    }
+}
+
+/// Serve a single framed connection to `server` until either side closes it
+/// or `shutdown` resolves, at which point the connection's
+/// [`crate::server::LocalSession`] is closed.
+async fn serve_stream_until<S, F>(
+    server: &Server,
+    stream: S,
+    shutdown: F,
+    config: FramedConfig,
+) -> Result<(), PerspectiveFramedError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+    F: Future<Output = ()>,
+{
+    let (sink, stream) = Framed::new(stream, config.codec()).split();
+    let stream = stream
+        .map_ok(|frame| frame.to_vec())
+        .map_err(PerspectiveFramedError::from)
+        .take_until(shutdown);
+
+    let sink = sink.with(|bytes: Vec<u8>| future::ok::<_, io::Error>(Bytes::from(bytes)));
+    serve_connection(server, Box::pin(stream), Box::pin(sink)).await
+}
+
+/// Serve a single framed connection (e.g. an accepted [`TcpStream`]) to
+/// `server`, returning when it closes.
+pub async fn serve_stream<S>(server: &Server, stream: S) -> Result<(), PerspectiveFramedError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    serve_stream_until(server, stream, future::pending(), FramedConfig::default()).await
+}
+
+/// Serve every connection from `incoming` to `server`, each on its own
+/// [`tokio`] task, until `shutdown` resolves. On shutdown, no new
+/// connections are accepted, open connections are closed and this function
+/// returns once all of their sessions have been closed.
+pub async fn serve<I, S, F>(server: Server, incoming: I, shutdown: F)
+where
+    I: Stream<Item = io::Result<S>>,
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    F: Future<Output = ()>,
+{
+    serve_with_config(server, incoming, shutdown, FramedConfig::default()).await
+}
+
+/// As [`serve`], with the connection options of a [`FramedConfig`].
+pub async fn serve_with_config<I, S, F>(
+    server: Server,
+    incoming: I,
+    shutdown: F,
+    config: FramedConfig,
+) where
+    I: Stream<Item = io::Result<S>>,
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    F: Future<Output = ()>,
+{
+    let token = CancellationToken::new();
+    let tracker = TaskTracker::new();
+    let mut incoming = std::pin::pin!(incoming.take_until(shutdown));
+    while let Some(stream) = incoming.next().await {
+        match stream {
+            Ok(stream) => {
+                let server = server.clone();
+                let shutdown = token.clone().cancelled_owned();
+                tracker.spawn(async move {
+                    let result = serve_stream_until(&server, stream, shutdown, config).await;
+                    if let Err(msg) = result {
+                        tracing::error!("Internal error {}", msg);
+                    }
+                });
+            },
+            Err(err) => tracing::error!("Failed to accept connection: {}", err),
+        }
+    }
+
+    token.cancel();
+    tracker.close();
+    tracker.wait().await;
+}
+
+/// Serve connections from a [`TcpListener`] to `server`. See [`serve`].
+pub async fn serve_tcp<F>(server: Server, listener: TcpListener, shutdown: F)
+where
+    F: Future<Output = ()>,
+{
+    serve(server, TcpListenerStream::new(listener), shutdown).await
+}
+
+/// Serve connections from a [`UnixListener`] to `server`. See [`serve`].
+#[cfg(unix)]
+pub async fn serve_unix<F>(server: Server, listener: UnixListener, shutdown: F)
+where
+    F: Future<Output = ()>,
+{
+    serve(server, UnixListenerStream::new(listener), shutdown).await
+}
+
+/// The [`ClientHandler`] for a framed connection, which forwards requests to
+/// the connection's task.
+#[derive(Clone)]
+struct FramedClientHandler(UnboundedSender<Vec<u8>>);
+
+impl ClientHandler for FramedClientHandler {
+    async fn send_request(&self, msg: Vec<u8>) -> Result<(), PerspectiveFramedError> {
+        Ok(self.0.unbounded_send(msg)?)
+    }
+}
+
+/// A [`Client`] connected over a framed connection, created by
+/// [`connect_stream`], [`connect_tcp`] or [`connect_unix`]. Use
+/// [`FramedClient::close`] to disconnect gracefully; dropping a
+/// [`FramedClient`] closes the connection without waiting.
+#[derive(Debug)]
+pub struct FramedClient {
+    client: Client,
+    sender: UnboundedSender<Vec<u8>>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl Deref for FramedClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+impl FramedClient {
+    /// Flush any pending requests and close the connection, waiting for the
+    /// server to close its end.
+    pub async fn close(mut self) {
+        self.sender.close_channel();
+        if let Some(task) = self.task.take() {
+            task.await.unwrap_or_log();
+        }
+    }
+}
+
+impl Drop for FramedClient {
+    fn drop(&mut self) {
+        self.sender.close_channel();
+    }
+}
+
+/// The connection's task: write requests from `receiver` and dispatch
+/// responses to `client`, until the server closes the connection (or the
+/// [`FramedClient`] is closed), then notify `client` via
+/// [`Client::handle_error`].
+async fn run_connection<S>(
+    stream: S,
+    config: FramedConfig,
+    mut receiver: UnboundedReceiver<Vec<u8>>,
+    sender: UnboundedSender<Vec<u8>>,
+    client: Client,
+) where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (mut sink, mut stream) = Framed::new(stream, config.codec()).split();
+    let writer = async {
+        while let Some(msg) = receiver.next().await {
+            sink.send(Bytes::from(msg)).await?;
+        }
+
+        sink.close().await
+    };
+
+    let reader = async {
+        let result = async {
+            while let Some(frame) = stream.next().await {
+                client.handle_response(&frame?).await.unwrap_or_log();
+            }
+
+            Ok::<_, io::Error>(())
+        }
+        .await;
+
+        sender.close_channel();
+        result
+    };
+
+    let message = match future::join(reader, writer).await {
+        (Ok(()), Ok(())) => "Connection closed".to_owned(),
+        (Err(err), _) | (_, Err(err)) => format!("Connection error: {}", err),
+    };
+
+    tracing::info!("{}", message);
+    client
+        .handle_error(Some(message), None)
+        .await
+        .unwrap_or_log();
+}
+
+/// Connect a new [`Client`] over an established framed connection `stream`,
+/// whose messages are handled on a [`tokio`] task.
+pub async fn connect_stream<S>(stream: S) -> Result<FramedClient, PerspectiveFramedError>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    connect_stream_with_config(stream, FramedConfig::default()).await
+}
+
+/// As [`connect_stream`], with the connection options of a [`FramedConfig`].
+pub async fn connect_stream_with_config<S>(
+    stream: S,
+    config: FramedConfig,
+) -> Result<FramedClient, PerspectiveFramedError>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (sender, receiver) = unbounded::<Vec<u8>>();
+    let client = Client::new(FramedClientHandler(sender.clone()));
+    let task = tokio::spawn(run_connection(
+        stream,
+        config,
+        receiver,
+        sender.clone(),
+        client.clone(),
+    ));
+
+    let client = FramedClient {
+        client,
+        sender,
+        task: Some(task),
+    };
+
+    client.init().await?;
+    Ok(client)
+}
+
+/// Connect a new [`Client`] to a server hosted by [`serve_tcp`] at `addr`.
+pub async fn connect_tcp<A>(addr: A) -> Result<FramedClient, PerspectiveFramedError>
+where
+    A: ToSocketAddrs,
+{
+    let stream = TcpStream::connect(addr).await?;
+    stream.set_nodelay(true)?;
+    connect_stream(stream).await
+}
+
+/// Connect a new [`Client`] to a server hosted by [`serve_unix`] at `path`.
+#[cfg(unix)]
+pub async fn connect_unix<P>(path: P) -> Result<FramedClient, PerspectiveFramedError>
+where
+    P: AsRef<Path>,
+{
+    connect_stream(UnixStream::connect(path).await?).await
+}
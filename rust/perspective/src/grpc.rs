@@ -0,0 +1,261 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! A [gRPC](https://grpc.io) transport for Perspective, via [`tonic`].
+//!
+//! The service is defined in `perspective_grpc.proto` as a single
+//! bidirectional streaming RPC, `perspective.proto.Perspective/Connect`,
+//! which carries the same `Request` and `Response` messages as the
+//! WebSocket transport. [`GrpcService`] serves a [`Server`] over this RPC,
+//! and [`connect`] creates a [`GrpcClient`] for it.
+//!
+//! ```rust,ignore
+//! tonic::transport::Server::builder()
+//!     .add_service(GrpcService::new(server))
+//!     .serve("127.0.0.1:50051".parse()?)
+//!     .await?;
+//!
+//! let channel = Channel::from_static("http://127.0.0.1:50051").connect().await?;
+//! let client = perspective::grpc::connect(channel).await?;
+//! client.close().await;
+//! ```
+
+use std::convert::Infallible;
+use std::error::Error;
+use std::ops::Deref;
+use std::task::{Context, Poll};
+
+use bytes::{Buf, BufMut};
+use futures::channel::mpsc::{unbounded, UnboundedSender};
+use futures::stream::BoxStream;
+use futures::{future, SinkExt, StreamExt, TryStreamExt};
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::codegen::{empty_body, http, Body, BoxFuture, Service, StdError};
+use tonic::server::{Grpc, NamedService, StreamingService};
+use tonic::transport::Channel;
+use tokio::task::JoinHandle;
+use tonic::{Status, Streaming};
+
+use crate::client::utils::PerspectiveResultExt;
+use crate::client::{Client, ClientHandler};
+use crate::connection::serve_connection;
+use crate::server::Server;
+
+/// A local error synonym for this module only.
+type PerspectiveGrpcError = Box<dyn Error + Send + Sync>;
+
+const CONNECT_PATH: &str = "/perspective.proto.Perspective/Connect";
+
+/// A [`Codec`] which passes messages through as already-encoded bytes, as
+/// both [`Server`] and [`Client`] produce and consume encoded messages.
+#[derive(Clone, Copy, Debug, Default)]
+struct BytesCodec;
+
+impl Codec for BytesCodec {
+    type Decode = Vec<u8>;
+    type Decoder = BytesCodec;
+    type Encode = Vec<u8>;
+    type Encoder = BytesCodec;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        BytesCodec
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        BytesCodec
+    }
+}
+
+impl Encoder for BytesCodec {
+    type Error = Status;
+    type Item = Vec<u8>;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        dst.put_slice(&item);
+        Ok(())
+    }
+}
+
+impl Decoder for BytesCodec {
+    type Error = Status;
+    type Item = Vec<u8>;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(Some(src.copy_to_bytes(src.remaining()).to_vec()))
+    }
+}
+
+/// The `Connect` RPC, which serves a single session per call.
+struct ConnectService(Server);
+
+impl StreamingService<Vec<u8>> for ConnectService {
+    type Future = BoxFuture<tonic::Response<Self::ResponseStream>, Status>;
+    type Response = Vec<u8>;
+    type ResponseStream = BoxStream<'static, Result<Vec<u8>, Status>>;
+
+    fn call(&mut self, request: tonic::Request<Streaming<Vec<u8>>>) -> Self::Future {
+        let server = self.0.clone();
+        let stream = request.into_inner().map_err(PerspectiveGrpcError::from);
+        let (sender, receiver) = unbounded::<Result<Vec<u8>, Status>>();
+        let sink = sender.with(|bytes| future::ok::<_, PerspectiveGrpcError>(Ok(bytes)));
+        tokio::spawn(async move {
+            if let Err(msg) = serve_connection(&server, Box::pin(stream), Box::pin(sink)).await {
+                tracing::error!("Internal error {}", msg);
+            }
+        });
+
+        Box::pin(async move { Ok(tonic::Response::new(receiver.boxed())) })
+    }
+}
+
+/// A [`tonic`] service which serves a [`Server`] over the
+/// `perspective.proto.Perspective` gRPC service, for use with
+/// [`tonic::transport::Server::add_service`]. Each `Connect` call creates a
+/// new [`crate::server::LocalSession`], which is closed when either side
+/// ends the call.
+#[derive(Clone, Debug)]
+pub struct GrpcService {
+    server: Server,
+}
+
+impl GrpcService {
+    pub fn new(server: Server) -> Self {
+        Self { server }
+    }
+}
+
+impl NamedService for GrpcService {
+    const NAME: &'static str = "perspective.proto.Perspective";
+}
+
+impl<B> Service<http::Request<B>> for GrpcService
+where
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+    type Response = http::Response<tonic::body::BoxBody>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        if req.uri().path() == CONNECT_PATH {
+            let service = ConnectService(self.server.clone());
+            Box::pin(async move { Ok(Grpc::new(BytesCodec).streaming(service, req).await) })
+        } else {
+            Box::pin(async move {
+                Ok(http::Response::builder()
+                    .status(200)
+                    .header("grpc-status", tonic::Code::Unimplemented as i32)
+                    .header("content-type", "application/grpc")
+                    .body(empty_body())
+                    .expect("Invalid response"))
+            })
+        }
+    }
+}
+
+/// The [`ClientHandler`] for a gRPC connection, which forwards requests to
+/// the `Connect` call's request stream.
+#[derive(Clone)]
+struct GrpcClientHandler(UnboundedSender<Vec<u8>>);
+
+impl ClientHandler for GrpcClientHandler {
+    async fn send_request(&self, msg: Vec<u8>) -> Result<(), PerspectiveGrpcError> {
+        Ok(self.0.unbounded_send(msg)?)
+    }
+}
+
+/// A [`Client`] connected over a gRPC `Connect` call, created by [`connect`].
+/// Use [`GrpcClient::close`] to end the call gracefully; dropping it also
+/// ends the call, without waiting.
+#[derive(Debug)]
+pub struct GrpcClient {
+    client: Client,
+    sender: UnboundedSender<Vec<u8>>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl Deref for GrpcClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+impl GrpcClient {
+    /// End the request stream and wait for the server to end the call.
+    pub async fn close(mut self) {
+        self.sender.close_channel();
+        if let Some(task) = self.task.take() {
+            task.await.unwrap_or_log();
+        }
+    }
+}
+
+impl Drop for GrpcClient {
+    fn drop(&mut self) {
+        self.sender.close_channel();
+    }
+}
+
+/// Connect a new [`Client`] to a Perspective server hosting a
+/// [`GrpcService`] over `channel`, which may be configured with TLS,
+/// timeouts, etc. via [`tonic::transport::Endpoint`].
+///
+/// The response stream is read on a [`tokio`] task. When the call ends, the
+/// [`Client`]'s [`Client::on_error`] callbacks are invoked (without a
+/// reconnect callback).
+pub async fn connect(channel: Channel) -> Result<GrpcClient, PerspectiveGrpcError> {
+    let mut grpc = tonic::client::Grpc::new(channel);
+    grpc.ready().await?;
+    let (sender, receiver) = unbounded::<Vec<u8>>();
+    let path = PathAndQuery::from_static(CONNECT_PATH);
+    let request = tonic::Request::new(receiver);
+    let mut responses = grpc
+        .streaming(request, path, BytesCodec)
+        .await?
+        .into_inner();
+    let client = Client::new(GrpcClientHandler(sender.clone()));
+    let task = tokio::spawn({
+        let client = client.clone();
+        async move {
+            let message = loop {
+                match responses.message().await {
+                    Ok(Some(msg)) => client.handle_response(&msg).await.unwrap_or_log(),
+                    Ok(None) => break "gRPC stream closed".to_owned(),
+                    Err(status) => break format!("gRPC error: {}", status),
+                }
+            };
+
+            tracing::info!("{}", message);
+            client
+                .handle_error(Some(message), None)
+                .await
+                .unwrap_or_log();
+        }
+    });
+
+    let client = GrpcClient {
+        client,
+        sender,
+        task: Some(task),
+    };
+
+    client.init().await?;
+    Ok(client)
+}
@@ -55,4 +55,53 @@
 #[cfg(feature = "axum-ws")]
 pub mod axum;
 
+#[cfg(any(
+    feature = "axum-ws",
+    feature = "tungstenite-ws",
+    feature = "grpc",
+    feature = "framed"
+))]
+pub mod connection;
+
+#[cfg(feature = "framed")]
+pub mod framed;
+
+#[cfg(feature = "file-table")]
+pub mod file_table;
+
+#[cfg(feature = "flight")]
+pub mod flight;
+
+#[cfg(feature = "grpc")]
+pub mod grpc;
+
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
+
+#[cfg(feature = "rest")]
+pub mod rest;
+
+#[cfg(feature = "source")]
+pub mod source;
+
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+#[cfg(feature = "sse")]
+pub mod sse;
+
+#[cfg(feature = "datafusion")]
+pub mod table_provider;
+
+#[cfg(feature = "tower-ws")]
+pub mod tower;
+
+#[cfg(feature = "tungstenite-ws")]
+pub mod tungstenite;
+
+#[cfg(feature = "websocket-client")]
+pub mod websocket_client;
+
+#[cfg(feature = "websocket-client")]
+pub use websocket_client::{connect, WebsocketClient};
 pub use {perspective_client as client, perspective_server as server};
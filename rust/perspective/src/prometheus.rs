@@ -0,0 +1,181 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! A [Prometheus](https://prometheus.io/) metrics endpoint for a
+//! [`Server`], in the Prometheus text exposition format.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use axum::extract::State;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, MethodRouter};
+
+use crate::server::Server;
+
+/// A local error synonym for this module only.
+type PerspectiveMetricsError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Escape a Prometheus label value.
+fn label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Render the metrics of a [`Server`] in the Prometheus text format.
+///
+/// In addition to [`Server::metrics`], this reports the number of hosted
+/// tables and their row counts (via [`Server::table_sizes`]), and the number
+/// of views by type (`flat`, `group_by` or `split_by`) and
+/// [`Server::sessions`].
+pub async fn render(server: &Server) -> Result<String, PerspectiveMetricsError> {
+    let mut out = String::new();
+    let tables = server.table_sizes().await?;
+    writeln!(out, "# HELP perspective_tables Number of hosted tables.")?;
+    writeln!(out, "# TYPE perspective_tables gauge")?;
+    writeln!(out, "perspective_tables {}", tables.len())?;
+    writeln!(
+        out,
+        "# HELP perspective_table_rows Number of rows per table."
+    )?;
+    writeln!(out, "# TYPE perspective_table_rows gauge")?;
+    for (name, size) in &tables {
+        writeln!(
+            out,
+            "perspective_table_rows{{table=\"{}\"}} {}",
+            label(name),
+            size
+        )?;
+    }
+
+    let sessions = server.sessions();
+    let mut views = HashMap::from([("flat", 0), ("group_by", 0), ("split_by", 0)]);
+    for view in sessions.iter().flat_map(|x| x.views.iter()) {
+        let kind = if !view.config.split_by.is_empty() {
+            "split_by"
+        } else if !view.config.group_by.is_empty() {
+            "group_by"
+        } else {
+            "flat"
+        };
+
+        *views.entry(kind).or_default() += 1;
+    }
+
+    writeln!(out, "# HELP perspective_sessions Number of open sessions.")?;
+    writeln!(out, "# TYPE perspective_sessions gauge")?;
+    writeln!(out, "perspective_sessions {}", sessions.len())?;
+    writeln!(out, "# HELP perspective_views Number of views by type.")?;
+    writeln!(out, "# TYPE perspective_views gauge")?;
+    for kind in ["flat", "group_by", "split_by"] {
+        writeln!(
+            out,
+            "perspective_views{{type=\"{}\"}} {}",
+            kind, views[kind]
+        )?;
+    }
+
+    let metrics = server.metrics();
+    writeln!(
+        out,
+        "# HELP perspective_table_updates_total Number of table updates."
+    )?;
+    writeln!(out, "# TYPE perspective_table_updates_total counter")?;
+    for (name, count) in &metrics.table_updates {
+        writeln!(
+            out,
+            "perspective_table_updates_total{{table=\"{}\"}} {}",
+            label(name),
+            count
+        )?;
+    }
+
+    writeln!(
+        out,
+        "# HELP perspective_table_rows_updated_total Number of rows received by table updates."
+    )?;
+    writeln!(out, "# TYPE perspective_table_rows_updated_total counter")?;
+    for (name, count) in &metrics.table_rows_updated {
+        writeln!(
+            out,
+            "perspective_table_rows_updated_total{{table=\"{}\"}} {}",
+            label(name),
+            count
+        )?;
+    }
+
+    writeln!(
+        out,
+        "# HELP perspective_request_duration_seconds Request latency by kind."
+    )?;
+    writeln!(out, "# TYPE perspective_request_duration_seconds histogram")?;
+    for (kind, histogram) in &metrics.request_latencies {
+        for (bound, count) in &histogram.buckets {
+            writeln!(
+                out,
+                "perspective_request_duration_seconds_bucket{{kind=\"{}\",le=\"{}\"}} {}",
+                kind, bound, count
+            )?;
+        }
+
+        let name = "perspective_request_duration_seconds";
+        writeln!(
+            out,
+            "{}_bucket{{kind=\"{}\",le=\"+Inf\"}} {}",
+            name, kind, histogram.count
+        )?;
+        writeln!(out, "{}_sum{{kind=\"{}\"}} {}", name, kind, histogram.sum)?;
+        writeln!(
+            out,
+            "{}_count{{kind=\"{}\"}} {}",
+            name, kind, histogram.count
+        )?;
+    }
+
+    writeln!(
+        out,
+        "# HELP perspective_queue_depth Engine calls waiting to run, by shard."
+    )?;
+    writeln!(out, "# TYPE perspective_queue_depth gauge")?;
+    for (shard, depth) in metrics.queue_depth.iter().enumerate() {
+        writeln!(
+            out,
+            "perspective_queue_depth{{shard=\"{}\"}} {}",
+            shard, depth
+        )?;
+    }
+
+    Ok(out)
+}
+
+/// An [`axum`] route which serves [`render`] as a Prometheus scrape target,
+/// e.g.
+///
+/// ```rust,ignore
+/// Router::new()
+///     .route("/metrics", perspective::prometheus::metrics_handler())
+///     .with_state(server)
+/// ```
+pub fn metrics_handler() -> MethodRouter<Server> {
+    async fn metrics_handler_internal(State(server): State<Server>) -> Response {
+        match render(&server).await {
+            Ok(body) => ([(CONTENT_TYPE, "text/plain; version=0.0.4")], body).into_response(),
+            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        }
+    }
+
+    get(metrics_handler_internal)
+}
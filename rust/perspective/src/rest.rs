@@ -0,0 +1,272 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! An HTTP/REST gateway to the tables hosted by a [`Server`], for consumers
+//! which can't use the binary WebSocket protocol (e.g. `curl` scripts, BI
+//! tools, cron jobs).
+//!
+//! | Route                        | Description                                     |
+//! | ---------------------------- | ----------------------------------------------- |
+//! | `GET /tables`                | The names of the hosted tables, as JSON.        |
+//! | `GET /tables/:name/schema`   | The table's schema, as JSON.                    |
+//! | `POST /tables/:name/update`  | Update the table with the request body.         |
+//! | `POST /tables/:name/replace` | Replace the table's data with the request body. |
+//! | `POST /tables/:name/query`   | Query a one-shot view of the table.             |
+//!
+//! Update data may be CSV, JSON (rows or columns), NDJSON or Arrow, chosen by
+//! the request's `Content-Type`. Queries take a JSON body of the form
+//! `{"config": ViewConfigUpdate, "window": ViewWindow}` (both optional), and
+//! return CSV, JSON, NDJSON or Arrow (IPC stream format) chosen by the
+//! request's `Accept` header. Requests the engine rejects (e.g. for an
+//! unknown column) fail with `400 Bad Request`.
+//!
+//! ```rust,ignore
+//! let app = Router::new()
+//!     .nest("/api", perspective::rest::router())
+//!     .with_state(server);
+//! ```
+
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::header::{ACCEPT, CONTENT_TYPE};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+
+use crate::axum::client_error_status;
+use crate::client::config::ViewConfigUpdate;
+use crate::client::{Client, ClientError, Table, UpdateData, UpdateOptions, View, ViewWindow};
+use crate::server::Server;
+
+const ARROW_STREAM: &str = "application/vnd.apache.arrow.stream";
+
+/// Arrow IPC file format, which is accepted as update data but not returned
+/// by queries (the engine only writes the streaming format).
+const ARROW_FILE: &str = "application/vnd.apache.arrow.file";
+
+/// An error response, with a plain text message.
+struct RestError(StatusCode, String);
+
+impl IntoResponse for RestError {
+    fn into_response(self) -> Response {
+        (self.0, self.1).into_response()
+    }
+}
+
+/// See [`client_error_status`].
+impl From<ClientError> for RestError {
+    fn from(value: ClientError) -> Self {
+        RestError(client_error_status(&value), value.to_string())
+    }
+}
+
+/// The body of a `POST /tables/:name/query` request.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct QueryRequest {
+    #[serde(default)]
+    config: Option<ViewConfigUpdate>,
+
+    #[serde(default)]
+    window: ViewWindow,
+}
+
+/// The output formats of `POST /tables/:name/query`.
+enum Format {
+    Csv,
+    Json,
+    Ndjson,
+    Arrow,
+}
+
+/// The media type of a header value, without parameters.
+fn media_type(value: &str) -> &str {
+    value.split(';').next().unwrap_or_default().trim()
+}
+
+/// Choose the [`Format`] of a query response from the `Accept` header,
+/// defaulting to JSON.
+fn accept_format(headers: &HeaderMap) -> Result<Format, RestError> {
+    let accept = headers
+        .get(ACCEPT)
+        .and_then(|x| x.to_str().ok())
+        .unwrap_or("*/*");
+
+    for mime in accept.split(',').map(media_type) {
+        match mime {
+            "text/csv" => return Ok(Format::Csv),
+            "application/json" | "application/*" | "*/*" => return Ok(Format::Json),
+            "application/x-ndjson" | "application/ndjson" => return Ok(Format::Ndjson),
+            ARROW_STREAM => return Ok(Format::Arrow),
+            _ => (),
+        }
+    }
+
+    Err(RestError(
+        StatusCode::NOT_ACCEPTABLE,
+        format!("Unsupported Accept {}", accept),
+    ))
+}
+
+/// Interpret a request body as [`UpdateData`] by its `Content-Type`.
+fn update_data(headers: &HeaderMap, body: Bytes) -> Result<UpdateData, RestError> {
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|x| x.to_str().ok())
+        .map(media_type)
+        .unwrap_or_default();
+
+    let text = |body: Bytes| {
+        String::from_utf8(body.to_vec())
+            .map_err(|e| RestError(StatusCode::BAD_REQUEST, e.to_string()))
+    };
+
+    match content_type {
+        "text/csv" => Ok(UpdateData::Csv(text(body)?)),
+        "application/json" => {
+            let json = text(body)?;
+            if json.trim_start().starts_with('[') {
+                Ok(UpdateData::JsonRows(json))
+            } else {
+                Ok(UpdateData::JsonColumns(json))
+            }
+        },
+        "application/x-ndjson" | "application/ndjson" => Ok(UpdateData::Ndjson(text(body)?)),
+        ARROW_STREAM | ARROW_FILE | "application/octet-stream" => Ok(UpdateData::Arrow(body)),
+        "application/vnd.apache.parquet" => Ok(UpdateData::Parquet(body)),
+        _ => Err(RestError(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            format!("Unsupported Content-Type {}", content_type),
+        )),
+    }
+}
+
+async fn open_table(client: &Client, name: String) -> Result<Table, RestError> {
+    if client.get_hosted_table_names().await?.contains(&name) {
+        Ok(client.open_table(name).await?)
+    } else {
+        Err(RestError(
+            StatusCode::NOT_FOUND,
+            format!("Unknown table {}", name),
+        ))
+    }
+}
+
+async fn get_tables(State(server): State<Server>) -> Result<Json<Vec<String>>, RestError> {
+    let client = server.new_local_client();
+    let result = client.get_hosted_table_names().await;
+    client.close().await;
+    Ok(Json(result?))
+}
+
+async fn get_schema(
+    State(server): State<Server>,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, RestError> {
+    let client = server.new_local_client();
+    let result =
+        async { Ok::<_, RestError>(open_table(&client, name).await?.schema().await?) }.await;
+    client.close().await;
+    Ok(Json(result?))
+}
+
+async fn update_table(
+    State(server): State<Server>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, RestError> {
+    let data = update_data(&headers, body)?;
+    let client = server.new_local_client();
+    let result = async {
+        let table = open_table(&client, name).await?;
+        table.update(data, UpdateOptions::default()).await?;
+        Ok::<_, RestError>(())
+    }
+    .await;
+
+    client.close().await;
+    result.map(|_| StatusCode::NO_CONTENT)
+}
+
+async fn replace_table(
+    State(server): State<Server>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, RestError> {
+    let data = update_data(&headers, body)?;
+    let client = server.new_local_client();
+    let result = async {
+        open_table(&client, name).await?.replace(data).await?;
+        Ok::<_, RestError>(())
+    }
+    .await;
+
+    client.close().await;
+    result.map(|_| StatusCode::NO_CONTENT)
+}
+
+async fn query_view(
+    view: &View,
+    format: Format,
+    window: ViewWindow,
+) -> Result<Response, ClientError> {
+    Ok(match format {
+        Format::Csv => ([(CONTENT_TYPE, "text/csv")], view.to_csv(window).await?).into_response(),
+        Format::Json => {
+            let json = view.to_json_string(window).await?;
+            ([(CONTENT_TYPE, "application/json")], json).into_response()
+        },
+        Format::Ndjson => {
+            let ndjson = view.to_ndjson(window).await?;
+            ([(CONTENT_TYPE, "application/x-ndjson")], ndjson).into_response()
+        },
+        Format::Arrow => {
+            ([(CONTENT_TYPE, ARROW_STREAM)], view.to_arrow(window).await?).into_response()
+        },
+    })
+}
+
+async fn query_table(
+    State(server): State<Server>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    Json(query): Json<QueryRequest>,
+) -> Result<Response, RestError> {
+    let format = accept_format(&headers)?;
+    let client = server.new_local_client();
+    let result = async {
+        let view = open_table(&client, name).await?.view(query.config).await?;
+        let resp = query_view(&view, format, query.window).await;
+        view.delete().await?;
+        Ok::<_, RestError>(resp?)
+    }
+    .await;
+
+    client.close().await;
+    result
+}
+
+/// An [`axum`] [`Router`] serving the REST API described in the module
+/// documentation, for a [`Server`] provided as router state. Each request is
+/// made via a temporary [`crate::server::LocalClient`].
+pub fn router() -> Router<Server> {
+    Router::new()
+        .route("/tables", get(get_tables))
+        .route("/tables/:name/schema", get(get_schema))
+        .route("/tables/:name/update", post(update_table))
+        .route("/tables/:name/replace", post(replace_table))
+        .route("/tables/:name/query", post(query_table))
+}
@@ -0,0 +1,388 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! Feed a [`Table`] from an async [`Stream`] of [`UpdateData`], with
+//! batching, error handling and metrics, plus adapters for common sources.
+//!
+//! ```rust,ignore
+//! use perspective::source::{self, SourcePolicy, TableSourceExt};
+//!
+//! let (tx, rx) = tokio::sync::mpsc::channel(1024);
+//! tokio::spawn(feed_handler(tx));
+//! let stats = table.ingest(source::from_mpsc(rx), SourcePolicy::default()).await?;
+//! ```
+//!
+//! The stream is only polled between calls to [`Table::update`], so a slow
+//! [`Table`] applies backpressure to the source (e.g. a bounded
+//! [`mpsc::channel`] fills up and its senders wait), and at most
+//! [`SourcePolicy::max_batch_size`] items are buffered at a time.
+
+use std::future::Future;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use futures::{Stream, StreamExt, stream};
+use tokio::fs::File;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncSeekExt, BufReader};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::client::{ClientError, Table, UpdateData, UpdateOptions};
+
+/// What to do when a [`Table::update`] call fails.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OnError {
+    /// Stop ingesting and return the error.
+    #[default]
+    Stop,
+
+    /// Log the error, count it in [`SourceStats::errors`] and continue with
+    /// the next batch.
+    Skip,
+}
+
+/// Options for [`TableSourceExt::ingest`].
+#[derive(Clone, Debug)]
+pub struct SourcePolicy {
+    /// The most items to collect into one batch.
+    pub max_batch_size: usize,
+
+    /// The longest to wait for a batch to fill, measured from its first item.
+    pub max_batch_delay: Duration,
+
+    pub on_error: OnError,
+
+    /// Passed to every [`Table::update`] call.
+    pub update_options: UpdateOptions,
+
+    /// Updated as the source is ingested. Clone this before calling
+    /// [`TableSourceExt::ingest`] to observe progress from another task.
+    pub metrics: SourceMetrics,
+}
+
+impl Default for SourcePolicy {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 1000,
+            max_batch_delay: Duration::from_millis(100),
+            on_error: OnError::default(),
+            update_options: UpdateOptions::default(),
+            metrics: SourceMetrics::default(),
+        }
+    }
+}
+
+/// Live counters for a running [`TableSourceExt::ingest`].
+#[derive(Clone, Debug, Default)]
+pub struct SourceMetrics(Arc<SourceCounters>);
+
+#[derive(Debug, Default)]
+struct SourceCounters {
+    items: AtomicU64,
+    batches: AtomicU64,
+    updates: AtomicU64,
+    errors: AtomicU64,
+}
+
+impl SourceMetrics {
+    pub fn snapshot(&self) -> SourceStats {
+        SourceStats {
+            items: self.0.items.load(Ordering::Relaxed),
+            batches: self.0.batches.load(Ordering::Relaxed),
+            updates: self.0.updates.load(Ordering::Relaxed),
+            errors: self.0.errors.load(Ordering::Relaxed),
+        }
+    }
+
+    fn incr(counter: &AtomicU64, n: u64) {
+        counter.fetch_add(n, Ordering::Relaxed);
+    }
+}
+
+/// A point-in-time copy of [`SourceMetrics`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SourceStats {
+    /// Items read from the stream.
+    pub items: u64,
+
+    /// Batches collected from those items.
+    pub batches: u64,
+
+    /// Successful [`Table::update`] calls. Within a batch, runs of
+    /// [`UpdateData::Ndjson`] items (or of [`UpdateData::Csv`] items with the
+    /// same header) are merged into one update.
+    pub updates: u64,
+
+    /// Failed [`Table::update`] calls.
+    pub errors: u64,
+}
+
+/// Extends [`Table`] with [`TableSourceExt::ingest`].
+pub trait TableSourceExt {
+    /// Update this [`Table`] from `stream` until it ends, returning the final
+    /// [`SourceStats`].
+    fn ingest<S>(
+        &self,
+        stream: S,
+        policy: SourcePolicy,
+    ) -> impl Future<Output = Result<SourceStats, ClientError>> + Send
+    where
+        S: Stream<Item = UpdateData> + Send;
+}
+
+impl TableSourceExt for Table {
+    fn ingest<S>(
+        &self,
+        stream: S,
+        policy: SourcePolicy,
+    ) -> impl Future<Output = Result<SourceStats, ClientError>> + Send
+    where
+        S: Stream<Item = UpdateData> + Send,
+    {
+        ingest(self, stream, policy)
+    }
+}
+
+async fn ingest<S>(
+    table: &Table,
+    stream: S,
+    policy: SourcePolicy,
+) -> Result<SourceStats, ClientError>
+where
+    S: Stream<Item = UpdateData> + Send,
+{
+    let metrics = &policy.metrics.0;
+    let mut stream = std::pin::pin!(stream);
+    let mut done = false;
+    while !done {
+        let Some(first) = stream.next().await else {
+            break;
+        };
+
+        let mut batch = vec![first];
+        let deadline = Instant::now() + policy.max_batch_delay;
+        while batch.len() < policy.max_batch_size {
+            match tokio::time::timeout_at(deadline, stream.next()).await {
+                Ok(Some(item)) => batch.push(item),
+                Ok(None) => {
+                    done = true;
+                    break;
+                },
+                Err(_) => break,
+            }
+        }
+
+        SourceMetrics::incr(&metrics.items, batch.len() as u64);
+        SourceMetrics::incr(&metrics.batches, 1);
+        for update in coalesce(batch) {
+            match table.update(update, policy.update_options.clone()).await {
+                Ok(()) => SourceMetrics::incr(&metrics.updates, 1),
+                Err(err) => {
+                    SourceMetrics::incr(&metrics.errors, 1);
+                    if policy.on_error == OnError::Stop {
+                        return Err(err);
+                    }
+
+                    tracing::warn!("Skipped failed source update: {}", err);
+                },
+            }
+        }
+    }
+
+    Ok(policy.metrics.snapshot())
+}
+
+/// Merge consecutive [`UpdateData::Ndjson`] items, and consecutive
+/// [`UpdateData::Csv`] items with the same header line, so a batch costs as
+/// few [`Table::update`] calls as possible. Other items are left as-is.
+fn coalesce(batch: Vec<UpdateData>) -> Vec<UpdateData> {
+    let mut out: Vec<UpdateData> = Vec::with_capacity(batch.len());
+    for item in batch {
+        let merged = match (out.last_mut(), &item) {
+            (Some(UpdateData::Ndjson(acc)), UpdateData::Ndjson(next)) => {
+                push_line(acc, next);
+                true
+            },
+            (Some(UpdateData::Csv(acc)), UpdateData::Csv(next)) => {
+                match (acc.split_once('\n'), next.split_once('\n')) {
+                    (Some((header, _)), Some((next_header, body))) if header == next_header => {
+                        push_line(acc, body);
+                        true
+                    },
+                    _ => false,
+                }
+            },
+            _ => false,
+        };
+
+        if !merged {
+            out.push(item);
+        }
+    }
+
+    out
+}
+
+fn push_line(acc: &mut String, line: &str) {
+    if !acc.ends_with('\n') {
+        acc.push('\n');
+    }
+
+    acc.push_str(line);
+}
+
+/// The format of each line read by [`from_lines`] and [`tail_file`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineFormat {
+    /// Each line is a JSON object, emitted as [`UpdateData::Ndjson`].
+    #[default]
+    Ndjson,
+
+    /// The first line is a CSV header, and each following line is emitted as
+    /// [`UpdateData::Csv`] with that header.
+    Csv,
+}
+
+impl LineFormat {
+    fn item(&self, header: Option<&str>, line: &str) -> UpdateData {
+        match (self, header) {
+            (LineFormat::Csv, Some(header)) => UpdateData::Csv(format!("{}\n{}", header, line)),
+            _ => UpdateData::Ndjson(line.to_owned()),
+        }
+    }
+}
+
+/// A [`Stream`] of the items sent to a [`mpsc::Receiver`], which ends when
+/// every sender is dropped.
+pub fn from_mpsc(rx: mpsc::Receiver<UpdateData>) -> impl Stream<Item = UpdateData> {
+    ReceiverStream::new(rx)
+}
+
+/// A [`Stream`] of the non-empty lines of `reader`, in `format`. The stream
+/// ends at EOF, or on the first read error (which is logged).
+pub fn from_lines<R>(reader: R, format: LineFormat) -> impl Stream<Item = UpdateData>
+where
+    R: AsyncRead + Unpin,
+{
+    let lines = BufReader::new(reader).lines();
+    stream::unfold(
+        (lines, None::<String>),
+        move |(mut lines, mut header)| async move {
+            loop {
+                match lines.next_line().await {
+                    Ok(Some(line)) if line.trim().is_empty() => (),
+                    Ok(Some(line)) if format == LineFormat::Csv && header.is_none() => {
+                        header = Some(line);
+                    },
+                    Ok(Some(line)) => {
+                        let item = format.item(header.as_deref(), &line);
+                        return Some((item, (lines, header)));
+                    },
+                    Ok(None) => return None,
+                    Err(err) => {
+                        tracing::error!("Source read failed: {}", err);
+                        return None;
+                    },
+                }
+            }
+        },
+    )
+}
+
+/// Options for [`tail_file`].
+#[derive(Clone, Copy, Debug)]
+pub struct TailOptions {
+    pub format: LineFormat,
+
+    /// Emit the lines already in the file, rather than only those appended
+    /// after [`tail_file`] is called.
+    pub from_start: bool,
+
+    /// How often to check the file for new lines at EOF.
+    pub poll_interval: Duration,
+}
+
+impl Default for TailOptions {
+    fn default() -> Self {
+        Self {
+            format: LineFormat::default(),
+            from_start: false,
+            poll_interval: Duration::from_millis(250),
+        }
+    }
+}
+
+/// A [`Stream`] of the lines appended to the file at `path`, like
+/// `tail -f`. For [`LineFormat::Csv`], the header is always read from the
+/// start of the file. Incomplete lines are held until their newline is
+/// written. The stream never ends on its own, except on a read error (which
+/// is logged); truncation and rotation are not detected.
+pub async fn tail_file(
+    path: impl AsRef<Path>,
+    options: TailOptions,
+) -> io::Result<impl Stream<Item = UpdateData>> {
+    let mut reader = BufReader::new(File::open(path).await?);
+    let mut header = String::new();
+    if options.format == LineFormat::Csv {
+        reader.read_line(&mut header).await?;
+        if header.trim().is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Missing CSV header",
+            ));
+        }
+    }
+
+    let header = Some(header.trim_end().to_owned()).filter(|x| !x.is_empty());
+    if !options.from_start {
+        reader.seek(io::SeekFrom::End(0)).await?;
+    }
+
+    Ok(tail_reader(reader, header, options))
+}
+
+/// The lines appended to a file from `reader`'s current position, see
+/// [`tail_file`].
+pub(crate) fn tail_reader(
+    reader: BufReader<File>,
+    header: Option<String>,
+    options: TailOptions,
+) -> impl Stream<Item = UpdateData> + Send + 'static {
+    let state = (reader, String::new());
+    stream::unfold(state, move |(mut reader, mut buf)| {
+        let header = header.clone();
+        async move {
+            loop {
+                match reader.read_line(&mut buf).await {
+                    Ok(0) => tokio::time::sleep(options.poll_interval).await,
+                    Ok(_) if buf.ends_with('\n') => {
+                        let line = std::mem::take(&mut buf);
+                        let line = line.trim_end();
+                        if !line.is_empty() {
+                            let item = options.format.item(header.as_deref(), line);
+                            return Some((item, (reader, buf)));
+                        }
+                    },
+                    Ok(_) => (),
+                    Err(err) => {
+                        tracing::error!("Tail of file failed: {}", err);
+                        return None;
+                    },
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,833 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! A [`VirtualServer`] which serves the tables (and views) of a
+//! [SQLite](https://sqlite.org) database, as a reference for implementing
+//! virtual backends. Serve it to a [`crate::client::Client`] with a
+//! [`VirtualSession`](crate::client::VirtualSession).
+//!
+//! - Table schemas are read with `PRAGMA table_info`, mapping each column's
+//!   declared type to a [`ColumnType`] by its
+//!   [affinity](https://sqlite.org/datatype3.html#determination_of_column_affinity),
+//!   see [`column_type`]. SQLite integers are 64-bit, so `INTEGER` columns
+//!   with values outside `i32` (e.g. rowids or epoch seconds) are `float`.
+//! - Views are translated to SQL with [`generate_sql`], and `split_by` values
+//!   are queried with [`generate_split_by_sql`] when the view is created.
+//! - Flat views are queried for each window with `LIMIT`/`OFFSET`, and counted
+//!   for each dimensions request, so they follow changes to the table.
+//!   `group_by` views are queried once per level when they are created, and
+//!   assembled into the same tree of total rows the engine emits.
+//! - Windows are encoded as Arrow with the types the engine uses.
+//!
+//! `expressions`, and aggregates SQLite lacks (e.g. `median`), are rejected
+//! when the view is created. Queries run on the [`tokio`] blocking thread
+//! pool, one at a time.
+//!
+//! ```rust,ignore
+//! let server = Arc::new(SqliteServer::open("trades.db")?);
+//! let session = VirtualSession::new(server, send_response);
+//! ```
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use arrow_array::types::Int32Type;
+use arrow_array::{
+    ArrayRef, BooleanArray, Date32Array, DictionaryArray, Float64Array, Int32Array, RecordBatch,
+    RecordBatchOptions, TimestampMillisecondArray,
+};
+use arrow_ipc::writer::StreamWriter;
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+pub use rusqlite;
+use rusqlite::Connection;
+use rusqlite::types::Value;
+
+use crate::client::config::{Aggregate, Scalar, SingleAggregate, ViewConfig, ViewConfigUpdate};
+use crate::client::proto::get_features_resp::ColumnTypeOptions;
+use crate::client::proto::{GetFeaturesResp, ViewDimensionsResp, ViewPort};
+use crate::client::{
+    ColumnType, GeneratedSql, SqlDialect, SqlOptions, VirtualServer, VirtualServerResult,
+    generate_split_by_sql, generate_sql,
+};
+
+const NUMBER_FILTER_OPS: &[&str] = &["==", "!=", ">", ">=", "<", "<=", "is not null", "is null"];
+
+const STRING_FILTER_OPS: &[&str] = &[
+    "==",
+    "!=",
+    ">",
+    ">=",
+    "<",
+    "<=",
+    "begins with",
+    "contains",
+    "ends with",
+    "in",
+    "not in",
+    "is not null",
+    "is null",
+];
+
+const BOOLEAN_FILTER_OPS: &[&str] = &["==", "!=", "is not null", "is null"];
+
+const MS_PER_DAY: i64 = 86_400_000;
+
+/// The [`ColumnType`] of a SQLite column declared as `decl`, by its affinity.
+/// `NUMERIC` affinity columns declared as a date, datetime or boolean keep
+/// that type.
+pub fn column_type(decl: &str) -> ColumnType {
+    let decl = decl.to_uppercase();
+    let has = |names: &[&str]| names.iter().any(|x| decl.contains(x));
+    if has(&["INT"]) {
+        ColumnType::Integer
+    } else if has(&["CHAR", "CLOB", "TEXT", "BLOB"]) || decl.is_empty() {
+        ColumnType::String
+    } else if has(&["REAL", "FLOA", "DOUB"]) {
+        ColumnType::Float
+    } else if has(&["BOOL"]) {
+        ColumnType::Boolean
+    } else if has(&["DATETIME", "TIMESTAMP"]) {
+        ColumnType::Datetime
+    } else if has(&["DATE"]) {
+        ColumnType::Date
+    } else {
+        ColumnType::Float
+    }
+}
+
+/// A row's `group_by` path and column values.
+type Row = (Vec<Value>, Vec<Value>);
+
+enum ViewRows {
+    /// Flat views are queried for each window.
+    Flat { sql: String },
+
+    /// The rows of a `group_by` view, in tree order.
+    Grouped(Vec<Row>),
+}
+
+struct SqliteView {
+    table_id: String,
+    config: ViewConfig,
+
+    /// The `group_by` columns and their types.
+    group_by: Vec<(String, ColumnType)>,
+
+    /// The column paths (after `split_by`) and their types.
+    columns: Vec<(String, ColumnType)>,
+
+    /// The types of `config.columns` (after aggregation).
+    schema: HashMap<String, ColumnType>,
+    rows: ViewRows,
+}
+
+/// A [`VirtualServer`] for the tables and views of a SQLite database.
+pub struct SqliteServer {
+    conn: Arc<Mutex<Connection>>,
+    views: Mutex<HashMap<String, Arc<SqliteView>>>,
+}
+
+impl SqliteServer {
+    pub fn new(conn: Connection) -> Self {
+        Self {
+            conn: Arc::new(Mutex::new(conn)),
+            views: Mutex::default(),
+        }
+    }
+
+    /// Open (or create) the database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        Connection::open(path).map(Self::new)
+    }
+
+    /// Open a new in-memory database.
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Connection::open_in_memory().map(Self::new)
+    }
+
+    /// Run `f` with the database connection, e.g. to create or load tables.
+    /// Existing views are not updated.
+    pub fn with_connection<T>(&self, f: impl FnOnce(&Connection) -> T) -> T {
+        f(&self.conn.lock().unwrap())
+    }
+
+    /// Run `f` with the database connection on the blocking thread pool, so
+    /// a long query doesn't stall the calling task's executor.
+    async fn blocking<T, F>(&self, f: F) -> VirtualServerResult<T>
+    where
+        F: FnOnce(&Connection) -> VirtualServerResult<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || f(&conn.lock().unwrap())).await?
+    }
+
+    fn view(&self, view_id: &str) -> VirtualServerResult<Arc<SqliteView>> {
+        self.views
+            .lock()
+            .unwrap()
+            .get(view_id)
+            .cloned()
+            .ok_or_else(|| format!("Unknown view `{}`", view_id).into())
+    }
+
+    /// The rows of `view` within `viewport`, and the range of its columns.
+    async fn window(
+        &self,
+        view: &SqliteView,
+        viewport: &ViewPort,
+    ) -> VirtualServerResult<(Vec<Row>, Range<usize>)> {
+        let num_cols = view.columns.len();
+        let start_col = (viewport.start_col.unwrap_or(0) as usize).min(num_cols);
+        let end_col = viewport
+            .end_col
+            .map_or(num_cols, |x| (x as usize).min(num_cols))
+            .max(start_col);
+
+        let start_row = viewport.start_row.unwrap_or(0) as usize;
+        let rows = match &view.rows {
+            ViewRows::Flat { sql, .. } => {
+                let limit = viewport
+                    .end_row
+                    .map_or(-1, |x| (x as i64 - start_row as i64).max(0));
+
+                let sql = format!("{}\nLIMIT {} OFFSET {}", sql, limit, start_row);
+                self.blocking(move |conn| Ok(query(conn, &sql)?))
+                    .await?
+                    .into_iter()
+                    .map(|values| (vec![], values))
+                    .collect()
+            },
+            ViewRows::Grouped(rows) => {
+                let end_row = viewport
+                    .end_row
+                    .map_or(rows.len(), |x| (x as usize).min(rows.len()));
+
+                rows.get(start_row..end_row.max(start_row))
+                    .unwrap_or_default()
+                    .to_vec()
+            },
+        };
+
+        Ok((rows, start_col..end_col))
+    }
+}
+
+impl VirtualServer for SqliteServer {
+    fn get_features(&self) -> GetFeaturesResp {
+        let options = |ops: &[&str]| ColumnTypeOptions {
+            options: ops.iter().map(|x| x.to_string()).collect(),
+        };
+
+        let filter_ops = [
+            (ColumnType::String, options(STRING_FILTER_OPS)),
+            (ColumnType::Date, options(NUMBER_FILTER_OPS)),
+            (ColumnType::Datetime, options(NUMBER_FILTER_OPS)),
+            (ColumnType::Integer, options(NUMBER_FILTER_OPS)),
+            (ColumnType::Float, options(NUMBER_FILTER_OPS)),
+            (ColumnType::Boolean, options(BOOLEAN_FILTER_OPS)),
+        ];
+
+        GetFeaturesResp {
+            group_by: true,
+            split_by: true,
+            expressions: false,
+            filter_ops: filter_ops
+                .into_iter()
+                .map(|(column_type, ops)| (column_type as u32, ops))
+                .collect(),
+        }
+    }
+
+    async fn get_hosted_tables(&self) -> VirtualServerResult<Vec<String>> {
+        self.blocking(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT name FROM sqlite_master WHERE type IN ('table', 'view') AND name NOT LIKE \
+                 'sqlite_%' ORDER BY name",
+            )?;
+
+            let names = stmt
+                .query_map([], |row| row.get(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(names)
+        })
+        .await
+    }
+
+    async fn table_schema(&self, table_id: &str) -> VirtualServerResult<Vec<(String, ColumnType)>> {
+        let table_id = table_id.to_owned();
+        self.blocking(move |conn| table_schema(conn, &table_id))
+            .await
+    }
+
+    async fn table_size(&self, table_id: &str) -> VirtualServerResult<u32> {
+        let table_id = table_id.to_owned();
+        self.blocking(move |conn| table_size(conn, &table_id)).await
+    }
+
+    async fn table_make_view(
+        &self,
+        table_id: &str,
+        view_id: &str,
+        config: ViewConfigUpdate,
+    ) -> VirtualServerResult<()> {
+        let table_id = table_id.to_owned();
+        let view = self
+            .blocking(move |conn| make_view(conn, &table_id, config))
+            .await?;
+
+        self.views
+            .lock()
+            .unwrap()
+            .insert(view_id.to_owned(), Arc::new(view));
+
+        Ok(())
+    }
+
+    async fn view_get_config(&self, view_id: &str) -> VirtualServerResult<ViewConfig> {
+        Ok(self.view(view_id)?.config.clone())
+    }
+
+    async fn view_schema(&self, view_id: &str) -> VirtualServerResult<HashMap<String, ColumnType>> {
+        Ok(self.view(view_id)?.schema.clone())
+    }
+
+    async fn view_column_paths(&self, view_id: &str) -> VirtualServerResult<Vec<String>> {
+        let view = self.view(view_id)?;
+        let row_path = (!view.group_by.is_empty()).then(|| "__ROW_PATH__".to_owned());
+        let paths = view.columns.iter().map(|(name, _)| name.clone());
+        Ok(row_path.into_iter().chain(paths).collect())
+    }
+
+    async fn view_dimensions(&self, view_id: &str) -> VirtualServerResult<ViewDimensionsResp> {
+        let view = self.view(view_id)?;
+        self.blocking(move |conn| {
+            Ok(ViewDimensionsResp {
+                num_table_rows: table_size(conn, &view.table_id)?,
+                num_table_columns: declared_schema(conn, &view.table_id)?.len() as u32,
+                num_view_rows: match &view.rows {
+                    ViewRows::Flat { sql } => {
+                        let count = format!("SELECT COUNT(*) FROM ({})", sql);
+                        conn.query_row(&count, [], |row| row.get(0))?
+                    },
+                    ViewRows::Grouped(rows) => rows.len() as u32,
+                },
+                num_view_columns: view.columns.len() as u32,
+            })
+        })
+        .await
+    }
+
+    async fn view_to_arrow(
+        &self,
+        view_id: &str,
+        viewport: ViewPort,
+    ) -> VirtualServerResult<Vec<u8>> {
+        let view = self.view(view_id)?;
+        let (rows, columns) = self.window(&view, &viewport).await?;
+        let mut fields = vec![];
+        let mut arrays = vec![];
+        for (i, (name, column_type)) in view.group_by.iter().enumerate() {
+            let name = format!("{} (Group by {})", name, i + 1);
+            fields.push(Field::new(name, data_type(*column_type), true));
+            arrays.push(to_array(
+                *column_type,
+                rows.iter().map(|(path, _)| path.get(i)),
+            ));
+        }
+
+        for i in columns {
+            let (name, column_type) = &view.columns[i];
+            fields.push(Field::new(name, data_type(*column_type), true));
+            arrays.push(to_array(
+                *column_type,
+                rows.iter().map(|(_, values)| values.get(i)),
+            ));
+        }
+
+        let options = RecordBatchOptions::new().with_row_count(Some(rows.len()));
+        let batch =
+            RecordBatch::try_new_with_options(Arc::new(Schema::new(fields)), arrays, &options)?;
+        let mut writer = StreamWriter::try_new(vec![], &batch.schema())?;
+        writer.write(&batch)?;
+        writer.finish()?;
+        Ok(writer.into_inner()?)
+    }
+
+    async fn view_to_columns_string(
+        &self,
+        view_id: &str,
+        viewport: ViewPort,
+    ) -> VirtualServerResult<String> {
+        let view = self.view(view_id)?;
+        let (rows, columns) = self.window(&view, &viewport).await?;
+        let mut json = serde_json::Map::new();
+        if !view.group_by.is_empty() {
+            let paths = rows
+                .iter()
+                .map(|(path, _)| {
+                    path.iter()
+                        .zip(&view.group_by)
+                        .map(|(value, (_, column_type))| to_json(*column_type, Some(value)))
+                        .collect()
+                })
+                .collect();
+
+            json.insert("__ROW_PATH__".to_owned(), serde_json::Value::Array(paths));
+        }
+
+        for i in columns {
+            let (name, column_type) = &view.columns[i];
+            let values = rows
+                .iter()
+                .map(|(_, values)| to_json(*column_type, values.get(i)))
+                .collect();
+
+            json.insert(name.clone(), serde_json::Value::Array(values));
+        }
+
+        Ok(serde_json::Value::Object(json).to_string())
+    }
+
+    async fn view_delete(&self, view_id: &str) -> VirtualServerResult<()> {
+        self.views.lock().unwrap().remove(view_id);
+        Ok(())
+    }
+}
+
+fn quote(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn query(conn: &Connection, sql: &str) -> rusqlite::Result<Vec<Vec<Value>>> {
+    let mut stmt = conn.prepare(sql)?;
+    let width = stmt.column_count();
+    let rows = stmt
+        .query_map([], |row| (0..width).map(|i| row.get(i)).collect())?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(rows)
+}
+
+/// The schema of `table_id` by its declared column types, see
+/// [`column_type`].
+fn declared_schema(
+    conn: &Connection,
+    table_id: &str,
+) -> VirtualServerResult<Vec<(String, ColumnType)>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", quote(table_id)))?;
+    let schema = stmt
+        .query_map([], |row| {
+            let decl: String = row.get(2)?;
+            Ok((row.get(1)?, column_type(&decl)))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if schema.is_empty() {
+        return Err(format!("Unknown table `{}`", table_id).into());
+    }
+
+    Ok(schema)
+}
+
+/// The [`declared_schema`] of `table_id`, with [`ColumnType::Integer`] columns
+/// whose values don't fit in an `i32` as [`ColumnType::Float`] instead (which
+/// is exact up to 2^53).
+fn table_schema(
+    conn: &Connection,
+    table_id: &str,
+) -> VirtualServerResult<Vec<(String, ColumnType)>> {
+    let mut schema = declared_schema(conn, table_id)?;
+    let integers = schema
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, column_type))| *column_type == ColumnType::Integer)
+        .map(|(idx, (name, _))| (idx, quote(name)))
+        .collect::<Vec<_>>();
+
+    if integers.is_empty() {
+        return Ok(schema);
+    }
+
+    let bounds = integers
+        .iter()
+        .map(|(_, name)| format!("MIN(CAST({0} AS REAL)), MAX(CAST({0} AS REAL))", name))
+        .collect::<Vec<_>>();
+
+    let sql = format!("SELECT {} FROM {}", bounds.join(", "), quote(table_id));
+    let bounds = conn.query_row(&sql, [], |row| {
+        (0..bounds.len() * 2)
+            .map(|idx| row.get::<_, Option<f64>>(idx))
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+
+    let range = i32::MIN as f64..=i32::MAX as f64;
+    for ((idx, _), bounds) in integers.iter().zip(bounds.chunks(2)) {
+        if !bounds.iter().flatten().all(|x| range.contains(x)) {
+            schema[*idx].1 = ColumnType::Float;
+        }
+    }
+
+    Ok(schema)
+}
+
+fn table_size(conn: &Connection, table_id: &str) -> VirtualServerResult<u32> {
+    let sql = format!("SELECT COUNT(*) FROM {}", quote(table_id));
+    Ok(conn.query_row(&sql, [], |row| row.get(0))?)
+}
+
+fn translated(sql: GeneratedSql) -> VirtualServerResult<String> {
+    if sql.untranslated.is_empty() {
+        Ok(sql.sql)
+    } else {
+        Err(format!("Not supported by SQLite: {}", sql.untranslated.join(", ")).into())
+    }
+}
+
+fn make_view(
+    conn: &Connection,
+    table_id: &str,
+    update: ViewConfigUpdate,
+) -> VirtualServerResult<SqliteView> {
+    let table_schema = table_schema(conn, table_id)?;
+    let types = table_schema.iter().cloned().collect::<HashMap<_, _>>();
+    let type_of = |name: &str| {
+        types
+            .get(name)
+            .copied()
+            .ok_or_else(|| format!("Unknown column `{}`", name))
+    };
+
+    // `apply_update` leaves these alone.
+    let (filter_op, group_by_depth) = (update.filter_op.clone(), update.group_by_depth);
+    let all_columns = update.columns.is_none();
+    let mut config = ViewConfig::default();
+    config.apply_update(update);
+    config.filter_op = filter_op.unwrap_or_default();
+    config.group_by_depth = group_by_depth;
+    if all_columns {
+        config.columns = table_schema
+            .iter()
+            .map(|(name, _)| Some(name.clone()))
+            .collect();
+    }
+
+    if !config.expressions.is_empty() {
+        return Err("Expressions are not supported".into());
+    }
+
+    let grouped = !config.group_by.is_empty();
+    let mut schema = HashMap::new();
+    for name in config.columns.iter().flatten() {
+        let column_type = type_of(name)?;
+        let column_type = if grouped {
+            let aggregate = config
+                .aggregates
+                .entry(name.clone())
+                .or_insert_with(|| column_type.default_aggregate());
+
+            aggregate_type(aggregate, column_type)
+        } else {
+            column_type
+        };
+
+        schema.insert(name.clone(), column_type);
+    }
+
+    let mut options = SqlOptions {
+        dialect: SqlDialect::Sqlite,
+        schema: types.clone(),
+        split_by_values: vec![],
+    };
+
+    if let Some(sql) = generate_split_by_sql(table_id, &config, &options) {
+        let split_by_types = config
+            .split_by
+            .iter()
+            .map(|x| type_of(x))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        options.split_by_values = query(conn, &translated(sql)?)?
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&split_by_types)
+                    .map(|(value, column_type)| to_scalar(*column_type, value))
+                    .collect()
+            })
+            .collect();
+    }
+
+    let mut columns = vec![];
+    let prefixes = options
+        .split_by_values
+        .iter()
+        .map(|values| values.iter().map(|x| format!("{}|", x)).collect::<String>());
+
+    for prefix in prefixes.chain(config.split_by.is_empty().then(String::new)) {
+        for name in config.columns.iter().flatten() {
+            columns.push((format!("{}{}", prefix, name), schema[name]));
+        }
+    }
+
+    let rows = if grouped {
+        ViewRows::Grouped(rollup(conn, table_id, &config, &options)?)
+    } else {
+        ViewRows::Flat {
+            sql: translated(generate_sql(table_id, &config, &options))?,
+        }
+    };
+
+    let group_by = config
+        .group_by
+        .iter()
+        .map(|name| Ok((name.clone(), type_of(name)?)))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(SqliteView {
+        table_id: table_id.to_owned(),
+        config,
+        group_by,
+        columns,
+        schema,
+        rows,
+    })
+}
+
+/// Query each level of a `group_by` view, and assemble the rows in tree order:
+/// each row followed by its children, in the order of their level's query.
+fn rollup(
+    conn: &Connection,
+    table_id: &str,
+    config: &ViewConfig,
+    options: &SqlOptions,
+) -> VirtualServerResult<Vec<Row>> {
+    let max_depth = config.group_by_depth.map_or(config.group_by.len(), |x| {
+        (x as usize).min(config.group_by.len())
+    });
+
+    let mut total = None;
+    let mut children: HashMap<String, Vec<Row>> = HashMap::new();
+    for depth in 0..=max_depth {
+        let config = ViewConfig {
+            group_by_depth: Some(depth as u32),
+            ..config.clone()
+        };
+
+        let sql = translated(generate_sql(table_id, &config, options))?;
+        for mut values in query(conn, &sql)? {
+            let path = values.drain(..depth).collect::<Vec<_>>();
+            if depth == 0 {
+                total.get_or_insert((path, values));
+            } else {
+                let parent = format!("{:?}", &path[..depth - 1]);
+                children.entry(parent).or_default().push((path, values));
+            }
+        }
+    }
+
+    fn push_tree(row: Row, children: &mut HashMap<String, Vec<Row>>, rows: &mut Vec<Row>) {
+        let key = format!("{:?}", row.0);
+        rows.push(row);
+        for child in children.remove(&key).unwrap_or_default() {
+            push_tree(child, children, rows);
+        }
+    }
+
+    let mut rows = vec![];
+    push_tree(total.unwrap_or_default(), &mut children, &mut rows);
+    Ok(rows)
+}
+
+/// The type of `column_type` values aggregated by `aggregate`.
+fn aggregate_type(aggregate: &Aggregate, column_type: ColumnType) -> ColumnType {
+    use SingleAggregate::*;
+    match aggregate {
+        Aggregate::MultiAggregate(..) => ColumnType::Float,
+        Aggregate::SingleAggregate(Count | DistinctCount) => ColumnType::Integer,
+        Aggregate::SingleAggregate(Avg | Mean | StdDev | Var | PctSumParent | PctSumGrandTotal) => {
+            ColumnType::Float
+        },
+        Aggregate::SingleAggregate(Join) => ColumnType::String,
+        Aggregate::SingleAggregate(_) => column_type,
+    }
+}
+
+fn data_type(column_type: ColumnType) -> DataType {
+    match column_type {
+        ColumnType::String => {
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+        },
+        ColumnType::Date => DataType::Date32,
+        ColumnType::Datetime => DataType::Timestamp(TimeUnit::Millisecond, None),
+        ColumnType::Integer => DataType::Int32,
+        ColumnType::Float => DataType::Float64,
+        ColumnType::Boolean => DataType::Boolean,
+    }
+}
+
+fn to_array<'a>(
+    column_type: ColumnType,
+    values: impl Iterator<Item = Option<&'a Value>>,
+) -> ArrayRef {
+    let values = values.map(|x| x.filter(|x| !matches!(x, Value::Null)));
+    match column_type {
+        ColumnType::String => {
+            let values = values.map(|x| x.map(to_string)).collect::<Vec<_>>();
+            Arc::new(
+                values
+                    .iter()
+                    .map(Option::as_deref)
+                    .collect::<DictionaryArray<Int32Type>>(),
+            )
+        },
+        ColumnType::Date => Arc::new(values.map(|x| x.and_then(to_date)).collect::<Date32Array>()),
+        ColumnType::Datetime => Arc::new(
+            values
+                .map(|x| x.and_then(to_datetime))
+                .collect::<TimestampMillisecondArray>(),
+        ),
+        // Values which don't fit (e.g. written after the schema was read) are
+        // null rather than wrapped.
+        ColumnType::Integer => Arc::new(
+            values
+                .map(|x| x.and_then(to_i64).and_then(|x| i32::try_from(x).ok()))
+                .collect::<Int32Array>(),
+        ),
+        ColumnType::Float => Arc::new(values.map(|x| x.and_then(to_f64)).collect::<Float64Array>()),
+        ColumnType::Boolean => Arc::new(
+            values
+                .map(|x| x.and_then(to_bool))
+                .collect::<BooleanArray>(),
+        ),
+    }
+}
+
+/// `value` as JSON, with dates and datetimes as epoch milliseconds like the
+/// engine's `to_columns_string`.
+fn to_json(column_type: ColumnType, value: Option<&Value>) -> serde_json::Value {
+    match value {
+        None | Some(Value::Null) => serde_json::Value::Null,
+        Some(value) => match column_type {
+            ColumnType::String => to_string(value).into(),
+            ColumnType::Date => to_date(value).map(|x| x as i64 * MS_PER_DAY).into(),
+            ColumnType::Datetime => to_datetime(value).into(),
+            ColumnType::Integer => to_i64(value).into(),
+            ColumnType::Float => to_f64(value).into(),
+            ColumnType::Boolean => to_bool(value).into(),
+        },
+    }
+}
+
+/// A `split_by` value. Dates and datetimes keep their stored representation,
+/// so they compare equal to it.
+fn to_scalar(column_type: ColumnType, value: &Value) -> Scalar {
+    match (column_type, value) {
+        (_, Value::Null) => Scalar::Null,
+        (ColumnType::Boolean, value) => to_bool(value).map_or(Scalar::Null, Scalar::Bool),
+        (ColumnType::Integer | ColumnType::Float, value) => {
+            to_f64(value).map_or(Scalar::Null, Scalar::Float)
+        },
+        (_, value) => Scalar::String(to_string(value)),
+    }
+}
+
+fn to_string(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Integer(x) => x.to_string(),
+        Value::Real(x) => x.to_string(),
+        Value::Text(x) => x.clone(),
+        Value::Blob(x) => String::from_utf8_lossy(x).into_owned(),
+    }
+}
+
+fn to_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Integer(x) => Some(*x),
+        Value::Real(x) => Some(*x as i64),
+        Value::Text(x) => x.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(x) => Some(*x as f64),
+        Value::Real(x) => Some(*x),
+        Value::Text(x) => x.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn to_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Integer(x) => Some(*x != 0),
+        Value::Real(x) => Some(*x != 0.0),
+        Value::Text(x) => match x.trim().to_lowercase().as_str() {
+            "true" | "t" | "1" => Some(true),
+            "false" | "f" | "0" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Days since the epoch. Text is parsed like [`to_datetime`].
+fn to_date(value: &Value) -> Option<i32> {
+    to_datetime(value).map(|x| x.div_euclid(MS_PER_DAY) as i32)
+}
+
+/// Milliseconds since the epoch, from unix seconds or SQLite's
+/// `YYYY-MM-DD[ HH:MM[:SS[.SSS]]]` time strings (as UTC).
+fn to_datetime(value: &Value) -> Option<i64> {
+    match value {
+        Value::Integer(x) => Some(x * 1000),
+        Value::Real(x) => Some((x * 1000.0) as i64),
+        Value::Text(x) => parse_datetime(x),
+        _ => None,
+    }
+}
+
+fn parse_datetime(text: &str) -> Option<i64> {
+    let text = text.trim().trim_end_matches('Z');
+    let (date, time) = match text.split_once([' ', 'T']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (text, None),
+    };
+
+    let mut date = date.splitn(3, '-').map(str::parse::<i64>);
+    let (y, m, d) = (date.next()?.ok()?, date.next()?.ok()?, date.next()?.ok()?);
+    let mut ms = days_from_civil(y, m, d) * MS_PER_DAY;
+    if let Some(time) = time {
+        let mut time = time.splitn(3, ':');
+        let hours: i64 = time.next()?.parse().ok()?;
+        let minutes: i64 = time.next()?.parse().ok()?;
+        let seconds: f64 = time.next().map_or(Ok(0.0), str::parse).ok()?;
+        ms += (hours * 3600 + minutes * 60) * 1000 + (seconds * 1000.0).round() as i64;
+    }
+
+    Some(ms)
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * (m + if m > 2 { -3 } else { 9 }) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
@@ -0,0 +1,291 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! A [Server-Sent Events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events)
+//! transport for read-only live subscriptions to a hosted table, for
+//! environments where WebSockets are unavailable (e.g. behind proxies which
+//! block them).
+//!
+//! A client `POST`s a JSON [`ViewConfigUpdate`] to [`sse_handler`], which
+//! creates a view of the table on its behalf and responds with an event
+//! stream: first a `snapshot` event with the view's current contents, then an
+//! `update` event for each [`crate::client::View::on_update`] delta. Events
+//! are NDJSON rows (the default), or base64-encoded Arrow with the query
+//! parameter `?format=arrow`. The view is deleted when the stream
+//! disconnects. Configs the engine rejects (e.g. for an unknown column) fail
+//! with `400 Bad Request`.
+
+use std::convert::Infallible;
+use std::io::Cursor;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Date32Type, Float32Type, Float64Type, Int32Type, Int64Type, TimestampMillisecondType,
+};
+use arrow_array::{Array, RecordBatch};
+use arrow_ipc::reader::StreamReader;
+use arrow_schema::{ArrowError, DataType, TimeUnit};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::routing::{post, MethodRouter};
+use axum::Json;
+use futures::channel::mpsc::{unbounded, UnboundedReceiver};
+use futures::{stream, Stream, StreamExt};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::axum::client_error_status;
+use crate::client::config::ViewConfigUpdate;
+use crate::client::utils::PerspectiveResultExt;
+use crate::client::{
+    Client, ClientError, OnUpdateMode, OnUpdateOptions, View, ViewOnUpdateResp, ViewWindow,
+};
+use crate::server::{LocalClient, Server};
+
+/// The encoding of the events of an SSE stream.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SseFormat {
+    #[default]
+    Ndjson,
+    Arrow,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SseParams {
+    #[serde(default)]
+    format: SseFormat,
+}
+
+/// The view (and client) backing a single SSE stream, which are cleaned up
+/// when the stream is dropped.
+struct Subscription {
+    client: Option<LocalClient>,
+    view: View,
+    update_id: u32,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            let view = self.view.clone();
+            let update_id = self.update_id;
+            tokio::spawn(async move {
+                view.remove_update(update_id).await.unwrap_or_log();
+                view.delete().await.unwrap_or_log();
+                client.close().await;
+            });
+        }
+    }
+}
+
+/// Encode a single cell of an engine Arrow column as JSON, in the same
+/// format as [`View::to_ndjson`] (i.e. dates and datetimes as epoch
+/// milliseconds, and `NaN` as `null`).
+fn cell_to_json(array: &dyn Array, row: usize) -> Result<Value, ArrowError> {
+    if array.is_null(row) {
+        return Ok(Value::Null);
+    }
+
+    Ok(match array.data_type() {
+        DataType::Boolean => array.as_boolean().value(row).into(),
+        DataType::Int32 => array.as_primitive::<Int32Type>().value(row).into(),
+        DataType::Int64 => array.as_primitive::<Int64Type>().value(row).into(),
+        DataType::Float32 => f64::from(array.as_primitive::<Float32Type>().value(row)).into(),
+        DataType::Float64 => array.as_primitive::<Float64Type>().value(row).into(),
+        DataType::Date32 => {
+            (i64::from(array.as_primitive::<Date32Type>().value(row)) * 86_400_000).into()
+        },
+        DataType::Timestamp(TimeUnit::Millisecond, _) => array
+            .as_primitive::<TimestampMillisecondType>()
+            .value(row)
+            .into(),
+        DataType::Utf8 => array.as_string::<i32>().value(row).into(),
+        DataType::Dictionary(..) => {
+            let dict = array
+                .as_dictionary_opt::<Int32Type>()
+                .ok_or_else(|| ArrowError::CastError("Expected Int32 dictionary".to_owned()))?;
+
+            let key = dict.keys().value(row) as usize;
+            cell_to_json(dict.values(), key)?
+        },
+        dtype => {
+            return Err(ArrowError::NotYetImplemented(format!(
+                "NDJSON encoding of {}",
+                dtype
+            )));
+        },
+    })
+}
+
+/// Encode the rows of `batch` as NDJSON, appending them to `ndjson`.
+fn batch_to_ndjson(batch: &RecordBatch, ndjson: &mut String) -> Result<(), ArrowError> {
+    let schema = batch.schema();
+    for row in 0..batch.num_rows() {
+        let mut cells = Vec::with_capacity(batch.num_columns());
+        for (field, column) in schema.fields().iter().zip(batch.columns()) {
+            let name = Value::from(field.name().as_str());
+            cells.push(format!("{}:{}", name, cell_to_json(column, row)?));
+        }
+
+        ndjson.push('{');
+        ndjson.push_str(&cells.join(","));
+        ndjson.push_str("}\n");
+    }
+
+    Ok(())
+}
+
+/// Decode an Arrow `delta` and encode its rows as NDJSON.
+fn arrow_to_ndjson(delta: &[u8]) -> Result<String, ArrowError> {
+    let mut ndjson = String::new();
+    for batch in StreamReader::try_new(Cursor::new(delta), None)? {
+        batch_to_ndjson(&batch?, &mut ndjson)?;
+    }
+
+    Ok(ndjson)
+}
+
+async fn snapshot(view: &View, format: SseFormat) -> Result<Event, ClientError> {
+    let data = match format {
+        SseFormat::Ndjson => view.to_ndjson(ViewWindow::default()).await?,
+        SseFormat::Arrow => base64::encode(view.to_arrow(ViewWindow::default()).await?),
+    };
+
+    Ok(Event::default().event("snapshot").data(data))
+}
+
+/// Encode an [`crate::client::View::on_update`] delta as an `update` event.
+fn update(delta: Vec<u8>, format: SseFormat) -> Result<Event, ArrowError> {
+    let data = match format {
+        SseFormat::Ndjson => arrow_to_ndjson(&delta)?,
+        SseFormat::Arrow => base64::encode(delta),
+    };
+
+    Ok(Event::default().event("update").data(data))
+}
+
+/// Open a view of `name` and subscribe to its updates, returning the view,
+/// its subscription id, its initial `snapshot` event and a receiver of its
+/// update deltas. Deltas are encoded as events by the stream's consumer,
+/// rather than in the `on_update` callback which runs while the updating
+/// session is still handling its request.
+///
+/// The subscription is registered before the snapshot is taken, so an update
+/// applied in between is buffered by the receiver and sent after the
+/// snapshot rather than lost (its rows may then appear in both).
+async fn subscribe(
+    client: &Client,
+    name: String,
+    config: ViewConfigUpdate,
+    format: SseFormat,
+) -> Result<(View, u32, Event, UnboundedReceiver<Vec<u8>>), ClientError> {
+    let view = client.open_table(name).await?.view(Some(config)).await?;
+    let (sender, receiver) = unbounded::<Vec<u8>>();
+    let on_update = move |resp: ViewOnUpdateResp| {
+        if let Some(delta) = resp.delta {
+            sender.unbounded_send(delta).unwrap_or_log();
+        }
+
+        async {}
+    };
+
+    let options = OnUpdateOptions {
+        mode: Some(OnUpdateMode::Row),
+    };
+
+    let update_id = view.on_update(on_update, options).await?;
+    let snapshot = snapshot(&view, format).await?;
+    Ok((view, update_id, snapshot, receiver))
+}
+
+/// Create a [`Subscription`] to the table `name` as a stream of SSE
+/// [`Event`]s, which ends the [`Subscription`] when dropped.
+async fn event_stream(
+    client: LocalClient,
+    name: String,
+    config: ViewConfigUpdate,
+    format: SseFormat,
+) -> Result<impl Stream<Item = Result<Event, Infallible>>, ClientError> {
+    let result = subscribe(&client, name, config, format).await;
+    let (view, update_id, snapshot, receiver) = match result {
+        Ok(x) => x,
+        Err(e) => {
+            client.close().await;
+            return Err(e);
+        },
+    };
+
+    let subscription = Subscription {
+        client: Some(client),
+        view,
+        update_id,
+    };
+
+    let updates = stream::unfold(
+        (receiver, subscription),
+        move |(mut receiver, sub)| async move {
+            loop {
+                let delta = receiver.next().await?;
+                match update(delta, format) {
+                    Ok(event) => return Some((Ok(event), (receiver, sub))),
+                    Err(e) => tracing::error!("Failed to encode delta: {}", e),
+                }
+            }
+        },
+    );
+
+    Ok(stream::once(async { Ok(snapshot) }).chain(updates))
+}
+
+/// An [`axum`] handler for SSE subscriptions to the table named by the
+/// route's single path parameter, e.g.
+///
+/// ```rust,ignore
+/// let app = Router::new()
+///     .route("/tables/:name/events", perspective::sse::sse_handler())
+///     .with_state(server);
+/// ```
+///
+/// See the module documentation for the request and event formats.
+pub fn sse_handler() -> MethodRouter<Server> {
+    async fn sse_handler_internal(
+        State(server): State<Server>,
+        Path(name): Path<String>,
+        Query(params): Query<SseParams>,
+        Json(config): Json<ViewConfigUpdate>,
+    ) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, String)> {
+        let client = server.new_local_client();
+        let exists = client
+            .get_hosted_table_names()
+            .await
+            .map(|names| names.contains(&name));
+
+        match exists {
+            Ok(true) => match event_stream(client, name, config, params.format).await {
+                Ok(events) => Ok(Sse::new(events).keep_alive(KeepAlive::default())),
+                Err(e) => Err((client_error_status(&e), e.to_string())),
+            },
+            Ok(false) => {
+                client.close().await;
+                Err((StatusCode::NOT_FOUND, format!("Unknown table {}", name)))
+            },
+            Err(e) => {
+                client.close().await;
+                Err((client_error_status(&e), e.to_string()))
+            },
+        }
+    }
+
+    post(sse_handler_internal)
+}
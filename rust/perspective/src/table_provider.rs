@@ -0,0 +1,411 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! A [DataFusion](https://datafusion.apache.org/) [`TableProvider`] for a
+//! [`Table`], so live tables can be queried with SQL (and joined with other
+//! DataFusion sources) without exporting a snapshot first.
+//!
+//! Each scan creates a [`crate::client::View`] of the table when it is
+//! executed (so e.g. `EXPLAIN` reads nothing), which pushes down what the
+//! engine can do itself:
+//!
+//! - The projection, as [`ViewConfigUpdate::columns`].
+//! - Comparisons of a column with a literal, `IS [NOT] NULL` and string `[NOT]
+//!   IN` lists, as [`ViewConfigUpdate::filter`]. The engine's string columns
+//!   are dictionary-encoded, so these also match through DataFusion's casts
+//!   between string types. They are reported as
+//!   [`TableProviderFilterPushDown::Inexact`], so DataFusion re-applies them
+//!   and SQL semantics are preserved.
+//! - The limit (when no filter remains above the scan), as
+//!   [`ViewWindow::end_row`].
+//!
+//! ```rust,ignore
+//! let ctx = SessionContext::new();
+//! let provider = PerspectiveTableProvider::try_new(table).await?;
+//! ctx.register_table("trades", Arc::new(provider))?;
+//! let df = ctx.sql("SELECT sym, SUM(qty) FROM trades GROUP BY sym").await?;
+//! ```
+
+use std::any::Any;
+use std::fmt;
+use std::io::Cursor;
+use std::sync::Arc;
+
+use arrow_array::{RecordBatch, RecordBatchOptions};
+use arrow_ipc::reader::StreamReader;
+use arrow_schema::{ArrowError, DataType, Schema, SchemaRef};
+use async_trait::async_trait;
+use bytes::Bytes;
+use datafusion::common::Column;
+use datafusion::datasource::{TableProvider, TableType};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::context::SessionState;
+use datafusion::execution::{SendableRecordBatchStream, TaskContext};
+use datafusion::logical_expr::expr::InList;
+use datafusion::logical_expr::{
+    BinaryExpr, Cast, Expr, Operator, TableProviderFilterPushDown, TryCast,
+};
+use datafusion::physical_expr::EquivalenceProperties;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionMode, ExecutionPlan, Partitioning, PlanProperties,
+};
+use datafusion::scalar::ScalarValue;
+use futures::{TryStreamExt, stream};
+
+use crate::client::config::{Filter, FilterTerm, Scalar, ViewConfigUpdate};
+use crate::client::{ClientError, Table, ViewWindow};
+
+/// A [`TableProvider`] which scans a [`Table`] through a new
+/// [`crate::client::View`] per query.
+pub struct PerspectiveTableProvider {
+    table: Table,
+    schema: SchemaRef,
+}
+
+impl PerspectiveTableProvider {
+    /// Wrap `table`. The Arrow schema is read from an empty
+    /// [`crate::client::View::to_arrow`] so it matches the scanned batches.
+    pub async fn try_new(table: Table) -> Result<Self, ClientError> {
+        let window = ViewWindow {
+            end_row: Some(0.0),
+            ..ViewWindow::default()
+        };
+
+        let (schema, _) = fetch(&table, None, window).await?;
+        Ok(Self { table, schema })
+    }
+}
+
+#[async_trait]
+impl TableProvider for PerspectiveTableProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        _state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let schema = match projection {
+            Some(indices) => Arc::new(self.schema.project(indices)?),
+            None => self.schema.clone(),
+        };
+
+        let mut columns: Vec<String> = schema.fields().iter().map(|x| x.name().clone()).collect();
+
+        // A `View` with no columns has no rows either (e.g. for `COUNT(*)`),
+        // so fetch one column and project it away.
+        if let Some(field) = self.schema.fields().first().filter(|_| columns.is_empty()) {
+            columns.push(field.name().clone());
+        }
+
+        let config = ViewConfigUpdate {
+            columns: Some(columns.into_iter().map(Some).collect()),
+            filter: Some(
+                filters
+                    .iter()
+                    .filter_map(|x| to_filter(x, &self.schema))
+                    .map(Into::into)
+                    .collect(),
+            ),
+            ..ViewConfigUpdate::default()
+        };
+
+        let window = ViewWindow {
+            end_row: limit.map(|x| x as f32),
+            ..ViewWindow::default()
+        };
+
+        Ok(Arc::new(PerspectiveExec::new(
+            self.table.clone(),
+            config,
+            window,
+            schema,
+        )))
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        Ok(filters
+            .iter()
+            .map(|expr| match to_filter(expr, &self.schema) {
+                Some(_) => TableProviderFilterPushDown::Inexact,
+                None => TableProviderFilterPushDown::Unsupported,
+            })
+            .collect())
+    }
+}
+
+/// An [`ExecutionPlan`] which reads a [`crate::client::View`] of a [`Table`]
+/// when it is executed, as a single partition.
+struct PerspectiveExec {
+    table: Table,
+    config: ViewConfigUpdate,
+    window: ViewWindow,
+    schema: SchemaRef,
+    properties: PlanProperties,
+}
+
+impl PerspectiveExec {
+    fn new(table: Table, config: ViewConfigUpdate, window: ViewWindow, schema: SchemaRef) -> Self {
+        let properties = PlanProperties::new(
+            EquivalenceProperties::new(schema.clone()),
+            Partitioning::UnknownPartitioning(1),
+            ExecutionMode::Bounded,
+        );
+
+        Self {
+            table,
+            config,
+            window,
+            schema,
+            properties,
+        }
+    }
+}
+
+impl fmt::Debug for PerspectiveExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PerspectiveExec")
+            .field("table", &self.table.get_name())
+            .field("config", &self.config)
+            .field("window", &self.window)
+            .finish()
+    }
+}
+
+impl DisplayAs for PerspectiveExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        let filter = self.config.filter.iter().flatten();
+        write!(
+            f,
+            "PerspectiveExec: table={}, filter=[{}]",
+            self.table.get_name(),
+            filter.map(|x| x.to_string()).collect::<Vec<_>>().join(", ")
+        )?;
+
+        match self.window.end_row {
+            Some(limit) => write!(f, ", limit={}", limit),
+            None => Ok(()),
+        }
+    }
+}
+
+impl ExecutionPlan for PerspectiveExec {
+    fn name(&self) -> &str {
+        "PerspectiveExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.properties
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        _partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let table = self.table.clone();
+        let config = self.config.clone();
+        let window = self.window.clone();
+        let schema = self.schema.clone();
+        let batches = stream::once(async move {
+            let (_, batches) = fetch(&table, Some(config), window)
+                .await
+                .map_err(|err| DataFusionError::External(Box::new(err)))?;
+
+            // Rebuild each batch with the planned schema, which for an empty
+            // projection drops the one column fetched.
+            let batches = batches.into_iter().map(move |batch| {
+                let columns = match schema.fields().len() {
+                    0 => vec![],
+                    _ => batch.columns().to_vec(),
+                };
+
+                let options = RecordBatchOptions::new().with_row_count(Some(batch.num_rows()));
+                RecordBatch::try_new_with_options(schema.clone(), columns, &options)
+                    .map_err(DataFusionError::from)
+            });
+
+            Ok::<_, DataFusionError>(stream::iter(batches))
+        })
+        .try_flatten();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema.clone(),
+            batches,
+        )))
+    }
+}
+
+/// Read `window` of a temporary view of `table` as record batches.
+async fn fetch(
+    table: &Table,
+    config: Option<ViewConfigUpdate>,
+    window: ViewWindow,
+) -> Result<(SchemaRef, Vec<RecordBatch>), ClientError> {
+    let window = ViewWindow {
+        compression: None,
+        ..window
+    };
+
+    let view = table.view(config).await?;
+    let arrow = view.to_arrow(window).await;
+    view.delete().await?;
+    decode(arrow?).map_err(|err| ClientError::ExternalError(Box::new(err)))
+}
+
+fn decode(arrow: Bytes) -> Result<(SchemaRef, Vec<RecordBatch>), ArrowError> {
+    let reader = StreamReader::try_new(Cursor::new(arrow), None)?;
+    let schema = reader.schema();
+    let batches = reader.collect::<Result<Vec<_>, _>>()?;
+    Ok((schema, batches))
+}
+
+/// Whether `data_type` is a string, including the engine's
+/// dictionary-encoded strings.
+fn is_string(data_type: &DataType) -> bool {
+    match data_type {
+        DataType::Utf8 | DataType::LargeUtf8 => true,
+        DataType::Dictionary(_, value) => is_string(value),
+        _ => false,
+    }
+}
+
+/// The column `expr` reads, looking through casts of a string column to
+/// another string type (as DataFusion's type coercion adds to compare a
+/// dictionary-encoded column with a string literal), which don't change its
+/// values.
+fn to_column<'a>(expr: &'a Expr, schema: &Schema) -> Option<&'a Column> {
+    match expr {
+        Expr::Column(column) => Some(column),
+        Expr::Cast(Cast { expr, data_type }) | Expr::TryCast(TryCast { expr, data_type })
+            if is_string(data_type) =>
+        {
+            let Expr::Column(column) = expr.as_ref() else {
+                return None;
+            };
+
+            let field = schema.field_with_name(&column.name).ok()?;
+            is_string(field.data_type()).then_some(column)
+        },
+        _ => None,
+    }
+}
+
+/// The engine [`Filter`] equivalent to `expr`, if there is one.
+fn to_filter(expr: &Expr, schema: &Schema) -> Option<Filter> {
+    match expr {
+        Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+            let (column, op, value) = match (left.as_ref(), right.as_ref()) {
+                (left, Expr::Literal(value)) => (to_column(left, schema)?, *op, value),
+                (Expr::Literal(value), right) => (to_column(right, schema)?, op.swap()?, value),
+                _ => return None,
+            };
+
+            let op = match op {
+                Operator::Eq => "==",
+                Operator::NotEq => "!=",
+                Operator::Lt => "<",
+                Operator::LtEq => "<=",
+                Operator::Gt => ">",
+                Operator::GtEq => ">=",
+                _ => return None,
+            };
+
+            let term = FilterTerm::Scalar(to_scalar(value)?);
+            Some(Filter::new(&column.name, op, term))
+        },
+        Expr::IsNull(inner) | Expr::IsNotNull(inner) => {
+            let column = to_column(inner, schema)?;
+            let op = match expr {
+                Expr::IsNull(_) => "is null",
+                _ => "is not null",
+            };
+
+            Some(Filter::new(
+                &column.name,
+                op,
+                FilterTerm::Scalar(Scalar::Null),
+            ))
+        },
+        Expr::InList(InList {
+            expr,
+            list,
+            negated,
+        }) => {
+            let column = to_column(expr, schema)?;
+            let values = list
+                .iter()
+                .map(|x| match x {
+                    Expr::Literal(value) => match to_scalar(value)? {
+                        x @ Scalar::String(_) => Some(x),
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+
+            let op = if *negated { "not in" } else { "in" };
+            Some(Filter::new(&column.name, op, FilterTerm::Array(values)))
+        },
+        _ => None,
+    }
+}
+
+fn to_scalar(value: &ScalarValue) -> Option<Scalar> {
+    Some(match value {
+        ScalarValue::Utf8(Some(x)) | ScalarValue::LargeUtf8(Some(x)) => Scalar::String(x.clone()),
+        ScalarValue::Boolean(Some(x)) => Scalar::Bool(*x),
+        ScalarValue::Float32(Some(x)) => Scalar::Float(*x as f64),
+        ScalarValue::Float64(Some(x)) => Scalar::Float(*x),
+        ScalarValue::Int8(Some(x)) => Scalar::Float(*x as f64),
+        ScalarValue::Int16(Some(x)) => Scalar::Float(*x as f64),
+        ScalarValue::Int32(Some(x)) => Scalar::Float(*x as f64),
+        ScalarValue::Int64(Some(x)) => Scalar::Float(*x as f64),
+        ScalarValue::UInt8(Some(x)) => Scalar::Float(*x as f64),
+        ScalarValue::UInt16(Some(x)) => Scalar::Float(*x as f64),
+        ScalarValue::UInt32(Some(x)) => Scalar::Float(*x as f64),
+        ScalarValue::UInt64(Some(x)) => Scalar::Float(*x as f64),
+        ScalarValue::Dictionary(_, value) => return to_scalar(value),
+        _ => return None,
+    })
+}
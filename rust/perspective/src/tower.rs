@@ -0,0 +1,136 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! A [`tower::Service`] which serves a [`Server`] over WebSocket, for use with
+//! any [`hyper`]-based framework (or [`hyper`] itself).
+
+use std::convert::Infallible;
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use http::header::{
+    CONNECTION, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, SEC_WEBSOCKET_PROTOCOL, UPGRADE,
+};
+use http::{Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::WebSocketStream;
+use tower::Service;
+
+use crate::server::Server;
+use crate::tungstenite::{serve_websocket_with_config, WebsocketConfig};
+
+/// A [`tower::Service`] which accepts WebSocket upgrade requests, serving
+/// each upgraded connection to a [`Server`] (via
+/// [`crate::connection::serve_connection`]) on a new [`tokio`] task.
+/// Requests which are not WebSocket upgrades are rejected with
+/// `400 Bad Request`, as are requests rejected by its [`WebsocketConfig`].
+///
+/// ```rust,ignore
+/// let app = axum::Router::new()
+///     .route_service("/ws", WebsocketService::new(server));
+/// ```
+#[derive(Clone, Debug)]
+pub struct WebsocketService {
+    server: Server,
+    config: WebsocketConfig,
+}
+
+impl WebsocketService {
+    pub fn new(server: Server) -> Self {
+        Self::with_config(server, WebsocketConfig::default())
+    }
+
+    /// As [`WebsocketService::new`], with keepalive, limits and connection
+    /// checks configured by a [`WebsocketConfig`].
+    pub fn with_config(server: Server, config: WebsocketConfig) -> Self {
+        Self { server, config }
+    }
+}
+
+fn status_response(status: StatusCode, body: &str) -> Response<String> {
+    let mut resp = Response::new(body.to_owned());
+    *resp.status_mut() = status;
+    resp
+}
+
+impl<B> Service<Request<B>> for WebsocketService
+where
+    B: Send + 'static,
+{
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+    type Response = Response<String>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        let server = self.server.clone();
+        let config = self.config.clone();
+        async move {
+            let is_upgrade = req
+                .headers()
+                .get(UPGRADE)
+                .is_some_and(|x| x.as_bytes().eq_ignore_ascii_case(b"websocket"));
+
+            let key = match req.headers().get(SEC_WEBSOCKET_KEY) {
+                Some(key) if is_upgrade => key.clone(),
+                _ => {
+                    let body = "Expected a WebSocket upgrade";
+                    return Ok(status_response(StatusCode::BAD_REQUEST, body));
+                },
+            };
+
+            let (protocol, principal) = match config.accept(req.headers()).await {
+                Ok(accepted) => accepted,
+                Err(status) => return Ok(status_response(status, "")),
+            };
+
+            let on_upgrade = hyper::upgrade::on(&mut req);
+            tokio::spawn(async move {
+                let result = match on_upgrade.await {
+                    Ok(upgraded) => {
+                        let io = TokioIo::new(upgraded);
+                        let protocol_config = Some(config.protocol_config());
+                        let socket =
+                            WebSocketStream::from_raw_socket(io, Role::Server, protocol_config)
+                                .await;
+
+                        serve_websocket_with_config(&server, socket, &config, principal).await
+                    },
+                    Err(err) => Err(err.into()),
+                };
+
+                if let Err(msg) = result {
+                    tracing::error!("Internal error {}", msg);
+                }
+            });
+
+            let mut resp = Response::builder()
+                .status(StatusCode::SWITCHING_PROTOCOLS)
+                .header(CONNECTION, "upgrade")
+                .header(UPGRADE, "websocket")
+                .header(SEC_WEBSOCKET_ACCEPT, derive_accept_key(key.as_bytes()));
+
+            if let Some(protocol) = protocol {
+                resp = resp.header(SEC_WEBSOCKET_PROTOCOL, protocol);
+            }
+
+            Ok(resp.body(String::new()).expect("Invalid response"))
+        }
+        .boxed()
+    }
+}
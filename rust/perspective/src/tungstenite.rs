@@ -0,0 +1,109 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+//! Serve a [`Server`] over a plain [`tokio_tungstenite`] WebSocket, without a
+//! web framework.
+
+use futures::{future, SinkExt, StreamExt};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{accept_async, WebSocketStream};
+
+pub use crate::connection::WebsocketConfig;
+use crate::connection::{serve_websocket_messages, IncomingMessage, OutgoingMessage};
+use crate::server::Server;
+
+/// A local error synonym for this module only.
+type PerspectiveWSError = Box<dyn std::error::Error + Send + Sync>;
+
+impl WebsocketConfig {
+    /// The [`tokio_tungstenite`] protocol config for this config's limits.
+    pub(crate) fn protocol_config(&self) -> WebSocketConfig {
+        let mut config = WebSocketConfig::default();
+        if let Some(size) = self.max_frame_size {
+            config.max_frame_size = Some(size);
+        }
+
+        if let Some(size) = self.max_message_size {
+            config.max_message_size = Some(size);
+        }
+
+        config
+    }
+}
+
+/// Serve an established [`WebSocketStream`] to `server`, returning when the
+/// socket closes. See [`crate::connection::serve_connection`].
+pub async fn serve_websocket<S>(
+    server: &Server,
+    socket: WebSocketStream<S>,
+) -> Result<(), PerspectiveWSError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    serve_websocket_with_config(server, socket, &WebsocketConfig::default(), None).await
+}
+
+/// As [`serve_websocket`], enforcing the keepalive and idle timeouts of
+/// `config` and attaching `principal` to the connection's
+/// [`crate::server::LocalSession`]. The frame and message size limits of
+/// `config` must already have been applied to `socket` (see
+/// [`WebSocketStream::from_raw_socket`]).
+///
+/// Outgoing messages (including pings) are written by a single writer,
+/// which finishes once the connection's session has been closed.
+pub async fn serve_websocket_with_config<S>(
+    server: &Server,
+    socket: WebSocketStream<S>,
+    config: &WebsocketConfig,
+    principal: Option<String>,
+) -> Result<(), PerspectiveWSError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (sink, stream) = socket.split();
+    let stream = stream.map(|msg| match msg {
+        Ok(Message::Binary(bytes)) => Ok(IncomingMessage::Binary(bytes)),
+        Ok(Message::Text(_)) => Ok(IncomingMessage::Text),
+        Ok(Message::Ping(_) | Message::Frame(_)) => Ok(IncomingMessage::Ping),
+        Ok(Message::Pong(_)) => Ok(IncomingMessage::Pong),
+        Ok(Message::Close(_)) => Ok(IncomingMessage::Close),
+        Err(err) => Err(PerspectiveWSError::from(err)),
+    });
+
+    let sink = sink.with(|msg| {
+        future::ok::<_, PerspectiveWSError>(match msg {
+            OutgoingMessage::Binary(bytes) => Message::Binary(bytes),
+            OutgoingMessage::Ping => Message::Ping(vec![]),
+        })
+    });
+
+    serve_websocket_messages(server, stream, Box::pin(sink), config, principal).await
+}
+
+/// Accept a WebSocket handshake on a raw `stream` (e.g. a
+/// [`tokio::net::TcpStream`] from a [`tokio::net::TcpListener`]), then
+/// serve it to `server` via [`serve_websocket`].
+///
+/// ```rust,ignore
+/// let listener = tokio::net::TcpListener::bind("127.0.0.1:3000").await?;
+/// while let Ok((stream, _)) = listener.accept().await {
+///     let server = server.clone();
+///     tokio::spawn(async move { accept(&server, stream).await });
+/// }
+/// ```
+pub async fn accept<S>(server: &Server, stream: S) -> Result<(), PerspectiveWSError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    serve_websocket(server, accept_async(stream).await?).await
+}
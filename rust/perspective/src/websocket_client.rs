@@ -0,0 +1,229 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+//! A [`Client`] transport for connecting to a remote Perspective server over
+//! a WebSocket, via [`tokio_tungstenite`].
+
+use std::error::Error;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures::future::{select, Either};
+use futures::stream::FusedStream;
+use futures::{FutureExt, SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+use crate::client::utils::PerspectiveResultExt;
+use crate::client::{Client, ClientHandler, ReconnectCallback};
+
+/// A local error synonym for this module only.
+type PerspectiveWSError = Box<dyn Error + Send + Sync>;
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// The current connection's request sender and message loop task.
+struct WSConnection {
+    sender: UnboundedSender<Vec<u8>>,
+    task: JoinHandle<()>,
+}
+
+/// The [`ClientHandler`] for a WebSocket connection, which forwards requests
+/// to the message loop of the current connection (if any). The connection is
+/// replaced on reconnect.
+#[derive(Clone, Default)]
+struct PerspectiveWSClientHandler(Arc<Mutex<Option<WSConnection>>>);
+
+impl PerspectiveWSClientHandler {
+    /// Take the current connection (if any) and close its request channel,
+    /// which ends its message loop after pending requests have been sent.
+    fn disconnect(&self) -> Option<JoinHandle<()>> {
+        let conn = self.0.lock().unwrap().take()?;
+        conn.sender.close_channel();
+        Some(conn.task)
+    }
+}
+
+impl ClientHandler for PerspectiveWSClientHandler {
+    async fn send_request(&self, msg: Vec<u8>) -> Result<(), PerspectiveWSError> {
+        match self.0.lock().unwrap().as_ref() {
+            Some(conn) => Ok(conn.sender.unbounded_send(msg)?),
+            None => Err("WebSocket disconnected".into()),
+        }
+    }
+}
+
+/// A [`Client`] connected to a remote server over a WebSocket, created by
+/// [`connect`]. Use [`WebsocketClient::close`] to disconnect gracefully;
+/// dropping it also closes the socket, without waiting.
+pub struct WebsocketClient {
+    client: Client,
+    handler: PerspectiveWSClientHandler,
+}
+
+impl Deref for WebsocketClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+impl WebsocketClient {
+    /// Flush any pending requests and close the connection, waiting for the
+    /// message loop to finish.
+    pub async fn close(self) {
+        if let Some(task) = self.handler.disconnect() {
+            task.await.unwrap_or_log();
+        }
+    }
+}
+
+impl Drop for WebsocketClient {
+    fn drop(&mut self) {
+        self.handler.disconnect();
+    }
+}
+
+/// The full-duplex message loop for a single connection. Returns `Ok` when
+/// the socket closes cleanly, from either side.
+async fn process_message_loop(
+    socket: Socket,
+    receiver: &mut UnboundedReceiver<Vec<u8>>,
+    client: &Client,
+) -> Result<(), PerspectiveWSError> {
+    use Either::*;
+    use Message::*;
+
+    let (mut sink, mut stream) = socket.split();
+    loop {
+        match select(stream.next(), receiver.next()).await {
+            Left((Some(Ok(Binary(bytes))), _)) => {
+                client.handle_response(&bytes).await.unwrap_or_log();
+            },
+            Left((Some(Ok(Ping(_) | Pong(_) | Frame(_))), _)) => (),
+            Left((Some(Ok(Text(_))), _)) => return Err("Unexpected message type".into()),
+            Left((None | Some(Ok(Close(_))), _)) => return Ok(()),
+            Left((Some(Err(err)), _)) => return Err(err.into()),
+            Right((Some(bytes), _)) => sink.send(Binary(bytes)).await?,
+            Right((None, _)) => {
+                sink.close().await?;
+                return Ok(());
+            },
+        }
+    }
+}
+
+/// Run a connection's message loop to completion, then notify the
+/// [`Client`] via [`Client::handle_error`]. Unless the connection was closed
+/// by [`WebsocketClient`], a [`ReconnectCallback`] which re-opens `url` is
+/// provided.
+async fn run_connection(
+    url: String,
+    socket: Socket,
+    mut receiver: UnboundedReceiver<Vec<u8>>,
+    handler: PerspectiveWSClientHandler,
+    client: Client,
+) {
+    let result = process_message_loop(socket, &mut receiver, &client).await;
+    let is_closed_by_client = receiver.is_terminated();
+    let message = match result {
+        Ok(()) if is_closed_by_client => "WebSocket closed by client".to_owned(),
+        Ok(()) => "WebSocket closed".to_owned(),
+        Err(err) => format!("WebSocket error: {}", err),
+    };
+
+    tracing::info!("{url} {message}");
+    let reconnect = if is_closed_by_client {
+        None
+    } else {
+        handler.0.lock().unwrap().take();
+        Some(reconnect_callback(url, handler, client.clone()))
+    };
+
+    client
+        .handle_error(Some(message), reconnect)
+        .await
+        .unwrap_or_log();
+}
+
+/// A [`ReconnectCallback`] which re-opens `url` for `client`.
+fn reconnect_callback(
+    url: String,
+    handler: PerspectiveWSClientHandler,
+    client: Client,
+) -> ReconnectCallback {
+    Arc::new(move || {
+        let url = url.clone();
+        let handler = handler.clone();
+        let client = client.clone();
+        async move {
+            open(url, handler, client)
+                .await
+                .map_err(|err| err as Box<dyn Error>)
+        }
+        .boxed_local()
+    })
+}
+
+/// Open a WebSocket connection to `url` and spawn its message loop.
+async fn open(
+    url: String,
+    handler: PerspectiveWSClientHandler,
+    client: Client,
+) -> Result<(), PerspectiveWSError> {
+    let (socket, _) = connect_async(url.as_str()).await?;
+    let (sender, receiver) = unbounded::<Vec<u8>>();
+    let mut conn = handler.0.lock().unwrap();
+    let task = tokio::spawn(run_connection(
+        url,
+        socket,
+        receiver,
+        handler.clone(),
+        client,
+    ));
+    *conn = Some(WSConnection { sender, task });
+    Ok(())
+}
+
+/// Connect a new [`Client`] to a remote Perspective server at `url` (e.g. a
+/// `perspective::axum::websocket_handler` route), over a WebSocket.
+///
+/// The connection's message loop runs as a [`tokio`] task. When the server
+/// closes the socket or it errors, the [`Client`]'s [`Client::on_error`]
+/// callbacks are invoked with a [`ReconnectCallback`] which re-opens the
+/// connection to `url`.
+///
+/// ```rust,ignore
+/// let client = perspective::connect("ws://localhost:3000/ws").await?;
+/// let table = client.open_table("my_data_source".to_owned()).await?;
+/// client.close().await;
+/// ```
+pub async fn connect(url: &str) -> Result<WebsocketClient, PerspectiveWSError> {
+    let handler = PerspectiveWSClientHandler::default();
+    let client = WebsocketClient {
+        client: Client::new(handler.clone()),
+        handler,
+    };
+
+    open(
+        url.to_owned(),
+        client.handler.clone(),
+        client.client.clone(),
+    )
+    .await?;
+    client.init().await?;
+    Ok(client)
+}
@@ -0,0 +1,156 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::error::Error;
+
+use perspective::server::Server;
+use perspective_client::{
+    Client, OnUpdateOptions, Session, TableInitOptions, UpdateData, ViewWindow,
+};
+use perspective_server::LocalClient;
+use tokio::sync::mpsc;
+
+/// A [`Client`] on a new [`Session`] of `server`, which is granted admin
+/// access if `is_admin`.
+async fn session_client(server: &Server, is_admin: bool) -> Result<Client, Box<dyn Error>> {
+    let (req_send, mut req_recv) = mpsc::unbounded_channel::<Vec<u8>>();
+    let (resp_send, mut resp_recv) = mpsc::unbounded_channel::<Vec<u8>>();
+    let client = Client::new_with_callback(move |msg| {
+        let req_send = req_send.clone();
+        Box::pin(async move { Ok(req_send.send(msg)?) })
+    });
+
+    let session = server
+        .new_session_with_callback(move |msg| {
+            let resp_send = resp_send.clone();
+            let msg = msg.to_vec();
+            Box::pin(async move { Ok(resp_send.send(msg)?) })
+        })
+        .await;
+
+    session.set_admin(is_admin);
+    tokio::spawn({
+        let client = client.clone();
+        async move {
+            loop {
+                tokio::select! {
+                    Some(req) = req_recv.recv() => {
+                        if session.handle_request(&req).await.is_ok() {
+                            session.poll().await.unwrap();
+                        }
+                    },
+                    Some(resp) = resp_recv.recv() => {
+                        client.handle_response(&resp).await.unwrap();
+                    },
+                    else => break,
+                }
+            }
+
+            session.close().await;
+        }
+    });
+
+    Ok(client)
+}
+
+#[tokio::test]
+async fn test_sessions_report_views_and_delete_view_evicts() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let mut options = TableInitOptions::default();
+    options.set_name("Table1");
+    let table = client
+        .table(UpdateData::Csv("x,y\n1,2".to_owned()).into(), options)
+        .await?;
+
+    let view = table.view(None).await?;
+    view.on_update(|_| async {}, OnUpdateOptions::default())
+        .await?;
+
+    let sessions = server.sessions();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].views.len(), 1);
+    assert_eq!(sessions[0].views[0].view_id, view.name);
+    assert_eq!(sessions[0].views[0].table_id, "Table1");
+    assert_eq!(sessions[0].on_update_subscriptions(), 1);
+    assert!(sessions[0].bytes_sent > 0);
+
+    server.delete_view(&view.name).await?;
+    assert!(server.sessions()[0].views.is_empty());
+    assert!(view.to_csv(ViewWindow::default()).await.is_err());
+    client.close().await;
+    assert!(server.sessions().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_close_session_evicts_session() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let table = client
+        .table(
+            UpdateData::Csv("x,y\n1,2".to_owned()).into(),
+            TableInitOptions::default(),
+        )
+        .await?;
+
+    let view = table.view(None).await?;
+    let sessions = server.sessions();
+    assert_eq!(sessions.len(), 1);
+    server.close_session(sessions[0].id).await?;
+    assert!(server.sessions().is_empty());
+    assert!(view.to_csv(ViewWindow::default()).await.is_err());
+    assert!(server.close_session(sessions[0].id).await.is_err());
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_admin_session_requests() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let admin = session_client(&server, true).await?;
+    let user = session_client(&server, false).await?;
+    let table = user
+        .table(
+            UpdateData::Csv("x,y\n1,2".to_owned()).into(),
+            TableInitOptions::default(),
+        )
+        .await?;
+
+    let view = table.view(None).await?;
+    let sessions = admin.server_sessions().await?;
+    assert_eq!(sessions.len(), 2);
+    let user_session = sessions
+        .iter()
+        .find(|x| !x.views.is_empty())
+        .ok_or("No session with views")?;
+
+    assert_eq!(user_session.views[0].view_id, view.name);
+    admin.server_delete_view(view.name.clone()).await?;
+    assert!(view.to_csv(ViewWindow::default()).await.is_err());
+    admin.server_close_session(user_session.id).await?;
+    assert_eq!(admin.server_sessions().await?.len(), 1);
+    assert_eq!(server.sessions().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_non_admin_session_requests_are_rejected() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let user = session_client(&server, false).await?;
+    assert!(user.server_sessions().await.is_err());
+    assert!(user.server_close_session(0).await.is_err());
+    assert!(user.server_delete_view("view".to_owned()).await.is_err());
+
+    assert_eq!(server.sessions().len(), 1);
+    Ok(())
+}
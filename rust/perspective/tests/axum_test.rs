@@ -0,0 +1,191 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#![cfg(all(feature = "axum-ws", feature = "websocket-client"))]
+
+use std::error::Error;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use axum::Router;
+use futures::{sink, stream, SinkExt, StreamExt};
+use perspective::axum::{websocket_handler_with_config, WebsocketConfig};
+use perspective::connection::serve_connection;
+use perspective::server::Server;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
+
+async fn start_server(server: Server, config: WebsocketConfig) -> Result<String, Box<dyn Error>> {
+    let app = Router::new()
+        .route("/ws", websocket_handler_with_config(config))
+        .with_state(server);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}/ws", listener.local_addr()?);
+    tokio::spawn(async move {
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+    });
+
+    Ok(url)
+}
+
+/// Wait for the [`Server`] to have `n` sessions.
+async fn wait_for_sessions(server: &Server, n: usize) -> Result<(), Box<dyn Error>> {
+    for _ in 0..100 {
+        if server.sessions().len() == n {
+            return Ok(());
+        }
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    Err(format!("Expected {} sessions", n).into())
+}
+
+#[tokio::test]
+async fn test_rejects_disallowed_origin() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let config = WebsocketConfig::default().allowed_origins(["http://localhost"]);
+    let url = start_server(server.clone(), config).await?;
+    let mut request = url.as_str().into_client_request()?;
+    request
+        .headers_mut()
+        .insert("Origin", "http://evil.example".parse()?);
+
+    assert!(connect_async(request).await.is_err());
+    let mut request = url.as_str().into_client_request()?;
+    request
+        .headers_mut()
+        .insert("Origin", "http://localhost".parse()?);
+
+    let _socket = connect_async(request).await?;
+    wait_for_sessions(&server, 1).await
+}
+
+#[tokio::test]
+async fn test_on_connect_attaches_principal() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let config = WebsocketConfig::default().on_connect(|headers| async move {
+        let user = headers.get("x-user").ok_or("Missing x-user")?;
+        Ok::<_, Box<dyn Error + Send + Sync>>(Some(user.to_str()?.to_owned()))
+    });
+
+    let url = start_server(server.clone(), config).await?;
+    assert!(connect_async(url.as_str()).await.is_err());
+    let mut request = url.as_str().into_client_request()?;
+    request.headers_mut().insert("x-user", "alice".parse()?);
+    let _socket = connect_async(request).await?;
+    wait_for_sessions(&server, 1).await?;
+    assert_eq!(server.sessions()[0].principal.as_deref(), Some("alice"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_idle_timeout_closes_session() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let config = WebsocketConfig::default().idle_timeout(Duration::from_millis(50));
+    let url = start_server(server.clone(), config).await?;
+    let _socket = connect_async(url.as_str()).await?;
+    wait_for_sessions(&server, 1).await?;
+    wait_for_sessions(&server, 0).await
+}
+
+#[tokio::test]
+async fn test_ping_interval_keeps_responsive_session() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let config = WebsocketConfig::default().ping_interval(Duration::from_millis(20));
+    let url = start_server(server.clone(), config).await?;
+    let (mut socket, _) = connect_async(url.as_str()).await?;
+    let mut pings = 0;
+    while pings < 5 {
+        match socket.next().await.ok_or("Socket closed")?? {
+            Message::Ping(_) => pings += 1,
+            msg => return Err(format!("Unexpected message {:?}", msg).into()),
+        }
+    }
+
+    assert_eq!(server.sessions().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pong_timeout_closes_session() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let config = WebsocketConfig::default()
+        .ping_interval(Duration::from_millis(20))
+        .pong_timeout(Duration::from_millis(20));
+
+    let url = start_server(server.clone(), config).await?;
+
+    // The client never reads the socket, so never replies to the ping.
+    let _socket = connect_async(url.as_str()).await?;
+    wait_for_sessions(&server, 1).await?;
+    wait_for_sessions(&server, 0).await
+}
+
+#[tokio::test]
+async fn test_max_frame_size_closes_session() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let config = WebsocketConfig::default().max_frame_size(1024);
+    let url = start_server(server.clone(), config).await?;
+    let (mut socket, _) = connect_async(url.as_str()).await?;
+    wait_for_sessions(&server, 1).await?;
+    socket.send(Message::Binary(vec![0; 4096])).await?;
+    wait_for_sessions(&server, 0).await
+}
+
+#[tokio::test]
+async fn test_requires_protocol() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let config = WebsocketConfig::default().protocols(["perspective"]);
+    let url = start_server(server.clone(), config).await?;
+    assert!(connect_async(url.as_str()).await.is_err());
+    let mut request = url.as_str().into_client_request()?;
+    request
+        .headers_mut()
+        .insert("Sec-WebSocket-Protocol", "other".parse()?);
+
+    assert!(connect_async(request).await.is_err());
+    let mut request = url.as_str().into_client_request()?;
+    request
+        .headers_mut()
+        .insert("Sec-WebSocket-Protocol", "other, perspective".parse()?);
+
+    let (_socket, resp) = connect_async(request).await?;
+    assert_eq!(
+        resp.headers().get("Sec-WebSocket-Protocol"),
+        Some(&"perspective".parse()?)
+    );
+
+    wait_for_sessions(&server, 1).await
+}
+
+#[tokio::test]
+async fn test_cancelled_connection_closes_session() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let task = tokio::spawn({
+        let server = server.clone();
+        async move {
+            let stream = stream::pending::<Result<Vec<u8>, Box<dyn Error + Send + Sync>>>();
+            serve_connection(&server, stream, sink::drain()).await
+        }
+    });
+
+    wait_for_sessions(&server, 1).await?;
+    task.abort();
+    wait_for_sessions(&server, 0).await
+}
@@ -31,6 +31,9 @@ async fn test_two_sync_clients_receive_messages_on_update() -> Result<(), Box<dy
                 index: None,
                 limit: None,
                 format: None,
+                csv: None,
+                parquet: None,
+                ingest: None,
             },
         )
         .await?;
@@ -0,0 +1,110 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use perspective::server::Server;
+use perspective_client::{
+    ColumnType, CsvOptions, TableInitOptions, UpdateData, UpdateOptions, ViewWindow,
+};
+use perspective_server::LocalClient;
+
+#[tokio::test]
+async fn test_csv_options() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let csv_options = CsvOptions {
+        delimiter: Some(';'),
+        decimal_point: Some(','),
+        null_values: Some(vec!["NA".to_owned(), "-".to_owned()]),
+        date_formats: Some(vec!["%d/%m/%Y".to_owned()]),
+        column_types: Some(HashMap::from([
+            ("zip".to_owned(), ColumnType::String),
+            ("when".to_owned(), ColumnType::Date),
+        ])),
+        ..CsvOptions::default()
+    };
+
+    let mut options = TableInitOptions {
+        csv: Some(csv_options.clone()),
+        ..TableInitOptions::default()
+    };
+
+    options.set_name("Table1");
+    let csv = "zip;price;when;note\n02134;1,5;25/12/2024;NA\n10001;2,25;01/01/2025;ok\n";
+    let table = client
+        .table(UpdateData::Csv(csv.to_owned()).into(), options)
+        .await?;
+
+    let schema = table.schema().await?;
+    assert_eq!(schema["zip"], ColumnType::String);
+    assert_eq!(schema["price"], ColumnType::Float);
+    assert_eq!(schema["when"], ColumnType::Date);
+    assert_eq!(schema["note"], ColumnType::String);
+
+    let options = UpdateOptions {
+        csv: Some(CsvOptions {
+            header: Some(false),
+            column_names: Some(vec![
+                "zip".to_owned(),
+                "price".to_owned(),
+                "when".to_owned(),
+                "note".to_owned(),
+            ]),
+            ..csv_options
+        }),
+        ..UpdateOptions::default()
+    };
+
+    table
+        .update(
+            UpdateData::Csv("00501;3,75;02/01/2025;-\n".to_owned()),
+            options,
+        )
+        .await?;
+
+    let view = table.view(None).await?;
+    let json = view.to_columns_string(ViewWindow::default()).await?;
+    assert!(json.contains(r#""zip":["02134","10001","00501"]"#));
+    assert!(json.contains(r#""price":[1.5,2.25,3.75]"#));
+    assert!(json.contains(r#""when":[1735084800000,1735689600000,1735776000000]"#));
+    assert!(json.contains(r#""note":[null,"ok",null]"#));
+
+    view.delete().await?;
+    table.delete().await?;
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_csv_options_reject_non_ascii_delimiter() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let options = TableInitOptions {
+        csv: Some(CsvOptions {
+            delimiter: Some('§'),
+            ..CsvOptions::default()
+        }),
+        ..TableInitOptions::default()
+    };
+
+    let result = client
+        .table(UpdateData::Csv("a§b\n1§2\n".to_owned()).into(), options)
+        .await;
+
+    let err = result.err().ok_or("Expected an error")?;
+    assert!(err.to_string().contains("must be an ASCII character"));
+    assert!(client.get_hosted_table_names().await?.is_empty());
+    client.close().await;
+    Ok(())
+}
@@ -0,0 +1,139 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#![cfg(feature = "datafusion")]
+
+use std::error::Error;
+use std::sync::Arc;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::Int64Type;
+use datafusion::arrow::datatypes::DataType;
+use datafusion::datasource::TableProvider;
+use datafusion::logical_expr::TableProviderFilterPushDown;
+use datafusion::prelude::{SessionContext, cast, col, lit};
+use datafusion::scalar::ScalarValue;
+use perspective::server::Server;
+use perspective::table_provider::PerspectiveTableProvider;
+use perspective_client::{TableInitOptions, UpdateData};
+use perspective_server::LocalClient;
+
+#[tokio::test]
+async fn test_datafusion_sql() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let csv = "x,y,z\n1,a,1.5\n2,b,2.5\n3,a,3.5\n4,c,4.5\n";
+    let table = client
+        .table(
+            UpdateData::Csv(csv.to_owned()).into(),
+            TableInitOptions::default(),
+        )
+        .await?;
+
+    let ctx = SessionContext::new();
+    let provider = PerspectiveTableProvider::try_new(table.clone()).await?;
+    ctx.register_table("t", Arc::new(provider))?;
+
+    let batches = ctx
+        .sql("SELECT COUNT(*) AS n FROM t WHERE y IN ('a', 'c') AND x > 1")
+        .await?
+        .collect()
+        .await?;
+
+    assert_eq!(batches[0].column(0).as_primitive::<Int64Type>().value(0), 2);
+
+    let batches = ctx
+        .sql("SELECT y, z FROM t LIMIT 3")
+        .await?
+        .collect()
+        .await?;
+
+    let rows: usize = batches.iter().map(|x| x.num_rows()).sum();
+    assert_eq!(rows, 3);
+    assert_eq!(batches[0].schema().fields().len(), 2);
+
+    table
+        .update(
+            UpdateData::Csv("x,y,z\n5,a,5.5".to_owned()),
+            Default::default(),
+        )
+        .await?;
+    let batches = ctx.sql("SELECT COUNT(*) FROM t").await?.collect().await?;
+    assert_eq!(batches[0].column(0).as_primitive::<Int64Type>().value(0), 5);
+    table.delete().await?;
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_datafusion_filter_pushdown() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let csv = "x,y\n1,a\n2,b\n3,a\n";
+    let table = client
+        .table(
+            UpdateData::Csv(csv.to_owned()).into(),
+            TableInitOptions::default(),
+        )
+        .await?;
+
+    // String columns are dictionary-encoded, so DataFusion compares them
+    // through a cast of the column or a dictionary literal.
+    let provider = PerspectiveTableProvider::try_new(table.clone()).await?;
+    let dictionary =
+        ScalarValue::Dictionary(Box::new(DataType::Int32), Box::new(ScalarValue::from("a")));
+
+    let filters = [
+        cast(col("y"), DataType::Utf8).eq(lit("a")),
+        col("y").eq(lit(dictionary)),
+        cast(col("x"), DataType::Utf8).eq(lit("1")),
+    ];
+
+    assert_eq!(
+        provider.supports_filters_pushdown(&filters.iter().collect::<Vec<_>>())?,
+        vec![
+            TableProviderFilterPushDown::Inexact,
+            TableProviderFilterPushDown::Inexact,
+            TableProviderFilterPushDown::Unsupported,
+        ]
+    );
+
+    let ctx = SessionContext::new();
+    ctx.register_table("t", Arc::new(provider))?;
+    let sql = "SELECT x FROM t WHERE y = 'a' AND x > 1";
+    let batches = ctx
+        .sql(&format!("EXPLAIN {}", sql))
+        .await?
+        .collect()
+        .await?;
+
+    let plan = batches
+        .iter()
+        .flat_map(|x| x.column(1).as_string::<i32>().iter().flatten())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let scan = plan
+        .lines()
+        .find(|x| x.contains("PerspectiveExec"))
+        .ok_or(plan.clone())?;
+
+    assert!(scan.contains("y == a"), "{}", scan);
+    assert!(scan.contains("x > 1"), "{}", scan);
+
+    let batches = ctx.sql(sql).await?.collect().await?;
+    let rows: usize = batches.iter().map(|x| x.num_rows()).sum();
+    assert_eq!(rows, 1);
+    table.delete().await?;
+    client.close().await;
+    Ok(())
+}
@@ -0,0 +1,82 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::error::Error;
+
+use perspective::server::{ExecutionStrategy, Server, ServerOptions};
+use perspective_client::{TableInitOptions, UpdateData, ViewWindow};
+use perspective_server::LocalClient;
+
+async fn group_by_on(server: &Server) -> Result<(), Box<dyn Error>> {
+    let client = LocalClient::new(server);
+    let table = client
+        .table(
+            UpdateData::Csv("x,y\n1,a\n2,a\n3,b".to_owned()).into(),
+            TableInitOptions::default(),
+        )
+        .await?;
+
+    let view = table
+        .view(Some(perspective_client::config::ViewConfigUpdate {
+            group_by: Some(vec!["y".to_owned()]),
+            ..Default::default()
+        }))
+        .await?;
+
+    let csv = view.to_csv(ViewWindow::default()).await?;
+    assert_eq!(csv.lines().count(), 4);
+    view.delete().await?;
+    table.delete().await?;
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_worker_thread_strategy() -> Result<(), Box<dyn Error>> {
+    let server = Server::new(ServerOptions {
+        execution: ExecutionStrategy::WorkerThread,
+        ..ServerOptions::default()
+    })?;
+
+    group_by_on(&server).await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_blocking_pool_strategy() -> Result<(), Box<dyn Error>> {
+    let server = Server::new(ServerOptions {
+        execution: ExecutionStrategy::BlockingPool,
+        ..ServerOptions::default()
+    })?;
+
+    group_by_on(&server).await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_blocking_pool_strategy_concurrent_sessions() -> Result<(), Box<dyn Error>> {
+    let server = Server::new(ServerOptions {
+        execution: ExecutionStrategy::BlockingPool,
+        ..ServerOptions::default()
+    })?;
+
+    let tasks = (0..8)
+        .map(|_| {
+            let server = server.clone();
+            tokio::spawn(async move { group_by_on(&server).await.map_err(|e| e.to_string()) })
+        })
+        .collect::<Vec<_>>();
+
+    for task in tasks {
+        task.await??;
+    }
+
+    Ok(())
+}
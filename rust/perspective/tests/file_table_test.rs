@@ -0,0 +1,69 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#![cfg(feature = "file-table")]
+
+use std::error::Error;
+use std::time::Duration;
+
+use perspective::client::Table;
+use perspective::file_table::{FileTable, FileTableOptions};
+use perspective::server::Server;
+use perspective_server::LocalClient;
+use tokio::io::AsyncWriteExt;
+
+async fn wait_for_size(table: &Table, size: usize) -> Result<(), Box<dyn Error>> {
+    for _ in 0..200 {
+        if table.size().await? == size {
+            return Ok(());
+        }
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    Err(format!("Table size {} != {}", table.size().await?, size).into())
+}
+
+#[tokio::test]
+async fn test_file_table_append_and_rewrite() -> Result<(), Box<dyn Error>> {
+    let path = std::env::temp_dir().join(format!("file_table_{}.csv", std::process::id()));
+    tokio::fs::write(&path, "x,y\n1,a\n2,b\n3,").await?;
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let options = FileTableOptions {
+        poll_interval: Duration::from_millis(10),
+        watch: false,
+        ..FileTableOptions::default()
+    };
+
+    let file = FileTable::open(&client, &path, options).await?;
+    let name = file.table().get_name().to_owned();
+    assert_eq!(name, format!("file_table_{}", std::process::id()));
+    assert_eq!(file.table().size().await?, 2);
+
+    let mut appender = tokio::fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .await?;
+
+    appender.write_all(b"c\n4,d\n").await?;
+    appender.flush().await?;
+    wait_for_size(file.table(), 4).await?;
+
+    tokio::fs::write(&path, "x,y\n9,z\n").await?;
+    wait_for_size(file.table(), 1).await?;
+
+    drop(file);
+    tokio::fs::remove_file(&path).await?;
+    client.close().await;
+    Ok(())
+}
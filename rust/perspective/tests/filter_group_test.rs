@@ -0,0 +1,107 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::error::Error;
+
+use perspective::server::Server;
+use perspective_client::config::{
+    Filter, FilterClause, FilterGroup, FilterReducer, FilterTerm, Scalar, ViewConfigUpdate,
+};
+use perspective_client::{TableInitOptions, UpdateData, ViewWindow};
+use perspective_server::LocalClient;
+
+fn float(column: &str, op: &str, value: f64) -> FilterClause {
+    Filter::new(column, op, FilterTerm::Scalar(Scalar::Float(value))).into()
+}
+
+fn string(column: &str, op: &str, value: &str) -> FilterClause {
+    Filter::new(
+        column,
+        op,
+        FilterTerm::Scalar(Scalar::String(value.to_owned())),
+    )
+    .into()
+}
+
+#[tokio::test]
+async fn test_filter_group() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let table = client
+        .table(
+            UpdateData::Csv("a,b,c\n1,3,x\n1,1,y\n2,5,x\n2,5,z\n1,4,z".to_owned()).into(),
+            TableInitOptions::default(),
+        )
+        .await?;
+
+    // `(a == 1 AND b > 2) OR c == "x"`
+    let filter = vec![
+        FilterGroup::new(FilterReducer::And, vec![
+            float("a", "==", 1.0),
+            float("b", ">", 2.0),
+        ])
+        .into(),
+        string("c", "==", "x"),
+    ];
+
+    let view = table
+        .view(Some(ViewConfigUpdate {
+            filter: Some(filter.clone()),
+            filter_op: Some(FilterReducer::Or),
+            ..ViewConfigUpdate::default()
+        }))
+        .await?;
+
+    let json = view.to_columns_string(ViewWindow::default()).await?;
+    assert!(json.contains(r#""a":[1,2,1]"#));
+    assert!(json.contains(r#""b":[3,5,4]"#));
+    assert!(json.contains(r#""c":["x","x","z"]"#));
+
+    let config = view.get_config().await?;
+    assert_eq!(config.filter, filter);
+    assert_eq!(config.filter_op, FilterReducer::Or);
+
+    view.delete().await?;
+    table.delete().await?;
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_filter_group_op_rejected_for_column() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let table = client
+        .table(
+            UpdateData::Csv("a,b\n1,2".to_owned()).into(),
+            TableInitOptions::default(),
+        )
+        .await?;
+
+    for op in ["and", "or"] {
+        let view = table
+            .view(Some(ViewConfigUpdate {
+                filter: Some(vec![
+                    Filter::new("a", op, FilterTerm::Array(vec![])).into(),
+                    float("b", "==", 2.0),
+                ]),
+                ..ViewConfigUpdate::default()
+            }))
+            .await;
+
+        assert!(view.is_err());
+    }
+
+    table.delete().await?;
+    client.close().await;
+    Ok(())
+}
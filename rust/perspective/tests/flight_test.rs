@@ -0,0 +1,94 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#![cfg(feature = "flight")]
+
+use std::error::Error;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, Int32Array, RecordBatch};
+use arrow_flight::encode::FlightDataEncoderBuilder;
+use arrow_flight::error::FlightError;
+use arrow_flight::{FlightClient, FlightDescriptor};
+use futures::{stream, TryStreamExt};
+use perspective::flight::{FlightTicket, PerspectiveFlightService};
+use perspective::server::Server;
+use perspective_client::{TableInitOptions, UpdateData};
+use perspective_server::LocalClient;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::{Channel, Server as GrpcServer};
+use tonic::Code;
+
+#[tokio::test]
+async fn test_flight_get_and_put() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let local = LocalClient::new(&server);
+    let mut options = TableInitOptions::default();
+    options.set_name("Table1");
+    let table = local
+        .table(UpdateData::Csv("x\n1\n2".to_owned()).into(), options)
+        .await?;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?);
+    tokio::spawn(
+        GrpcServer::builder()
+            .add_service(PerspectiveFlightService::new(server).into_service())
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+
+    let channel = Channel::from_shared(url)?.connect().await?;
+    let mut client = FlightClient::new(channel);
+    let infos = client
+        .list_flights("")
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(infos.len(), 1);
+    assert_eq!(infos[0].total_records, 2);
+
+    let ticket = FlightTicket {
+        table: "Table1".to_owned(),
+        config: None,
+    };
+
+    let batches = client
+        .do_get(ticket.to_ticket()?)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let rows: usize = batches.iter().map(|x| x.num_rows()).sum();
+    assert_eq!(rows, 2);
+
+    let column: ArrayRef = Arc::new(Int32Array::from(vec![3]));
+    let batch = RecordBatch::try_from_iter(vec![("x", column)])?;
+    let data = FlightDataEncoderBuilder::new()
+        .with_flight_descriptor(Some(FlightDescriptor::new_path(vec!["Table1".to_owned()])))
+        .build(stream::iter(vec![Ok(batch)]));
+
+    client.do_put(data).await?.try_collect::<Vec<_>>().await?;
+    assert_eq!(table.size().await?, 3);
+
+    let ticket = FlightTicket {
+        table: "Table1".to_owned(),
+        config: Some(serde_json::from_str(r#"{"columns": ["y"]}"#)?),
+    };
+
+    match client.do_get(ticket.to_ticket()?).await {
+        Err(FlightError::Tonic(status)) => assert_eq!(status.code(), Code::InvalidArgument),
+        _ => panic!("Expected an invalid argument error"),
+    }
+
+    local.close().await;
+    Ok(())
+}
@@ -0,0 +1,202 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#![cfg(feature = "framed")]
+
+use std::error::Error;
+use std::time::Duration;
+
+use perspective::framed;
+use perspective::server::Server;
+use perspective_client::{TableInitOptions, UpdateData};
+use perspective_server::LocalClient;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::oneshot;
+
+#[tokio::test]
+async fn test_framed_tcp_shutdown() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let local = LocalClient::new(&server);
+    let mut options = TableInitOptions::default();
+    options.set_name("Table1");
+    local
+        .table(UpdateData::Csv("x,y\n1,2\n3,4".to_owned()).into(), options)
+        .await?;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let (shutdown, on_shutdown) = oneshot::channel::<()>();
+    let serve = tokio::spawn(framed::serve_tcp(server.clone(), listener, async {
+        on_shutdown.await.ok();
+    }));
+
+    let client = framed::connect_tcp(addr).await?;
+    let table = client.open_table("Table1".to_owned()).await?;
+    assert_eq!(table.size().await?, 2);
+    assert_eq!(server.sessions().len(), 2);
+
+    shutdown.send(()).ok();
+    tokio::time::timeout(Duration::from_secs(5), serve).await??;
+    assert_eq!(server.sessions().len(), 1);
+    client.close().await;
+    local.close().await;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_framed_unix_close() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let path = std::env::temp_dir().join(format!("perspective-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listener = tokio::net::UnixListener::bind(&path)?;
+    tokio::spawn(framed::serve_unix(
+        server.clone(),
+        listener,
+        std::future::pending(),
+    ));
+
+    let client = framed::connect_unix(&path).await?;
+    assert!(client.get_hosted_table_names().await?.is_empty());
+    client.close().await;
+    for _ in 0..100 {
+        if server.sessions().is_empty() {
+            break;
+        }
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    assert!(server.sessions().is_empty());
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_framed_large_frames() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(framed::serve_tcp(
+        server.clone(),
+        listener,
+        std::future::pending(),
+    ));
+
+    // Larger than the 8 MiB default of `LengthDelimitedCodec`, both as a
+    // request and as a response.
+    let rows = 1_500_000;
+    let mut csv = String::from("x\n");
+    for i in 0..rows {
+        csv.push_str(&format!("{}\n", 1_000_000 + i));
+    }
+
+    assert!(csv.len() > 8 * 1024 * 1024);
+    let client = framed::connect_tcp(addr).await?;
+    let table = client
+        .table(UpdateData::Csv(csv).into(), TableInitOptions::default())
+        .await?;
+
+    assert_eq!(table.size().await?, rows);
+    let view = table.view(None).await?;
+    let arrow = view.to_arrow(Default::default()).await?;
+    assert!(arrow.len() > 8 * 1024 * 1024);
+    view.delete().await?;
+    table.delete().await?;
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_framed_max_frame_length() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let config = framed::FramedConfig::default().max_frame_length(1024);
+    tokio::spawn(framed::serve_with_config(
+        server.clone(),
+        tokio_stream::wrappers::TcpListenerStream::new(listener),
+        std::future::pending(),
+        config,
+    ));
+
+    let client = framed::connect_tcp(addr).await?;
+    assert_eq!(server.sessions().len(), 1);
+
+    // The server ends the connection on receiving the oversized frame, so
+    // the request itself is never answered.
+    let csv = format!("x\n{}", "1\n".repeat(1024));
+    let request = tokio::spawn(async move {
+        client
+            .table(UpdateData::Csv(csv).into(), TableInitOptions::default())
+            .await
+    });
+
+    for _ in 0..100 {
+        if server.sessions().is_empty() {
+            break;
+        }
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    assert!(server.sessions().is_empty());
+    request.abort();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_framed_client_drop() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(framed::serve_tcp(
+        server.clone(),
+        listener,
+        std::future::pending(),
+    ));
+
+    let client = framed::connect_tcp(addr).await?;
+    assert_eq!(server.sessions().len(), 1);
+    drop(client);
+    for _ in 0..100 {
+        if server.sessions().is_empty() {
+            break;
+        }
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    assert!(server.sessions().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_framed_rejects_oversized_length_prefix() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(framed::serve_tcp(
+        server.clone(),
+        listener,
+        std::future::pending(),
+    ));
+
+    // A lone length prefix declaring a frame larger than the default limit
+    // ends the connection, rather than waiting for (and reserving) 4 GiB.
+    let mut stream = tokio::net::TcpStream::connect(addr).await?;
+    stream.write_all(&u32::MAX.to_be_bytes()).await?;
+    let mut buf = vec![];
+    let read = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut buf)).await?;
+    assert_eq!(read?, 0);
+    Ok(())
+}
@@ -0,0 +1,80 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#![cfg(feature = "grpc")]
+
+use std::error::Error;
+use std::time::Duration;
+
+use perspective::grpc::GrpcService;
+use perspective::server::Server;
+use perspective_client::{TableInitOptions, UpdateData};
+use perspective_server::LocalClient;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::{Channel, Server as GrpcServer};
+
+async fn start_server(server: Server) -> Result<Channel, Box<dyn Error>> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?);
+    tokio::spawn(
+        GrpcServer::builder()
+            .add_service(GrpcService::new(server))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+
+    Ok(Channel::from_shared(url)?.connect().await?)
+}
+
+/// Wait for the [`Server`] to have `n` sessions.
+async fn wait_for_sessions(server: &Server, n: usize) -> Result<(), Box<dyn Error>> {
+    for _ in 0..100 {
+        if server.sessions().len() == n {
+            return Ok(());
+        }
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    Err(format!("Expected {} sessions", n).into())
+}
+
+#[tokio::test]
+async fn test_grpc_open_table() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let local = LocalClient::new(&server);
+    let mut options = TableInitOptions::default();
+    options.set_name("Table1");
+    local
+        .table(UpdateData::Csv("x,y\n1,2\n3,4".to_owned()).into(), options)
+        .await?;
+
+    let channel = start_server(server.clone()).await?;
+    let client = perspective::grpc::connect(channel).await?;
+    assert_eq!(client.get_hosted_table_names().await?, vec!["Table1"]);
+    let table = client.open_table("Table1".to_owned()).await?;
+    assert_eq!(table.size().await?, 2);
+    assert_eq!(server.sessions().len(), 2);
+    client.close().await;
+    assert_eq!(server.sessions().len(), 1);
+    local.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_grpc_drop_ends_call() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let channel = start_server(server.clone()).await?;
+    let client = perspective::grpc::connect(channel).await?;
+    wait_for_sessions(&server, 1).await?;
+    drop(client);
+    wait_for_sessions(&server, 0).await
+}
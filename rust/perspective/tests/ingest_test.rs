@@ -0,0 +1,228 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::error::Error;
+
+use perspective::server::Server;
+use perspective_client::{
+    ClientError, ColumnType, IngestMode, IngestOptions, TableData, TableInitOptions, UpdateData,
+    UpdateOptions, ViewWindow,
+};
+use perspective_server::LocalClient;
+
+#[tokio::test]
+async fn test_ingest_report() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let schema = TableData::Schema(vec![
+        ("x".to_owned(), ColumnType::Integer),
+        ("y".to_owned(), ColumnType::String),
+    ]);
+
+    let table = client.table(schema, TableInitOptions::default()).await?;
+    let options = UpdateOptions {
+        ingest: Some(IngestOptions {
+            mode: IngestMode::Lenient,
+            rejects_table: Some("rejects".to_owned()),
+        }),
+        ..UpdateOptions::default()
+    };
+
+    let data = r#"[{"x": 1, "y": "a"}, {"x": "2", "y": 3}, {"x": "abc", "y": "c"}]"#;
+    let report = table
+        .update_with_report(UpdateData::JsonRows(data.to_owned()), options)
+        .await?;
+
+    assert_eq!(report.accepted, vec![0, 1]);
+    assert_eq!(report.coerced.len(), 2);
+    assert!(report.ignored.is_empty());
+    assert_eq!(report.rejected.len(), 1);
+    assert_eq!(report.rejected[0].row, 2);
+    assert_eq!(report.rejected[0].column, "x");
+    assert_eq!(table.size().await?, 2);
+
+    let view = table.view(None).await?;
+    let json = view.to_columns_string(ViewWindow::default()).await?;
+    assert!(json.contains(r#""x":[1,2]"#));
+    assert!(json.contains(r#""y":["a","3"]"#));
+
+    let rejects = client.open_table("rejects".to_owned()).await?;
+    assert_eq!(rejects.size().await?, 1);
+    assert_eq!(rejects.columns().await?, vec![
+        "row", "column", "reason", "data"
+    ]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ingest_strict() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let options = TableInitOptions {
+        ingest: Some(IngestOptions {
+            mode: IngestMode::Strict,
+            rejects_table: None,
+        }),
+        ..TableInitOptions::default()
+    };
+
+    let data = UpdateData::JsonRows(r#"[{"x": 1}, {"x": 2}]"#.to_owned());
+    let (table, report) = client
+        .table_with_report(data.into(), options.clone())
+        .await?;
+    assert_eq!(report.accepted, vec![0, 1]);
+    assert!(report.rejected.is_empty());
+    assert_eq!(table.schema().await?["x"], ColumnType::Integer);
+
+    let data = UpdateData::JsonRows(r#"[{"x": 1}, {"x": true}]"#.to_owned());
+    let err = client.table(data.into(), options).await.unwrap_err();
+    assert!(matches!(err, ClientError::IngestRejected(issue) if issue.row == 1));
+    assert_eq!(client.get_hosted_table_names().await?.len(), 1);
+    table.delete().await?;
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ingest_strict_creates_no_table() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let options = TableInitOptions {
+        ingest: Some(IngestOptions {
+            mode: IngestMode::Strict,
+            rejects_table: None,
+        }),
+        ..TableInitOptions::default()
+    };
+
+    let data = UpdateData::JsonColumns(r#"{"x": [1, 2.5, "abc"]}"#.to_owned());
+    let err = client.table(data.into(), options).await.unwrap_err();
+    assert!(matches!(err, ClientError::IngestRejected(issue) if issue.row == 2));
+    assert!(client.get_hosted_table_names().await?.is_empty());
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ingest_dates() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let schema = TableData::Schema(vec![
+        ("d".to_owned(), ColumnType::Date),
+        ("t".to_owned(), ColumnType::Datetime),
+    ]);
+
+    let table = client.table(schema, TableInitOptions::default()).await?;
+    let options = UpdateOptions {
+        ingest: Some(IngestOptions {
+            mode: IngestMode::Lenient,
+            rejects_table: None,
+        }),
+        ..UpdateOptions::default()
+    };
+
+    let data = r#"[
+        {"d": "2024-02-29", "t": "2024-01-01T12:30:00.000Z"},
+        {"d": "2023-02-29", "t": "2024-01-01 12:30:00"},
+        {"d": "01/31/2024", "t": "not a date"},
+        {"d": 1704067200000, "t": 1704067200000, "z": 1}
+    ]"#;
+
+    let report = table
+        .update_with_report(UpdateData::JsonRows(data.to_owned()), options)
+        .await?;
+
+    assert_eq!(report.accepted, vec![0, 3]);
+    assert_eq!(report.rejected.len(), 2);
+    assert_eq!(
+        (report.rejected[0].row, report.rejected[0].column.as_str()),
+        (1, "d")
+    );
+    assert_eq!(
+        (report.rejected[1].row, report.rejected[1].column.as_str()),
+        (2, "t")
+    );
+    assert!(report.coerced.is_empty());
+    assert_eq!(report.ignored, vec!["z"]);
+    assert_eq!(table.size().await?, 2);
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ingest_infers_schema() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let options = TableInitOptions {
+        ingest: Some(IngestOptions {
+            mode: IngestMode::Lenient,
+            rejects_table: None,
+        }),
+        ..TableInitOptions::default()
+    };
+
+    let data = r#"{"s": ["a", null], "i": [1, 2], "f": [1, 2.5], "b": [null, true],
+        "d": ["2024-01-01", "2024-01-02"], "t": ["2024-01-01", "2024-01-02T00:00"]}"#;
+
+    let (table, report) = client
+        .table_with_report(UpdateData::JsonColumns(data.to_owned()).into(), options)
+        .await?;
+
+    assert_eq!(report.accepted, vec![0, 1]);
+    assert!(report.rejected.is_empty());
+    assert_eq!(table.columns().await?, vec!["s", "i", "f", "b", "d", "t"]);
+    let schema = table.schema().await?;
+    assert_eq!(schema["s"], ColumnType::String);
+    assert_eq!(schema["i"], ColumnType::Integer);
+    assert_eq!(schema["f"], ColumnType::Float);
+    assert_eq!(schema["b"], ColumnType::Boolean);
+    assert_eq!(schema["d"], ColumnType::Date);
+    assert_eq!(schema["t"], ColumnType::Datetime);
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ingest_integer_range() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let schema = TableData::Schema(vec![("x".to_owned(), ColumnType::Integer)]);
+    let table = client.table(schema, TableInitOptions::default()).await?;
+    let options = UpdateOptions {
+        ingest: Some(IngestOptions {
+            mode: IngestMode::Lenient,
+            rejects_table: None,
+        }),
+        ..UpdateOptions::default()
+    };
+
+    let data = r#"[{"x": 2147483647}, {"x": 10000000000}, {"x": "-2147483649"}, {"x": 3e9}]"#;
+    let report = table
+        .update_with_report(UpdateData::JsonRows(data.to_owned()), options)
+        .await?;
+
+    assert_eq!(report.accepted, vec![0]);
+    let rows = report.rejected.iter().map(|x| x.row).collect::<Vec<_>>();
+    assert_eq!(rows, vec![1, 2, 3]);
+    assert!(
+        report
+            .rejected
+            .iter()
+            .all(|x| x.reason.contains("out of range"))
+    );
+
+    let view = table.view(None).await?;
+    let json = view.to_columns_string(ViewWindow::default()).await?;
+    assert!(json.contains(r#""x":[2147483647]"#));
+    client.close().await;
+    Ok(())
+}
@@ -0,0 +1,81 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#![cfg(feature = "parquet")]
+
+use std::error::Error;
+
+use perspective::server::Server;
+use perspective_client::{
+    ParquetCompression, ParquetReadOptions, TableInitOptions, UpdateData, UpdateOptions, ViewWindow,
+};
+use perspective_server::LocalClient;
+
+#[tokio::test]
+async fn test_parquet_roundtrip() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let mut options = TableInitOptions::default();
+    options.set_name("Table1");
+    let table = client
+        .table(
+            UpdateData::Csv("x,y,z\n1,a,1.5\n2,b,2.5".to_owned()).into(),
+            options,
+        )
+        .await?;
+
+    let view = table.view(None).await?;
+    let parquet = view
+        .to_parquet(ViewWindow::default(), ParquetCompression::Snappy)
+        .await?;
+
+    assert_eq!(&parquet[..4], b"PAR1");
+    let options = TableInitOptions {
+        parquet: Some(ParquetReadOptions {
+            columns: Some(vec!["z".to_owned(), "x".to_owned()]),
+            row_groups: None,
+        }),
+        ..TableInitOptions::default()
+    };
+
+    let table2 = client
+        .table(UpdateData::Parquet(parquet.clone()).into(), options)
+        .await?;
+
+    let view2 = table2.view(None).await?;
+    assert_eq!(table2.columns().await?, vec!["x", "z"]);
+    assert_eq!(
+        view2.to_csv(ViewWindow::default()).await?,
+        "\"x\",\"z\"\n1,1.5\n2,2.5\n"
+    );
+
+    let options = UpdateOptions {
+        parquet: Some(ParquetReadOptions {
+            columns: Some(vec!["x".to_owned(), "z".to_owned()]),
+            row_groups: Some(vec![1]),
+        }),
+        ..UpdateOptions::default()
+    };
+
+    assert!(
+        table2
+            .update(UpdateData::Parquet(parquet), options)
+            .await
+            .is_err()
+    );
+
+    view2.delete().await?;
+    table2.delete().await?;
+    view.delete().await?;
+    client.close().await;
+    Ok(())
+}
@@ -0,0 +1,60 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#![cfg(feature = "polars")]
+
+use std::error::Error;
+
+use perspective::server::Server;
+use perspective_client::polars::df;
+use perspective_client::polars::prelude::{IntoLazy, col, lit};
+use perspective_client::{
+    ColumnType, TableData, TableInitOptions, UpdateData, UpdateOptions, ViewWindow,
+};
+use perspective_server::LocalClient;
+
+#[tokio::test]
+async fn test_polars_roundtrip() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let df = df!(
+        "x" => [1i32, 2, 3],
+        "y" => ["a", "b", "c"],
+        "z" => [1.5f64, 2.5, 3.5],
+    )?;
+
+    let schema = TableData::try_from(&df.schema())?;
+    let table = client.table(schema, TableInitOptions::default()).await?;
+    let table_schema = table.schema().await?;
+    assert_eq!(table_schema["x"], ColumnType::Integer);
+    assert_eq!(table_schema["y"], ColumnType::String);
+    assert_eq!(table_schema["z"], ColumnType::Float);
+
+    let options = UpdateOptions::default();
+    table
+        .update(UpdateData::try_from(df.clone())?, options.clone())
+        .await?;
+
+    let lazy = df.lazy().filter(col("x").gt(lit(1)));
+    table.update(UpdateData::try_from(lazy)?, options).await?;
+    assert_eq!(table.size().await?, 5);
+
+    let view = table.view(None).await?;
+    let output = view.to_polars(ViewWindow::default()).await?;
+    assert_eq!(output.height(), 5);
+    assert_eq!(output.get_column_names(), vec!["x", "y", "z"]);
+    assert_eq!(output.column("z")?.f64()?.sum(), Some(13.5));
+    view.delete().await?;
+    table.delete().await?;
+    client.close().await;
+    Ok(())
+}
@@ -0,0 +1,130 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+#![cfg(feature = "axum-ws")]
+
+use std::error::Error;
+use std::time::Duration;
+
+use perspective::server::{PollLoopOptions, Server};
+use perspective_client::{
+    Client, OnUpdateOptions, Session, TableInitOptions, UpdateData, UpdateOptions,
+};
+use tokio::sync::mpsc;
+
+/// A [`Client`] on its own [`Session`] which, like a websocket connection
+/// that isn't currently sending anything, never calls [`Session::poll`]. A
+/// `LocalClient` polls after each of its own requests, so these tests use
+/// this instead to isolate the poll loop.
+async fn unpolled_client(server: &Server) -> Result<Client, Box<dyn Error>> {
+    let (req_send, mut req_recv) = mpsc::unbounded_channel::<Vec<u8>>();
+    let (resp_send, mut resp_recv) = mpsc::unbounded_channel::<Vec<u8>>();
+    let client = Client::new_with_callback(move |msg| {
+        let req_send = req_send.clone();
+        Box::pin(async move { Ok(req_send.send(msg)?) })
+    });
+
+    let session = server
+        .new_session_with_callback(move |msg| {
+            let resp_send = resp_send.clone();
+            let msg = msg.to_vec();
+            Box::pin(async move { Ok(resp_send.send(msg)?) })
+        })
+        .await;
+
+    tokio::spawn({
+        let client = client.clone();
+        async move {
+            loop {
+                tokio::select! {
+                    Some(req) = req_recv.recv() => session.handle_request(&req).await.unwrap(),
+                    Some(resp) = resp_recv.recv() => {
+                        client.handle_response(&resp).await.unwrap();
+                    },
+                    else => break,
+                }
+            }
+
+            session.close().await;
+        }
+    });
+
+    client.init().await?;
+    Ok(client)
+}
+
+/// Update a table from one unpolled [`Client`] and wait for a view of it on
+/// another to be notified, returning `false` on timeout.
+async fn is_update_delivered(server: &Server) -> Result<bool, Box<dyn Error>> {
+    let feed = unpolled_client(server).await?;
+    let mut options = TableInitOptions::default();
+    options.set_name("feed");
+    let table = feed
+        .table(UpdateData::Csv("x\n1".to_owned()).into(), options)
+        .await?;
+
+    let subscriber = unpolled_client(server).await?;
+    let view = subscriber
+        .open_table("feed".to_owned())
+        .await?
+        .view(None)
+        .await?;
+    let (send, mut recv) = mpsc::unbounded_channel();
+    view.on_update(
+        move |_| {
+            let send = send.clone();
+            async move { send.send(()).unwrap() }
+        },
+        OnUpdateOptions::default(),
+    )
+    .await?;
+
+    table
+        .update(UpdateData::Csv("x\n2".to_owned()), UpdateOptions::default())
+        .await?;
+
+    Ok(tokio::time::timeout(Duration::from_secs(1), recv.recv())
+        .await
+        .is_ok())
+}
+
+#[tokio::test]
+async fn test_updates_are_not_delivered_without_poll() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    assert!(!is_update_delivered(&server).await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_poll_loop_interval_delivers_updates() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let poll_loop = server.spawn_poll_loop(PollLoopOptions {
+        interval: Some(Duration::from_millis(50)),
+        coalesce: None,
+    });
+
+    assert!(is_update_delivered(&server).await?);
+    poll_loop.abort();
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_poll_loop_on_change_coalesces_updates() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let poll_loop = server.spawn_poll_loop(PollLoopOptions {
+        interval: None,
+        coalesce: Some(Duration::from_millis(50)),
+    });
+
+    assert!(is_update_delivered(&server).await?);
+    poll_loop.abort();
+    Ok(())
+}
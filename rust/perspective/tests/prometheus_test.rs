@@ -0,0 +1,88 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#![cfg(feature = "prometheus")]
+
+use std::error::Error;
+
+use perspective::server::Server;
+use perspective_client::{TableInitOptions, UpdateData, UpdateOptions};
+use perspective_server::LocalClient;
+
+#[tokio::test]
+async fn test_render_prometheus_metrics() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let mut options = TableInitOptions::default();
+    options.set_name("Table1");
+    let table = client
+        .table(UpdateData::Csv("x,y\n1,2".to_owned()).into(), options)
+        .await?;
+
+    table
+        .update(
+            UpdateData::Csv("x,y\n3,4\n5,6".to_owned()),
+            UpdateOptions::default(),
+        )
+        .await?;
+
+    let _view = table.view(None).await?;
+    let _ = perspective::prometheus::render(&server).await?;
+    let text = perspective::prometheus::render(&server).await?;
+    assert!(text.contains("perspective_tables 1\n"));
+    assert!(text.contains("perspective_table_rows{table=\"Table1\"} 3\n"));
+    assert!(text.contains("perspective_sessions 1\n"));
+    assert!(text.contains("perspective_table_rows_updated_total{table=\"Table1\"} 2\n"));
+    assert!(!text.contains("kind=\"table_size\""));
+    assert!(text.contains("perspective_views{type=\"flat\"} 1\n"));
+    assert!(text.contains("perspective_table_updates_total{table=\"Table1\"} 1\n"));
+    assert!(text.contains("perspective_request_duration_seconds_count{kind=\"table_update\"} 1\n"));
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_table_metrics_pruned() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let mut options = TableInitOptions::default();
+    options.set_name("Table1");
+    let table = client
+        .table(UpdateData::Csv("x,y\n1,2".to_owned()).into(), options)
+        .await?;
+
+    table
+        .update(
+            UpdateData::Csv("x,y\n3,4".to_owned()),
+            UpdateOptions::default(),
+        )
+        .await?;
+
+    assert_eq!(server.metrics().table_updates.get("Table1"), Some(&1));
+    table.delete().await?;
+    assert!(server.metrics().table_updates.is_empty());
+    assert!(server.metrics().table_rows_updated.is_empty());
+
+    // Updates which fail (here, of a deleted table) are not counted.
+    let result = table
+        .update(
+            UpdateData::Csv("x,y\n5,6".to_owned()),
+            UpdateOptions::default(),
+        )
+        .await;
+
+    assert!(result.is_err());
+    assert!(server.metrics().table_updates.is_empty());
+    assert!(server.metrics().table_rows_updated.is_empty());
+    client.close().await;
+    Ok(())
+}
@@ -0,0 +1,91 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#![cfg(feature = "rest")]
+
+use std::error::Error;
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use perspective::server::Server;
+use perspective_client::{TableInitOptions, UpdateData};
+use perspective_server::LocalClient;
+use tower::ServiceExt;
+
+async fn request(app: &Router, req: Request<Body>) -> Result<(StatusCode, String), Box<dyn Error>> {
+    let resp = app.clone().oneshot(req).await?;
+    let status = resp.status();
+    let body = to_bytes(resp.into_body(), usize::MAX).await?;
+    Ok((status, String::from_utf8(body.to_vec())?))
+}
+
+#[tokio::test]
+async fn test_rest_gateway() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let mut options = TableInitOptions::default();
+    options.set_name("Table1");
+    client
+        .table(UpdateData::Csv("x,y\n1,a\n2,b".to_owned()).into(), options)
+        .await?;
+
+    let app = perspective::rest::router().with_state(server);
+    let get = |uri: &str| Request::get(uri).body(Body::empty());
+    assert_eq!(
+        request(&app, get("/tables")?).await?,
+        (StatusCode::OK, r#"["Table1"]"#.to_owned())
+    );
+
+    let (status, schema) = request(&app, get("/tables/Table1/schema")?).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert!(schema.contains(r#""x":"integer""#));
+    assert_eq!(
+        request(&app, get("/tables/Table2/schema")?).await?.0,
+        StatusCode::NOT_FOUND
+    );
+
+    let update = Request::post("/tables/Table1/update")
+        .header("Content-Type", "application/json")
+        .body(Body::from(r#"[{"x": 3, "y": "c"}]"#))?;
+
+    assert_eq!(request(&app, update).await?.0, StatusCode::NO_CONTENT);
+    let query = Request::post("/tables/Table1/query")
+        .header("Content-Type", "application/json")
+        .header("Accept", "text/csv")
+        .body(Body::from(r#"{"config": {"columns": ["x"]}}"#))?;
+
+    assert_eq!(
+        request(&app, query).await?,
+        (StatusCode::OK, "\"x\"\n1\n2\n3\n".to_owned())
+    );
+
+    let query = Request::post("/tables/Table1/query")
+        .header("Content-Type", "application/json")
+        .header("Accept", "application/vnd.apache.arrow.file")
+        .body(Body::from(r#"{"config": {"columns": ["x"]}}"#))?;
+
+    assert_eq!(request(&app, query).await?.0, StatusCode::NOT_ACCEPTABLE);
+    let query = Request::post("/tables/Table1/query")
+        .header("Content-Type", "application/json")
+        .header("Accept", "application/vnd.apache.arrow.stream")
+        .body(Body::from(r#"{"config": {"columns": ["x"]}}"#))?;
+
+    assert_eq!(request(&app, query).await?.0, StatusCode::OK);
+    let query = Request::post("/tables/Table1/query")
+        .header("Content-Type", "application/json")
+        .body(Body::from(r#"{"config": {"columns": ["z"]}}"#))?;
+
+    assert_eq!(request(&app, query).await?.0, StatusCode::BAD_REQUEST);
+    client.close().await;
+    Ok(())
+}
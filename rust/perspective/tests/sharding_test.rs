@@ -0,0 +1,101 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::error::Error;
+
+use perspective::server::{ExecutionStrategy, Server, ServerOptions, ShardPlacement};
+use perspective_client::{TableInitOptions, UpdateData};
+use perspective_server::LocalClient;
+
+#[tokio::test]
+async fn test_sharded_server_aggregates_hosted_tables() -> Result<(), Box<dyn Error>> {
+    let server = Server::new(ServerOptions {
+        execution: ExecutionStrategy::WorkerThread,
+        shards: Some(2),
+        placement: ShardPlacement::explicit(|name| Some(if name == "a" { 0 } else { 1 })),
+    })?;
+
+    let client = LocalClient::new(&server);
+    for name in ["a", "b"] {
+        let mut options = TableInitOptions::default();
+        options.set_name(name);
+        client
+            .table(UpdateData::Csv("x\n1\n2".to_owned()).into(), options)
+            .await?;
+    }
+
+    let mut names = client.get_hosted_table_names().await?;
+    names.sort();
+    assert_eq!(names, vec!["a".to_owned(), "b".to_owned()]);
+    for name in names {
+        let table = client.open_table(name).await?;
+        let view = table.view(None).await?;
+        assert_eq!(view.num_rows().await?, 2);
+        view.delete().await?;
+    }
+
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sharded_server_recreates_deleted_tables() -> Result<(), Box<dyn Error>> {
+    let server = Server::new(ServerOptions {
+        shards: Some(2),
+        ..ServerOptions::default()
+    })?;
+
+    let client = LocalClient::new(&server);
+    for _ in 0..2 {
+        let mut options = TableInitOptions::default();
+        options.set_name("a");
+        let table = client
+            .table(UpdateData::Csv("x\n1\n2".to_owned()).into(), options)
+            .await?;
+
+        let bad_view = table
+            .view(Some(perspective_client::config::ViewConfigUpdate {
+                columns: Some(vec![Some("y".to_owned())]),
+                ..Default::default()
+            }))
+            .await;
+
+        assert!(bad_view.is_err());
+        let view = table.view(None).await?;
+        assert_eq!(view.num_rows().await?, 2);
+        view.delete().await?;
+        table.delete().await?;
+    }
+
+    assert!(client.get_hosted_table_names().await?.is_empty());
+    client.close().await;
+    Ok(())
+}
+
+#[cfg(not(windows))]
+#[tokio::test]
+async fn test_sharded_server_sums_system_info() -> Result<(), Box<dyn Error>> {
+    let single = Server::new(ServerOptions::default())?;
+    let sharded = Server::new(ServerOptions {
+        shards: Some(2),
+        ..ServerOptions::default()
+    })?;
+
+    let single_client = LocalClient::new(&single);
+    let sharded_client = LocalClient::new(&sharded);
+    let single_heap = single_client.system_info().await?.heap_size;
+    let sharded_heap = sharded_client.system_info().await?.heap_size;
+    assert!(sharded_heap > single_heap);
+    single_client.close().await;
+    sharded_client.close().await;
+    Ok(())
+}
@@ -0,0 +1,98 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#![cfg(feature = "source")]
+
+use std::error::Error;
+use std::time::Duration;
+
+use futures::StreamExt;
+use perspective::server::Server;
+use perspective::source::{self, LineFormat, SourcePolicy, TableSourceExt, TailOptions};
+use perspective_client::{ColumnType, TableData, TableInitOptions, UpdateData};
+use perspective_server::LocalClient;
+
+#[tokio::test]
+async fn test_source_mpsc_batching() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let schema = TableData::Schema(vec![("x".to_owned(), ColumnType::Integer)]);
+    let table = client.table(schema, TableInitOptions::default()).await?;
+    let (tx, rx) = tokio::sync::mpsc::channel(4);
+    tokio::spawn(async move {
+        for x in 0..10 {
+            let row = format!("{{\"x\": {}}}", x);
+            tx.send(UpdateData::Ndjson(row)).await.unwrap();
+        }
+    });
+
+    let policy = SourcePolicy {
+        max_batch_size: 5,
+        max_batch_delay: Duration::from_secs(10),
+        ..SourcePolicy::default()
+    };
+
+    let stats = table.ingest(source::from_mpsc(rx), policy).await?;
+    assert_eq!(stats.items, 10);
+    assert_eq!(stats.batches, 2);
+    assert_eq!(stats.updates, 2);
+    assert_eq!(stats.errors, 0);
+    assert_eq!(table.size().await?, 10);
+    table.delete().await?;
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_source_csv_lines() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let schema = TableData::Schema(vec![
+        ("x".to_owned(), ColumnType::Integer),
+        ("y".to_owned(), ColumnType::String),
+    ]);
+
+    let table = client.table(schema, TableInitOptions::default()).await?;
+    let csv: &[u8] = b"x,y\n1,a\n\n2,b\n3,c\n";
+    let lines = source::from_lines(csv, LineFormat::Csv);
+    let stats = table.ingest(lines, SourcePolicy::default()).await?;
+    assert_eq!(stats.items, 3);
+    assert_eq!(stats.updates, 1);
+    assert_eq!(table.size().await?, 3);
+    table.delete().await?;
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_source_tail_file() -> Result<(), Box<dyn Error>> {
+    let path = std::env::temp_dir().join(format!("source_test_{}.ndjson", std::process::id()));
+    tokio::fs::write(&path, "{\"x\": 1}\n").await?;
+    let options = TailOptions {
+        from_start: true,
+        poll_interval: Duration::from_millis(10),
+        ..TailOptions::default()
+    };
+
+    let mut lines = Box::pin(source::tail_file(&path, options).await?);
+    assert!(matches!(lines.next().await, Some(UpdateData::Ndjson(x)) if x == "{\"x\": 1}"));
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .await?;
+    tokio::io::AsyncWriteExt::write_all(&mut file, b"{\"x\": 2}").await?;
+    tokio::io::AsyncWriteExt::write_all(&mut file, b"\n").await?;
+    assert!(matches!(lines.next().await, Some(UpdateData::Ndjson(x)) if x == "{\"x\": 2}"));
+    tokio::fs::remove_file(&path).await?;
+    Ok(())
+}
@@ -0,0 +1,77 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#![cfg(feature = "sql")]
+
+use std::error::Error;
+
+use perspective::server::Server;
+use perspective_client::{ClientError, TableInitOptions, UpdateData};
+use perspective_server::LocalClient;
+
+#[tokio::test]
+async fn test_sql_query() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let data = r#"{"x": [1, 2, 3, 4], "y": ["a", "b", "b", "c"]}"#;
+    let table = client
+        .table(
+            UpdateData::JsonColumns(data.to_owned()).into(),
+            TableInitOptions::default(),
+        )
+        .await?;
+
+    let (view, window) = table
+        .query("SELECT x * 10 AS z FROM t WHERE x > 1 ORDER BY x DESC LIMIT 2")
+        .await?;
+
+    let json = view.to_columns_string(window).await?;
+    assert_eq!(json, r#"{"z":[40,30]}"#);
+    view.delete().await?;
+
+    let (view, window) = table
+        .query("SELECT y, SUM(x) AS total FROM t GROUP BY y ORDER BY total DESC LIMIT 1")
+        .await?;
+
+    let json = view.to_columns_string(window).await?;
+    assert!(json.contains(r#""__ROW_PATH__":[["b"]]"#));
+    assert!(json.contains(r#""total":[5]"#));
+    view.delete().await?;
+
+    let (view, window) = table.query("SELECT COUNT(*) AS n FROM t").await?;
+    let json = view.to_columns_string(window).await?;
+    assert!(json.contains(r#""n":[4]"#));
+    view.delete().await?;
+
+    let (view, window) = table
+        .query("SELECT y, COUNT(x) AS n FROM t GROUP BY y ORDER BY y LIMIT 2 OFFSET 1")
+        .await?;
+
+    let json = view.to_columns_string(window).await?;
+    assert!(json.contains(r#""__ROW_PATH__":[["b"],["c"]]"#));
+    assert!(json.contains(r#""n":[2,1]"#));
+    view.delete().await?;
+
+    for sql in [
+        "SELECT * FROM t JOIN u ON t.x = u.x",
+        "SELECT x, y, COUNT(*) FROM t GROUP BY x, y",
+        "SELECT y, COUNT(x) AS n FROM t GROUP BY y ORDER BY y DESC",
+        "SELECT y, COUNT(x) AS n FROM t GROUP BY y ORDER BY n, y",
+    ] {
+        let result = table.query(sql).await;
+        assert!(matches!(result, Err(ClientError::SqlError(_))), "{}", sql);
+    }
+
+    table.delete().await?;
+    client.close().await;
+    Ok(())
+}
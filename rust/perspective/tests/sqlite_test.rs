@@ -0,0 +1,230 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#![cfg(feature = "sqlite")]
+
+use std::error::Error;
+use std::io::Cursor;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::Float64Type;
+use arrow_ipc::reader::StreamReader;
+use perspective::server::Server;
+use perspective::sqlite::SqliteServer;
+use perspective_client::config::ViewConfigUpdate;
+use perspective_client::proto::ViewPort;
+use perspective_client::{
+    ColumnType, Table, TableInitOptions, UpdateData, ViewWindow, VirtualServer,
+};
+use perspective_server::LocalClient;
+use serde_json::Value;
+
+const CSV: &str = "region,desk,qty,price
+EU,a,10,1.5
+US,b,25,2.0
+EU,b,3,3.25
+APAC,a,7,4.0
+US,a,40,0.5
+EU,c,12,6.0
+";
+
+fn sqlite() -> Result<SqliteServer, Box<dyn Error>> {
+    let server = SqliteServer::open_in_memory()?;
+    server.with_connection(|conn| {
+        conn.execute_batch(
+            "CREATE TABLE trades (region TEXT, desk TEXT, qty INTEGER, price REAL);
+             INSERT INTO trades VALUES
+                ('EU', 'a', 10, 1.5), ('US', 'b', 25, 2.0), ('EU', 'b', 3, 3.25),
+                ('APAC', 'a', 7, 4.0), ('US', 'a', 40, 0.5), ('EU', 'c', 12, 6.0);",
+        )
+    })?;
+
+    Ok(server)
+}
+
+/// Compare JSON, with numbers as `f64`.
+fn assert_json_eq(left: &Value, right: &Value) {
+    match (left, right) {
+        (Value::Number(x), Value::Number(y)) => {
+            let (x, y) = (x.as_f64().unwrap(), y.as_f64().unwrap());
+            assert!((x - y).abs() < 1e-9, "{} != {}", x, y);
+        },
+        (Value::Array(x), Value::Array(y)) => {
+            assert_eq!(x.len(), y.len(), "{:?} != {:?}", x, y);
+            x.iter().zip(y).for_each(|(x, y)| assert_json_eq(x, y));
+        },
+        (Value::Object(x), Value::Object(y)) => {
+            assert_eq!(x.keys().collect::<Vec<_>>(), y.keys().collect::<Vec<_>>());
+            x.values()
+                .zip(y.values())
+                .for_each(|(x, y)| assert_json_eq(x, y));
+        },
+        (x, y) => assert_eq!(x, y),
+    }
+}
+
+fn field_names(arrow: Vec<u8>) -> Result<Vec<String>, Box<dyn Error>> {
+    let reader = StreamReader::try_new(Cursor::new(arrow), None)?;
+    Ok(reader
+        .schema()
+        .fields()
+        .iter()
+        .map(|x| x.name().clone())
+        .collect())
+}
+
+/// Create `config` on both the engine's `table` and `sqlite`, and compare the
+/// results.
+async fn assert_conforms(
+    table: &Table,
+    sqlite: &SqliteServer,
+    config: &str,
+) -> Result<(), Box<dyn Error>> {
+    let config: ViewConfigUpdate = serde_json::from_str(config)?;
+    let view = table.view(Some(config.clone())).await?;
+    sqlite.table_make_view("trades", "view", config).await?;
+
+    let expected = view.to_columns_string(ViewWindow::default()).await?;
+    let actual = sqlite
+        .view_to_columns_string("view", ViewPort::default())
+        .await?;
+
+    assert_json_eq(
+        &serde_json::from_str(&actual)?,
+        &serde_json::from_str(&expected)?,
+    );
+
+    let expected = field_names(view.to_arrow(ViewWindow::default()).await?.to_vec())?;
+    let actual = field_names(sqlite.view_to_arrow("view", ViewPort::default()).await?)?;
+    assert_eq!(actual, expected);
+
+    let dimensions = sqlite.view_dimensions("view").await?;
+    assert_eq!(dimensions.num_view_rows, view.num_rows().await?);
+    assert_eq!(
+        sqlite.view_column_paths("view").await?,
+        view.column_paths().await?
+    );
+
+    view.delete().await?;
+    sqlite.view_delete("view").await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sqlite_conforms_to_engine() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let table = client
+        .table(
+            UpdateData::Csv(CSV.to_owned()).into(),
+            TableInitOptions::default(),
+        )
+        .await?;
+
+    let sqlite = sqlite()?;
+    let configs = [
+        r#"{"columns": ["region", "qty"], "filter": [["qty", ">", 5]], "sort": [["qty", "desc"]]}"#,
+        r#"{"columns": ["desk", "price"], "filter": [["desk", "==", "a"]]}"#,
+        r#"{"group_by": ["region"], "columns": ["qty", "price"], "aggregates": {"price": "avg"}, "sort": [["qty", "desc"]]}"#,
+        r#"{"group_by": ["region", "desk"], "columns": ["qty"], "filter": [["desk", "!=", "c"]]}"#,
+    ];
+
+    for config in configs {
+        assert_conforms(&table, &sqlite, config).await?;
+    }
+
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sqlite_windows_and_schema() -> Result<(), Box<dyn Error>> {
+    let sqlite = sqlite()?;
+    assert_eq!(sqlite.get_hosted_tables().await?, vec!["trades"]);
+    assert_eq!(sqlite.table_size("trades").await?, 6);
+    assert_eq!(sqlite.table_schema("trades").await?, vec![
+        ("region".to_owned(), ColumnType::String),
+        ("desk".to_owned(), ColumnType::String),
+        ("qty".to_owned(), ColumnType::Integer),
+        ("price".to_owned(), ColumnType::Float),
+    ]);
+
+    let config = serde_json::from_str(r#"{"columns": ["qty"]}"#)?;
+    sqlite.table_make_view("trades", "flat", config).await?;
+    let viewport = ViewPort {
+        start_row: Some(1),
+        end_row: Some(3),
+        ..ViewPort::default()
+    };
+
+    let json = sqlite.view_to_columns_string("flat", viewport).await?;
+    assert_eq!(json, r#"{"qty":[25,3]}"#);
+
+    sqlite.with_connection(|conn| {
+        conn.execute("INSERT INTO trades VALUES ('EU', 'd', 1, 1.0)", [])
+    })?;
+
+    let dimensions = sqlite.view_dimensions("flat").await?;
+    assert_eq!(dimensions.num_view_rows, 7);
+    assert_eq!(dimensions.num_table_rows, 7);
+
+    let config = serde_json::from_str(r#"{"expressions": {"x": "\"qty\" + 1"}}"#)?;
+    assert!(
+        sqlite
+            .table_make_view("trades", "expr", config)
+            .await
+            .is_err()
+    );
+
+    let config =
+        serde_json::from_str(r#"{"group_by": ["region"], "aggregates": {"qty": "median"}}"#)?;
+    assert!(
+        sqlite
+            .table_make_view("trades", "median", config)
+            .await
+            .is_err()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sqlite_64_bit_integers() -> Result<(), Box<dyn Error>> {
+    let sqlite = SqliteServer::open_in_memory()?;
+    sqlite.with_connection(|conn| {
+        conn.execute_batch(
+            "CREATE TABLE events (id BIGINT, ts INTEGER, n INT);
+             INSERT INTO events VALUES (1, 1700000000000, 1), (2, -1700000000000, 2);",
+        )
+    })?;
+
+    assert_eq!(sqlite.table_schema("events").await?, vec![
+        ("id".to_owned(), ColumnType::Integer),
+        ("ts".to_owned(), ColumnType::Float),
+        ("n".to_owned(), ColumnType::Integer),
+    ]);
+
+    let config = serde_json::from_str(r#"{"columns": ["ts"]}"#)?;
+    sqlite.table_make_view("events", "flat", config).await?;
+    let json = sqlite
+        .view_to_columns_string("flat", ViewPort::default())
+        .await?;
+
+    assert_eq!(json, r#"{"ts":[1700000000000.0,-1700000000000.0]}"#);
+    let arrow = sqlite.view_to_arrow("flat", ViewPort::default()).await?;
+    let batch = StreamReader::try_new(Cursor::new(arrow), None)?
+        .next()
+        .unwrap()?;
+
+    let ts = batch.column(0).as_primitive::<Float64Type>();
+    assert_eq!(ts.values(), &[1_700_000_000_000.0, -1_700_000_000_000.0]);
+    Ok(())
+}
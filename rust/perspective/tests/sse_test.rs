@@ -0,0 +1,155 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#![cfg(feature = "sse")]
+
+use std::error::Error;
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::routing::Router;
+use futures::{Stream, StreamExt};
+use perspective::server::Server;
+use perspective_client::{TableInitOptions, UpdateData, UpdateOptions};
+use perspective_server::LocalClient;
+use tower::ServiceExt;
+
+/// Read from an SSE body until an event named `name` has been received,
+/// returning its data.
+async fn next_event<S, E>(body: &mut S, name: &str) -> Result<String, Box<dyn Error>>
+where
+    S: Stream<Item = Result<axum::body::Bytes, E>> + Unpin,
+    E: Error + 'static,
+{
+    let mut text = String::new();
+    let header = format!("event: {}\n", name);
+    loop {
+        let chunk = body.next().await.ok_or("Stream ended")??;
+        text.push_str(std::str::from_utf8(&chunk)?);
+        if let Some(start) = text.find(&header) {
+            if let Some(end) = text[start..].find("\n\n") {
+                let event = &text[start + header.len()..start + end];
+                let data = event
+                    .lines()
+                    .filter_map(|x| x.strip_prefix("data: "))
+                    .collect::<Vec<_>>();
+
+                return Ok(data.join("\n"));
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_sse_subscription() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let mut options = TableInitOptions::default();
+    options.set_name("Table1");
+    let table = client
+        .table(UpdateData::Csv("x\n1\n2".to_owned()).into(), options)
+        .await?;
+
+    let app = Router::new()
+        .route("/tables/:name/events", perspective::sse::sse_handler())
+        .with_state(server.clone());
+
+    let req = Request::post("/tables/Table1/events")
+        .header("Content-Type", "application/json")
+        .body(Body::from(r#"{"columns": ["x"]}"#))?;
+
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let mut body = resp.into_body().into_data_stream();
+    let snapshot = next_event(&mut body, "snapshot").await?;
+    assert!(snapshot.contains(r#"{"x":1}"#) && snapshot.contains(r#"{"x":2}"#));
+    table
+        .update(UpdateData::Csv("x\n3".to_owned()), UpdateOptions::default())
+        .await?;
+
+    let update = next_event(&mut body, "update").await?;
+    assert!(update.contains(r#"{"x":3}"#) && !update.contains(r#"{"x":1}"#));
+    drop(body);
+    for _ in 0..100 {
+        if server.sessions().len() == 1 {
+            break;
+        }
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(server.sessions().len(), 1);
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sse_subscription_filtered_and_sorted() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let mut options = TableInitOptions::default();
+    options.set_name("Table1");
+    let table = client
+        .table(UpdateData::Csv("x\n1\n2".to_owned()).into(), options)
+        .await?;
+
+    let app = Router::new()
+        .route("/tables/:name/events", perspective::sse::sse_handler())
+        .with_state(server.clone());
+
+    let req = Request::post("/tables/Table1/events")
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            r#"{"columns": ["x"], "filter": [["x", ">", 1]], "sort": [["x", "desc"]]}"#,
+        ))?;
+
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let mut body = resp.into_body().into_data_stream();
+    let snapshot = next_event(&mut body, "snapshot").await?;
+    assert!(snapshot.contains(r#"{"x":2}"#) && !snapshot.contains(r#"{"x":1}"#));
+    table
+        .update(UpdateData::Csv("x\n3".to_owned()), UpdateOptions::default())
+        .await?;
+
+    let update = next_event(&mut body, "update").await?;
+    assert!(update.contains(r#"{"x":3}"#) && !update.contains(r#"{"x":1}"#));
+    assert_eq!(client.get_hosted_table_names().await?, vec!["Table1"]);
+    drop(body);
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sse_rejects_unknown_column() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let client = LocalClient::new(&server);
+    let mut options = TableInitOptions::default();
+    options.set_name("Table1");
+    client
+        .table(UpdateData::Csv("x\n1\n2".to_owned()).into(), options)
+        .await?;
+
+    let app = Router::new()
+        .route("/tables/:name/events", perspective::sse::sse_handler())
+        .with_state(server.clone());
+
+    let req = Request::post("/tables/Table1/events")
+        .header("Content-Type", "application/json")
+        .body(Body::from(r#"{"columns": ["y"]}"#))?;
+
+    let resp = app.oneshot(req).await?;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    client.close().await;
+    Ok(())
+}
@@ -0,0 +1,69 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#![cfg(all(feature = "tungstenite-ws", feature = "websocket-client"))]
+
+use std::error::Error;
+
+use perspective::server::Server;
+use perspective_client::{TableInitOptions, UpdateData};
+use perspective_server::LocalClient;
+
+async fn host_table(server: &Server) -> Result<LocalClient, Box<dyn Error>> {
+    let client = LocalClient::new(server);
+    let mut options = TableInitOptions::default();
+    options.set_name("Table1");
+    client
+        .table(UpdateData::Csv("x,y\n1,2\n3,4".to_owned()).into(), options)
+        .await?;
+
+    Ok(client)
+}
+
+#[tokio::test]
+async fn test_tungstenite_accept() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let local = host_table(&server).await?;
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let server = server.clone();
+            tokio::spawn(async move { perspective::tungstenite::accept(&server, stream).await });
+        }
+    });
+
+    let client = perspective::connect(&url).await?;
+    let table = client.open_table("Table1".to_owned()).await?;
+    assert_eq!(table.size().await?, 2);
+    local.close().await;
+    Ok(())
+}
+
+#[cfg(all(feature = "tower-ws", feature = "axum-ws"))]
+#[tokio::test]
+async fn test_tower_websocket_service() -> Result<(), Box<dyn Error>> {
+    use perspective::tower::WebsocketService;
+
+    let server = Server::default();
+    let local = host_table(&server).await?;
+    let app = axum::Router::new().route_service("/ws", WebsocketService::new(server));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}/ws", listener.local_addr()?);
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let client = perspective::connect(&url).await?;
+    let table = client.open_table("Table1".to_owned()).await?;
+    assert_eq!(table.size().await?, 2);
+    local.close().await;
+    Ok(())
+}
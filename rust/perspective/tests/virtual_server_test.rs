@@ -0,0 +1,189 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use async_lock::RwLock;
+use perspective_client::config::{ViewConfig, ViewConfigUpdate};
+use perspective_client::proto::{ViewDimensionsResp, ViewPort};
+use perspective_client::{
+    Client, ClientError, ClientHandler, ColumnType, Session, ViewWindow, VirtualServer,
+    VirtualServerResult, VirtualSession,
+};
+
+/// A single table `numbers`, with one column `x` of `0..10`.
+#[derive(Default)]
+struct Numbers {
+    views: Mutex<HashMap<String, ViewConfig>>,
+}
+
+impl VirtualServer for Numbers {
+    async fn get_hosted_tables(&self) -> VirtualServerResult<Vec<String>> {
+        Ok(vec!["numbers".to_owned()])
+    }
+
+    async fn table_schema(
+        &self,
+        _table_id: &str,
+    ) -> VirtualServerResult<Vec<(String, ColumnType)>> {
+        Ok(vec![("x".to_owned(), ColumnType::Integer)])
+    }
+
+    async fn table_size(&self, _table_id: &str) -> VirtualServerResult<u32> {
+        Ok(10)
+    }
+
+    async fn table_make_view(
+        &self,
+        _table_id: &str,
+        view_id: &str,
+        config: ViewConfigUpdate,
+    ) -> VirtualServerResult<()> {
+        let config = ViewConfig {
+            columns: config.columns.unwrap_or(vec![Some("x".to_owned())]),
+            ..ViewConfig::default()
+        };
+
+        self.views
+            .lock()
+            .unwrap()
+            .insert(view_id.to_owned(), config);
+        Ok(())
+    }
+
+    async fn view_get_config(&self, view_id: &str) -> VirtualServerResult<ViewConfig> {
+        Ok(self.views.lock().unwrap()[view_id].clone())
+    }
+
+    async fn view_schema(
+        &self,
+        _view_id: &str,
+    ) -> VirtualServerResult<HashMap<String, ColumnType>> {
+        Ok(HashMap::from([("x".to_owned(), ColumnType::Integer)]))
+    }
+
+    async fn view_column_paths(&self, _view_id: &str) -> VirtualServerResult<Vec<String>> {
+        Ok(vec!["x".to_owned()])
+    }
+
+    async fn view_dimensions(&self, _view_id: &str) -> VirtualServerResult<ViewDimensionsResp> {
+        Ok(ViewDimensionsResp {
+            num_table_rows: 10,
+            num_table_columns: 1,
+            num_view_rows: 10,
+            num_view_columns: 1,
+        })
+    }
+
+    async fn view_to_arrow(
+        &self,
+        _view_id: &str,
+        _viewport: ViewPort,
+    ) -> VirtualServerResult<Vec<u8>> {
+        Err("Arrow is not implemented".into())
+    }
+
+    async fn view_to_columns_string(
+        &self,
+        _view_id: &str,
+        viewport: ViewPort,
+    ) -> VirtualServerResult<String> {
+        let start = viewport.start_row.unwrap_or(0);
+        let end = viewport.end_row.unwrap_or(10).min(10);
+        let x = (start..end).map(|x| x.to_string()).collect::<Vec<_>>();
+        Ok(format!("{{\"x\":[{}]}}", x.join(",")))
+    }
+
+    async fn view_delete(&self, view_id: &str) -> VirtualServerResult<()> {
+        self.views.lock().unwrap().remove(view_id);
+        Ok(())
+    }
+}
+
+/// Connects a [`Client`] to a [`VirtualSession`] in-process.
+#[derive(Clone)]
+struct VirtualClient {
+    session: Arc<RwLock<Option<VirtualSession<Numbers>>>>,
+    responses: Arc<Mutex<Vec<Vec<u8>>>>,
+    client: Arc<OnceLock<Client>>,
+}
+
+impl ClientHandler for VirtualClient {
+    async fn send_request(&self, msg: Vec<u8>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if let Some(session) = self.session.read().await.as_ref() {
+            session.handle_request(&msg).await?;
+        }
+
+        let responses = std::mem::take(&mut *self.responses.lock().unwrap());
+        for resp in responses {
+            self.client.get().unwrap().handle_response(&resp).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_virtual_server() -> Result<(), Box<dyn Error>> {
+    let server = Arc::new(Numbers::default());
+    let responses: Arc<Mutex<Vec<Vec<u8>>>> = Arc::default();
+    let session = VirtualSession::new(server.clone(), {
+        let responses = responses.clone();
+        move |msg: &[u8]| {
+            responses.lock().unwrap().push(msg.to_vec());
+            Ok(())
+        }
+    });
+
+    let handler = VirtualClient {
+        session: Arc::new(RwLock::new(Some(session))),
+        responses,
+        client: Arc::default(),
+    };
+
+    let client = Client::new(handler.clone());
+    let _ = handler.client.set(client.clone());
+    client.init().await?;
+    assert_eq!(client.get_hosted_table_names().await?, vec!["numbers"]);
+
+    let table = client.open_table("numbers".to_owned()).await?;
+    assert_eq!(table.size().await?, 10);
+    assert_eq!(table.columns().await?, vec!["x"]);
+
+    let view = table.view(None).await?;
+    assert_eq!(view.get_config().await?.columns, vec![Some("x".to_owned())]);
+    assert_eq!(view.num_rows().await?, 10);
+
+    let window = ViewWindow {
+        start_row: Some(2.0),
+        end_row: Some(5.0),
+        ..ViewWindow::default()
+    };
+
+    assert_eq!(view.to_columns_string(window).await?, r#"{"x":[2,3,4]}"#);
+    let result = view.to_csv(ViewWindow::default()).await;
+    assert!(matches!(result, Err(ClientError::Internal(msg)) if msg.contains("to_csv")));
+
+    view.delete().await?;
+    let result = view.num_rows().await;
+    assert!(matches!(result, Err(ClientError::ViewNotFound)));
+
+    // Closing the session deletes the views it created.
+    table.view(None).await?;
+    assert_eq!(server.views.lock().unwrap().len(), 1);
+    let session = handler.session.write().await.take().unwrap();
+    session.close().await;
+    assert!(server.views.lock().unwrap().is_empty());
+    Ok(())
+}
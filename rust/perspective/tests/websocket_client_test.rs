@@ -0,0 +1,177 @@
+// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+// ┃ ██████ ██████ ██████       █      █      █      █      █ █▄  ▀███ █       ┃
+// ┃ ▄▄▄▄▄█ █▄▄▄▄▄ ▄▄▄▄▄█  ▀▀▀▀▀█▀▀▀▀▀ █ ▀▀▀▀▀█ ████████▌▐███ ███▄  ▀█ █ ▀▀▀▀▀ ┃
+// ┃ █▀▀▀▀▀ █▀▀▀▀▀ █▀██▀▀ ▄▄▄▄▄ █ ▄▄▄▄▄█ ▄▄▄▄▄█ ████████▌▐███ █████▄   █ ▄▄▄▄▄ ┃
+// ┃ █      ██████ █  ▀█▄       █ ██████      █      ███▌▐███ ███████▄ █       ┃
+// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+// ┃ Copyright (c) 2017, the Perspective Authors.                              ┃
+// ┃ ╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌╌ ┃
+// ┃ This file is part of the Perspective library, distributed under the terms ┃
+// ┃ of the [Apache License 2.0](https://www.apache.org/licenses/LICENSE-2.0). ┃
+// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+
+#![cfg(all(feature = "websocket-client", feature = "axum-ws"))]
+
+use std::error::Error;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use axum::Router;
+use futures::channel::mpsc::{unbounded, UnboundedReceiver};
+use futures::{FutureExt, StreamExt};
+use perspective::axum::{websocket_handler_with_config, WebsocketConfig};
+use perspective::client::{Client, ReconnectCallback};
+use perspective::server::Server;
+use perspective_client::{TableInitOptions, UpdateData};
+use perspective_server::LocalClient;
+
+async fn start_server(server: Server) -> Result<String, Box<dyn Error>> {
+    start_server_with_config(server, WebsocketConfig::default()).await
+}
+
+async fn start_server_with_config(
+    server: Server,
+    config: WebsocketConfig,
+) -> Result<String, Box<dyn Error>> {
+    let app = Router::new()
+        .route("/ws", websocket_handler_with_config(config))
+        .with_state(server);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}/ws", listener.local_addr()?);
+    tokio::spawn(async move {
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+    });
+
+    Ok(url)
+}
+
+/// A TCP proxy to `url` which accepts a single connection, returning the
+/// proxy's URL and a handle which resets the connection when aborted.
+async fn start_proxy(url: &str) -> Result<(String, tokio::task::JoinHandle<()>), Box<dyn Error>> {
+    let upstream_addr = url
+        .trim_start_matches("ws://")
+        .trim_end_matches("/ws")
+        .to_owned();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let proxy_url = format!("ws://{}/ws", listener.local_addr()?);
+    let task = tokio::spawn(async move {
+        let (mut downstream, _) = listener.accept().await.unwrap();
+        let mut upstream = tokio::net::TcpStream::connect(upstream_addr).await.unwrap();
+        let _ = tokio::io::copy_bidirectional(&mut downstream, &mut upstream).await;
+    });
+
+    Ok((proxy_url, task))
+}
+
+/// Collect the arguments of `client`'s [`Client::on_error`] callbacks.
+async fn on_error_channel(
+    client: &Client,
+) -> Result<UnboundedReceiver<(Option<String>, Option<ReconnectCallback>)>, Box<dyn Error>> {
+    let (sender, receiver) = unbounded();
+    client
+        .on_error(Box::new(move |message, reconnect| {
+            let _ = sender.unbounded_send((message, reconnect));
+            async { Ok(()) }.boxed()
+        }))
+        .await?;
+
+    Ok(receiver)
+}
+
+/// Wait for the [`Server`] to have `n` sessions.
+async fn wait_for_sessions(server: &Server, n: usize) -> Result<(), Box<dyn Error>> {
+    for _ in 0..100 {
+        if server.sessions().len() == n {
+            return Ok(());
+        }
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    Err(format!("Expected {} sessions", n).into())
+}
+
+#[tokio::test]
+async fn test_websocket_client_open_table() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let local = LocalClient::new(&server);
+    let mut options = TableInitOptions::default();
+    options.set_name("Table1");
+    local
+        .table(UpdateData::Csv("x,y\n1,2\n3,4".to_owned()).into(), options)
+        .await?;
+
+    let url = start_server(server).await?;
+    let client = perspective::connect(&url).await?;
+    assert_eq!(client.get_hosted_table_names().await?, vec!["Table1"]);
+    let table = client.open_table("Table1".to_owned()).await?;
+    assert_eq!(table.size().await?, 2);
+    local.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_websocket_client_server_close_reconnects() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let config = WebsocketConfig::default().idle_timeout(Duration::from_millis(200));
+    let url = start_server_with_config(server.clone(), config).await?;
+    let client = perspective::connect(&url).await?;
+    let mut errors = on_error_channel(&client).await?;
+    let (message, reconnect) = errors.next().await.ok_or("No error")?;
+    assert_eq!(message.as_deref(), Some("WebSocket closed"));
+    assert!(client.get_hosted_table_names().await.is_err());
+    reconnect.ok_or("No reconnect callback")?()
+        .await
+        .map_err(|err| err.to_string())?;
+
+    assert!(client.get_hosted_table_names().await?.is_empty());
+    client.close().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_websocket_client_connection_error() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let url = start_server(server.clone()).await?;
+    let (proxy_url, proxy) = start_proxy(&url).await?;
+    let client = perspective::connect(&proxy_url).await?;
+    let mut errors = on_error_channel(&client).await?;
+    proxy.abort();
+    let (message, reconnect) = errors.next().await.ok_or("No error")?;
+    assert!(
+        message
+            .ok_or("No message")?
+            .starts_with("WebSocket error: ")
+    );
+    assert!(reconnect.is_some());
+    wait_for_sessions(&server, 0).await
+}
+
+#[tokio::test]
+async fn test_websocket_client_close() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let url = start_server(server.clone()).await?;
+    let client = perspective::connect(&url).await?;
+    let mut errors = on_error_channel(&client).await?;
+    wait_for_sessions(&server, 1).await?;
+    client.close().await;
+    let (message, reconnect) = errors.next().await.ok_or("No error")?;
+    assert_eq!(message.as_deref(), Some("WebSocket closed by client"));
+    assert!(reconnect.is_none());
+    wait_for_sessions(&server, 0).await
+}
+
+#[tokio::test]
+async fn test_websocket_client_drop_closes_socket() -> Result<(), Box<dyn Error>> {
+    let server = Server::default();
+    let url = start_server(server.clone()).await?;
+    let client = perspective::connect(&url).await?;
+    wait_for_sessions(&server, 1).await?;
+    drop(client);
+    wait_for_sessions(&server, 0).await
+}